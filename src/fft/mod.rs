@@ -0,0 +1,248 @@
+//! Discrete Fourier transforms.
+//!
+//! Transforms of any length are supported. Lengths whose prime factors are all at most 13 use a
+//! mixed-radix Cooley-Tukey algorithm, where the pass for each factor `p` costs `O(n p)`. Other
+//! lengths fall back on Bluestein's algorithm, which costs `O(n log n)` through a transform of
+//! power-of-two length.
+//!
+//! The transforms are implemented as member functions of `Tensor` and operate along a single
+//! axis (`fft`, `ifft`, `rfft`, `irfft`), the last two axes (`fft2`, `ifft2`) or all axes
+//! (`fftn`, `ifftn`):
+//!
+//! ```
+//! use numeric::Tensor;
+//! use numeric::fft::{FftNorm, fftfreq};
+//!
+//! let t = Tensor::new(vec![1.0_f64, 2.0, 0.0, -1.0, 1.5]);
+//! let spectrum = t.rfft(0, FftNorm::Backward);
+//! let freqs = fftfreq(5, 0.1);
+//! ```
+
+use tensor::{Tensor, Full, StridedSlice};
+use traits::TensorTrait;
+use num::complex::Complex;
+use num::traits::Zero;
+
+mod plan;
+
+pub use self::plan::FftPlan;
+
+/// Determines how the transforms are scaled, following the conventions of Numpy.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FftNorm {
+    /// No scaling of the forward transform, `1/n` for the inverse.
+    Backward,
+    /// Both directions are scaled by `1/sqrt(n)`, making the transforms unitary.
+    Ortho,
+    /// `1/n` for the forward transform, no scaling of the inverse.
+    Forward,
+}
+
+impl FftNorm {
+    /// Returns the scale factor for a transform of length `n`.
+    fn scale(self, n: usize, inverse: bool) -> f64 {
+        match (self, inverse) {
+            (FftNorm::Backward, false) | (FftNorm::Forward, true) => 1.0,
+            (FftNorm::Backward, true) | (FftNorm::Forward, false) => 1.0 / n as f64,
+            (FftNorm::Ortho, _) => 1.0 / (n as f64).sqrt(),
+        }
+    }
+}
+
+/// Returns the sample frequencies of a transform of length `n`, with sample spacing `d`. The
+/// frequencies are in the same order as the output of `fft`, starting with zero, then the
+/// positive frequencies followed by the negative ones.
+pub fn fftfreq(n: usize, d: f64) -> Tensor<f64> {
+    let scale = 1.0 / (n as f64 * d);
+    let freqs = (0..n).map(|k| {
+        let k = if k < (n + 1) / 2 { k as isize } else { k as isize - n as isize };
+        k as f64 * scale
+    }).collect();
+    Tensor::new(freqs)
+}
+
+/// Returns the sample frequencies of `rfft` of a signal of length `n`, with sample spacing `d`.
+pub fn rfftfreq(n: usize, d: f64) -> Tensor<f64> {
+    let scale = 1.0 / (n as f64 * d);
+    Tensor::new((0..(n / 2 + 1)).map(|k| k as f64 * scale).collect())
+}
+
+/// Applies `f` to every lane along `axis` of a canonical tensor with the given shape.
+fn for_each_lane<T, F>(data: &mut [T], shape: &[usize], axis: usize, mut f: F)
+        where T: Copy + Zero, F: FnMut(&mut [T]) {
+    let n = shape[axis];
+    let inner: usize = shape[axis + 1..].iter().product();
+    let outer: usize = shape[..axis].iter().product();
+    let mut lane = vec![T::zero(); n];
+    for o in 0..outer {
+        for i in 0..inner {
+            let base = o * n * inner + i;
+            for k in 0..n {
+                lane[k] = data[base + k * inner];
+            }
+            f(&mut lane);
+            for k in 0..n {
+                data[base + k * inner] = lane[k];
+            }
+        }
+    }
+}
+
+impl<T: TensorTrait> Tensor<T> {
+    /// Rotates the elements by `shift` positions along `axis`.
+    fn roll_axis(&self, shift: usize, axis: usize) -> Tensor<T> {
+        let t = self.canonize();
+        let n = self.dim(axis);
+        if n == 0 {
+            return t;
+        }
+        let inner: usize = self.shape()[axis + 1..].iter().product();
+        let mut y = t.clone();
+        {
+            let src = t.slice();
+            let data = y.slice_mut();
+            for (p, v) in src.iter().enumerate() {
+                let k = (p / inner) % n;
+                let q = p + ((k + shift) % n) * inner - k * inner;
+                data[q] = *v;
+            }
+        }
+        y
+    }
+
+    /// Shifts the zero-frequency component to the center, along all axes.
+    pub fn fftshift(&self) -> Tensor<T> {
+        let mut t = self.canonize();
+        for axis in 0..self.ndim() {
+            t = t.roll_axis(self.dim(axis) / 2, axis);
+        }
+        t
+    }
+
+    /// The inverse of `fftshift`. The two only differ for odd lengths.
+    pub fn ifftshift(&self) -> Tensor<T> {
+        let mut t = self.canonize();
+        for axis in 0..self.ndim() {
+            let n = self.dim(axis);
+            t = t.roll_axis(n - n / 2, axis);
+        }
+        t
+    }
+}
+
+macro_rules! add_fft_impl {
+    ($t:ty) => (
+        impl Tensor<Complex<$t>> {
+            /// Transforms along `axis`, in the direction given by `inverse`.
+            fn transform_axis(&self, axis: usize, norm: FftNorm, inverse: bool)
+                    -> Tensor<Complex<$t>> {
+                assert!(axis < self.ndim(), "Axis out of range");
+                let n = self.dim(axis);
+                if n == 0 {
+                    return self.canonize();
+                }
+                let plan = FftPlan::<$t>::cached(n);
+                let scale = norm.scale(n, inverse) as $t;
+                let mut y = self.canonize();
+                let shape = y.shape().clone();
+                for_each_lane(y.slice_mut(), &shape, axis, |lane| {
+                    if inverse {
+                        plan.inverse(lane);
+                    } else {
+                        plan.forward(lane);
+                    }
+                    if scale != 1.0 {
+                        for v in lane.iter_mut() {
+                            *v = v.scale(scale);
+                        }
+                    }
+                });
+                y
+            }
+
+            /// Computes the one-dimensional discrete Fourier transform along `axis`.
+            pub fn fft(&self, axis: usize, norm: FftNorm) -> Tensor<Complex<$t>> {
+                self.transform_axis(axis, norm, false)
+            }
+
+            /// Computes the one-dimensional inverse discrete Fourier transform along `axis`.
+            pub fn ifft(&self, axis: usize, norm: FftNorm) -> Tensor<Complex<$t>> {
+                self.transform_axis(axis, norm, true)
+            }
+
+            /// Computes the two-dimensional transform over the last two axes.
+            pub fn fft2(&self, norm: FftNorm) -> Tensor<Complex<$t>> {
+                assert!(self.ndim() >= 2, "Tensor must have at least two dimensions");
+                let d = self.ndim();
+                self.fft(d - 2, norm).fft(d - 1, norm)
+            }
+
+            /// Computes the two-dimensional inverse transform over the last two axes.
+            pub fn ifft2(&self, norm: FftNorm) -> Tensor<Complex<$t>> {
+                assert!(self.ndim() >= 2, "Tensor must have at least two dimensions");
+                let d = self.ndim();
+                self.ifft(d - 2, norm).ifft(d - 1, norm)
+            }
+
+            /// Computes the n-dimensional transform over all axes.
+            pub fn fftn(&self, norm: FftNorm) -> Tensor<Complex<$t>> {
+                (0..self.ndim()).fold(self.canonize(), |t, axis| t.fft(axis, norm))
+            }
+
+            /// Computes the n-dimensional inverse transform over all axes.
+            pub fn ifftn(&self, norm: FftNorm) -> Tensor<Complex<$t>> {
+                (0..self.ndim()).fold(self.canonize(), |t, axis| t.ifft(axis, norm))
+            }
+
+            /// Computes the inverse of `rfft` along `axis`, returning a real signal of length
+            /// `n`. Only the first `n / 2 + 1` elements along the axis are used, and the
+            /// imaginary parts of the zero (and, for even `n`, the Nyquist) frequency are
+            /// ignored.
+            pub fn irfft(&self, n: usize, axis: usize, norm: FftNorm) -> Tensor<$t> {
+                assert!(axis < self.ndim(), "Axis out of range");
+                let m = n / 2 + 1;
+                assert!(self.dim(axis) >= m, "Input is too short for the output length");
+
+                // Rebuild the full Hermitian-symmetric spectrum
+                let mut shape = self.shape().clone();
+                shape[axis] = n;
+                let inner: usize = shape[axis + 1..].iter().product();
+                let src = self.canonize();
+                let src_len = self.dim(axis);
+                let mut full: Tensor<Complex<$t>> = Tensor::zeros(&shape);
+                {
+                    let s = src.slice();
+                    let data = full.slice_mut();
+                    for p in 0..data.len() {
+                        let o = p / (n * inner);
+                        let k = (p / inner) % n;
+                        let i = p % inner;
+                        let (kk, conj) = if k < m { (k, false) } else { (n - k, true) };
+                        let v = s[(o * src_len + kk) * inner + i];
+                        data[p] = if conj { v.conj() } else { v };
+                    }
+                }
+                full.ifft(axis, norm).real()
+            }
+        }
+
+        impl Tensor<$t> {
+            /// Computes the transform of a real signal along `axis`. Since the result is
+            /// Hermitian-symmetric, only the `n / 2 + 1` non-negative frequencies are returned.
+            pub fn rfft(&self, axis: usize, norm: FftNorm) -> Tensor<Complex<$t>> {
+                assert!(axis < self.ndim(), "Axis out of range");
+                let n = self.dim(axis);
+                let full = self.to_complex().fft(axis, norm);
+                if n == 0 {
+                    return full;
+                }
+                let mut sel = vec![Full; self.ndim()];
+                sel[axis] = StridedSlice(None, Some((n / 2 + 1) as isize), 1);
+                full.index(&sel).canonize()
+            }
+        }
+    )
+}
+
+add_fft_impl!(f32);
+add_fft_impl!(f64);
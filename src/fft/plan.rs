@@ -0,0 +1,178 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use num::complex::Complex;
+use num::traits::{Float, Zero, cast};
+
+/// Prime factors larger than this are handled by Bluestein's algorithm, since the generic
+/// butterfly is quadratic in the radix.
+const MAX_RADIX: usize = 13;
+
+enum Algorithm<T> {
+    /// Mixed-radix Cooley-Tukey, with the prime factors of the length.
+    MixedRadix(Vec<usize>),
+    /// Bluestein's algorithm, which rewrites the transform as a convolution computed with a
+    /// power-of-two transform. Holds the chirp and the transform of the convolution kernel.
+    Bluestein(Rc<FftPlan<T>>, Vec<Complex<T>>, Vec<Complex<T>>),
+}
+
+/// A precomputed plan for transforms of a given length. Plans are cached per thread and length,
+/// so repeated transforms of the same size do not recompute twiddle factors.
+pub struct FftPlan<T> {
+    n: usize,
+    twiddles: Vec<Complex<T>>,
+    algorithm: Algorithm<T>,
+}
+
+fn factorize(mut n: usize) -> Vec<usize> {
+    let mut factors = vec![];
+    // Radix 4 is cheaper than two passes of radix 2
+    while n > 1 && n % 4 == 0 {
+        factors.push(4);
+        n /= 4;
+    }
+    let mut p = 2;
+    while p * p <= n {
+        while n % p == 0 {
+            factors.push(p);
+            n /= p;
+        }
+        p += 1;
+    }
+    if n > 1 {
+        factors.push(n);
+    }
+    factors
+}
+
+impl<T: Float> FftPlan<T> {
+    /// Creates a plan for transforms of length `n`.
+    pub fn new(n: usize) -> FftPlan<T> {
+        let twiddles = (0..n).map(|k| {
+            let theta = -2.0 * PI * (k as f64) / (n as f64);
+            Complex::new(cast(theta.cos()).unwrap(), cast(theta.sin()).unwrap())
+        }).collect();
+
+        let factors = factorize(n);
+        let algorithm = if factors.iter().all(|&p| p <= MAX_RADIX) {
+            Algorithm::MixedRadix(factors)
+        } else {
+            let m = (2 * n - 1).next_power_of_two();
+            let inner = Rc::new(FftPlan::new(m));
+            // The chirp exp(-i pi k^2 / n), with k^2 reduced modulo 2n to preserve accuracy
+            let chirp: Vec<Complex<T>> = (0..n).map(|k| {
+                let k2 = (k * k) % (2 * n);
+                let theta = -PI * (k2 as f64) / (n as f64);
+                Complex::new(cast(theta.cos()).unwrap(), cast(theta.sin()).unwrap())
+            }).collect();
+            let mut kernel = vec![Complex::zero(); m];
+            kernel[0] = chirp[0].conj();
+            for k in 1..n {
+                kernel[k] = chirp[k].conj();
+                kernel[m - k] = chirp[k].conj();
+            }
+            inner.forward(&mut kernel);
+            Algorithm::Bluestein(inner, chirp, kernel)
+        };
+        FftPlan { n: n, twiddles: twiddles, algorithm: algorithm }
+    }
+
+    /// The transform length.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Computes the (unnormalized) forward transform in-place.
+    pub fn forward(&self, x: &mut [Complex<T>]) {
+        assert!(x.len() == self.n, "Input length does not match plan");
+        if self.n <= 1 {
+            return;
+        }
+        match self.algorithm {
+            Algorithm::MixedRadix(ref factors) => {
+                let input = x.to_vec();
+                self.mixed_radix(&input, 1, x, factors, 1);
+            },
+            Algorithm::Bluestein(ref inner, ref chirp, ref kernel) => {
+                let m = inner.len();
+                let mut a = vec![Complex::zero(); m];
+                for k in 0..self.n {
+                    a[k] = x[k] * chirp[k];
+                }
+                inner.forward(&mut a);
+                for k in 0..m {
+                    a[k] = a[k] * kernel[k];
+                }
+                inner.inverse(&mut a);
+                let scale: T = cast(m).unwrap();
+                for k in 0..self.n {
+                    x[k] = (a[k] * chirp[k]).unscale(scale);
+                }
+            },
+        }
+    }
+
+    /// Computes the (unnormalized) inverse transform in-place.
+    pub fn inverse(&self, x: &mut [Complex<T>]) {
+        // ifft(x) = conj(fft(conj(x)))
+        for v in x.iter_mut() {
+            *v = v.conj();
+        }
+        self.forward(x);
+        for v in x.iter_mut() {
+            *v = v.conj();
+        }
+    }
+
+    /// Recursive decimation-in-time step. Transforms `input[0], input[stride], ...` into `out`,
+    /// where `tstride` maps twiddle exponents of this sub-transform to the full length.
+    fn mixed_radix(&self, input: &[Complex<T>], stride: usize, out: &mut [Complex<T>],
+                   factors: &[usize], tstride: usize) {
+        let n = out.len();
+        if n == 1 {
+            out[0] = input[0];
+            return;
+        }
+        let p = factors[0];
+        let m = n / p;
+        for j in 0..p {
+            self.mixed_radix(&input[j * stride..], stride * p, &mut out[j * m..(j + 1) * m],
+                             &factors[1..], tstride * p);
+        }
+
+        // Radix-p butterflies
+        let mut tmp = vec![Complex::zero(); p];
+        for k in 0..m {
+            for q in 0..p {
+                let mut v = Complex::zero();
+                for j in 0..p {
+                    let e = (j * (k + q * m) * tstride) % self.n;
+                    v = v + out[j * m + k] * self.twiddles[e];
+                }
+                tmp[q] = v;
+            }
+            for q in 0..p {
+                out[q * m + k] = tmp[q];
+            }
+        }
+    }
+}
+
+macro_rules! add_cache_impl {
+    ($t:ty) => (
+        impl FftPlan<$t> {
+            /// Returns the plan for length `n` from the thread-local cache, creating it if needed.
+            pub fn cached(n: usize) -> Rc<FftPlan<$t>> {
+                thread_local!(static CACHE: RefCell<HashMap<usize, Rc<FftPlan<$t>>>> =
+                              RefCell::new(HashMap::new()));
+                CACHE.with(|cache| {
+                    cache.borrow_mut().entry(n).or_insert_with(|| Rc::new(FftPlan::new(n))).clone()
+                })
+            }
+        }
+    )
+}
+
+add_cache_impl!(f32);
+add_cache_impl!(f64);
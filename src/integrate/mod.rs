@@ -0,0 +1,163 @@
+//! Numerical integration.
+//!
+//! Sampled data can be integrated along an axis with `trapz`, `simpson` and
+//! `cumulative_trapezoid`, while functions are integrated with `quad` (adaptive Gauss-Kronrod)
+//! or `fixed_quad` (Gauss-Legendre):
+//!
+//! ```
+//! use numeric::Tensor;
+//! use numeric::integrate::{trapz, quad, Spacing};
+//!
+//! let y = Tensor::new(vec![1.0, 2.0, 3.0]);
+//! assert!(trapz(&y, Spacing::Uniform(1.0), 0) == Tensor::scalar(4.0));
+//!
+//! let r = quad(|x: f64| x.sin(), 0.0, ::std::f64::consts::PI);
+//! assert!((r.value - 2.0).abs() < 1e-12 && r.error < 1e-10);
+//! ```
+//!
+//! Initial value problems for systems of ordinary differential equations are solved with
+//! `solve_ivp`:
+//!
+//! ```
+//! use numeric::Tensor;
+//! use numeric::integrate::{solve_ivp, OdeMethod};
+//!
+//! let res = solve_ivp(|_t, y: &Tensor<f64>| -y, (0.0, 1.0), &Tensor::new(vec![1.0]),
+//!                     OdeMethod::Rk45);
+//! let n = res.t.size();
+//! assert!((res.y[(n - 1, 0)] - (-1.0f64).exp()).abs() < 1e-3);
+//! ```
+
+use tensor::Tensor;
+
+mod quadrature;
+mod ode;
+
+pub use self::quadrature::{QuadResult, quad, quad_tol, gauss_legendre, fixed_quad};
+pub use self::ode::{solve_ivp, solve_ivp_options, OdeMethod, OdeOptions, OdeResult, OdeSolution,
+                    OdeStatus, Event};
+
+/// The sample points of data passed to `trapz`, `simpson` and `cumulative_trapezoid`.
+#[derive(Copy, Clone)]
+pub enum Spacing<'a> {
+    /// The samples are evenly spaced, with the given distance.
+    Uniform(f64),
+    /// The sample points, as a vector with the same length as the integrated axis.
+    Points(&'a Tensor<f64>),
+}
+
+impl<'a> Spacing<'a> {
+    /// Returns the widths of the `n - 1` intervals between `n` samples.
+    fn widths(&self, n: usize) -> Vec<f64> {
+        match *self {
+            Spacing::Uniform(dx) => vec![dx; if n > 0 { n - 1 } else { 0 }],
+            Spacing::Points(x) => {
+                assert!(x.ndim() == 1 && x.size() == n,
+                        "Sample points must be a vector matching the integrated axis");
+                let x: Vec<f64> = x.iter().collect();
+                x.windows(2).map(|w| w[1] - w[0]).collect()
+            },
+        }
+    }
+}
+
+/// Applies `f` to each lane of `y` along `axis`, where `f` writes `len` values. Returns a
+/// tensor with the same shape as `y`, except that `axis` has length `len`.
+fn map_lanes<F>(y: &Tensor<f64>, axis: usize, len: usize, f: F) -> Tensor<f64>
+        where F: Fn(&[f64], &mut [f64]) {
+    assert!(axis < y.ndim(), "Axis out of range");
+    let n = y.dim(axis);
+    let inner: usize = y.shape()[axis + 1..].iter().product();
+    let outer: usize = y.shape()[..axis].iter().product();
+    let mut shape = y.shape().clone();
+    shape[axis] = len;
+    let src = y.canonize();
+    let src = src.slice();
+    let mut out = Tensor::zeros(&shape);
+    {
+        let data = out.slice_mut();
+        let mut lane = vec![0.0; n];
+        let mut result = vec![0.0; len];
+        for o in 0..outer {
+            for i in 0..inner {
+                for k in 0..n {
+                    lane[k] = src[(o * n + k) * inner + i];
+                }
+                f(&lane, &mut result);
+                for k in 0..len {
+                    data[(o * len + k) * inner + i] = result[k];
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Removes `axis`, which must have length one.
+fn squeeze(t: Tensor<f64>, axis: usize) -> Tensor<f64> {
+    let shape: Vec<isize> = t.shape().iter().enumerate()
+                             .filter(|&(k, _)| k != axis).map(|(_, &d)| d as isize).collect();
+    t.reshape(&shape)
+}
+
+/// Integrates `y` along `axis` using the composite trapezoidal rule. The result has one
+/// dimension less than `y`.
+pub fn trapz(y: &Tensor<f64>, spacing: Spacing, axis: usize) -> Tensor<f64> {
+    let h = spacing.widths(y.dim(axis));
+    squeeze(map_lanes(y, axis, 1, |lane, out| {
+        out[0] = h.iter().enumerate().map(|(k, &hk)| 0.5 * hk * (lane[k] + lane[k + 1])).sum();
+    }), axis)
+}
+
+/// Integrates `y` along `axis` using the composite Simpson's rule, which is exact for
+/// quadratics. Unevenly spaced samples are supported. For an odd number of intervals, the last
+/// interval is integrated with a quadratic through the last three points.
+pub fn simpson(y: &Tensor<f64>, spacing: Spacing, axis: usize) -> Tensor<f64> {
+    let n = y.dim(axis);
+    let h = spacing.widths(n);
+    squeeze(map_lanes(y, axis, 1, |f, out| {
+        if n < 3 {
+            out[0] = if n == 2 { 0.5 * h[0] * (f[0] + f[1]) } else { 0.0 };
+            return;
+        }
+        let intervals = n - 1;
+        let mut s = 0.0;
+        let mut k = 0;
+        while k + 2 <= intervals - intervals % 2 {
+            let (h0, h1) = (h[k], h[k + 1]);
+            s += (h0 + h1) / 6.0 * ((2.0 - h1 / h0) * f[k] +
+                                    (h0 + h1) * (h0 + h1) / (h0 * h1) * f[k + 1] +
+                                    (2.0 - h0 / h1) * f[k + 2]);
+            k += 2;
+        }
+        if intervals % 2 == 1 {
+            let (h0, h1) = (h[n - 3], h[n - 2]);
+            let alpha = (2.0 * h1 * h1 + 3.0 * h0 * h1) / (6.0 * (h0 + h1));
+            let beta = (h1 * h1 + 3.0 * h0 * h1) / (6.0 * h0);
+            let eta = h1 * h1 * h1 / (6.0 * h0 * (h0 + h1));
+            s += alpha * f[n - 1] + beta * f[n - 2] - eta * f[n - 3];
+        }
+        out[0] = s;
+    }), axis)
+}
+
+/// Computes the running integral of `y` along `axis` using the trapezoidal rule. The result has
+/// one element less than `y` along `axis`, unless an `initial` value is given. In that case, the
+/// integral starts from `initial`, which is also prepended to the result.
+pub fn cumulative_trapezoid(y: &Tensor<f64>, spacing: Spacing, axis: usize,
+                            initial: Option<f64>) -> Tensor<f64> {
+    let n = y.dim(axis);
+    let h = spacing.widths(n);
+    let start = if initial.is_some() { 1 } else { 0 };
+    let len = if n > 0 { n - 1 + start } else { start };
+    map_lanes(y, axis, len, |f, out| {
+        let mut s = initial.unwrap_or(0.0);
+        if let Some(v) = initial {
+            out[0] = v;
+        }
+        for k in 0..h.len() {
+            s += 0.5 * h[k] * (f[k] + f[k + 1]);
+            out[k + start] = s;
+        }
+    })
+}
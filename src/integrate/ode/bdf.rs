@@ -0,0 +1,311 @@
+use std::f64;
+use tensor::Tensor;
+use linalg::LuFactorization;
+use super::{Rhs, Stepper, OdeOptions, rms, min_step};
+
+const MAX_ORDER: usize = 5;
+const NEWTON_MAXITER: usize = 4;
+const MIN_FACTOR: f64 = 0.2;
+const MAX_FACTOR: f64 = 10.0;
+
+/// Computes the matrix that changes the step size of the difference array by `factor`.
+fn compute_r(order: usize, factor: f64) -> Vec<Vec<f64>> {
+    let mut m = vec![vec![0.0; order + 1]; order + 1];
+    for j in 0..order + 1 {
+        m[0][j] = 1.0;
+    }
+    for i in 1..order + 1 {
+        for j in 1..order + 1 {
+            m[i][j] = (i as f64 - 1.0 - factor * j as f64) / i as f64;
+        }
+    }
+    // Cumulative product down the columns
+    for i in 1..order + 1 {
+        for j in 0..order + 1 {
+            m[i][j] *= m[i - 1][j];
+        }
+    }
+    m
+}
+
+/// Rescales the backward differences `d` for a step size multiplied by `factor`.
+fn change_d(d: &mut Vec<Vec<f64>>, order: usize, factor: f64) {
+    let r = compute_r(order, factor);
+    let u = compute_r(order, 1.0);
+    let k = order + 1;
+    let mut ru = vec![vec![0.0; k]; k];
+    for i in 0..k {
+        for j in 0..k {
+            ru[i][j] = (0..k).map(|l| r[i][l] * u[l][j]).sum();
+        }
+    }
+    let n = d[0].len();
+    let old: Vec<Vec<f64>> = d[..k].to_vec();
+    for i in 0..k {
+        for p in 0..n {
+            d[i][p] = (0..k).map(|j| ru[j][i] * old[j][p]).sum();
+        }
+    }
+}
+
+/// The variable order BDF method, following the implementation in Scipy. The state is kept as
+/// a table of backward differences of the solution.
+pub struct Bdf {
+    h_abs: f64,
+    rtol: f64,
+    atol: f64,
+    max_step: f64,
+    direction: f64,
+    newton_tol: f64,
+    order: usize,
+    n_equal_steps: usize,
+    d: Vec<Vec<f64>>,
+    gamma: Vec<f64>,
+    alpha: Vec<f64>,
+    error_const: Vec<f64>,
+    /// The Jacobian, row-major.
+    jac: Vec<f64>,
+    /// Whether the Jacobian was computed at the current step.
+    current_jac: bool,
+    lu: Option<LuFactorization<f64>>,
+}
+
+impl Bdf {
+    pub fn new(rhs: &Rhs, t0: f64, y0: &[f64], f0: &[f64], h_abs: f64, options: &OdeOptions,
+               direction: f64) -> Bdf {
+        let n = y0.len();
+        let h_abs = h_abs.min(options.max_step);
+        let kappa = [0.0, -0.1850, -1.0 / 9.0, -0.0823, -0.0415, 0.0];
+        let mut gamma = vec![0.0; MAX_ORDER + 1];
+        for k in 1..MAX_ORDER + 1 {
+            gamma[k] = gamma[k - 1] + 1.0 / k as f64;
+        }
+        let alpha = (0..MAX_ORDER + 1).map(|k| (1.0 - kappa[k]) * gamma[k]).collect();
+        let error_const = (0..MAX_ORDER + 1).map(|k| kappa[k] * gamma[k] + 1.0 / (k + 1) as f64)
+                                            .collect();
+        let mut d = vec![vec![0.0; n]; MAX_ORDER + 3];
+        d[0] = y0.to_vec();
+        d[1] = f0.iter().map(|f| f * h_abs * direction).collect();
+        Bdf {
+            h_abs: h_abs,
+            rtol: options.rtol,
+            atol: options.atol,
+            max_step: options.max_step,
+            direction: direction,
+            newton_tol: (10.0 * f64::EPSILON / options.rtol).max(0.03f64.min(options.rtol.sqrt())),
+            order: 1,
+            n_equal_steps: 0,
+            d: d,
+            gamma: gamma,
+            alpha: alpha,
+            error_const: error_const,
+            jac: jacobian(rhs, t0, y0, f0),
+            current_jac: true,
+            lu: None,
+        }
+    }
+
+    /// Solves the implicit equations of a step with a simplified Newton iteration. Returns
+    /// whether it converged, the number of iterations, the new state and its difference from
+    /// the predicted state.
+    fn solve_system(&self, rhs: &Rhs, t_new: f64, y_predict: &[f64], c: f64, psi: &[f64],
+                    scale: &[f64]) -> (bool, usize, Vec<f64>, Vec<f64>) {
+        let n = y_predict.len();
+        let lu = self.lu.as_ref().unwrap();
+        let mut y = y_predict.to_vec();
+        let mut d = vec![0.0; n];
+        let mut dy_norm_old: Option<f64> = None;
+        for k in 0..NEWTON_MAXITER {
+            let f = rhs.eval(t_new, &y);
+            if !f.iter().all(|v| v.is_finite()) {
+                return (false, k + 1, y, d);
+            }
+            let b: Vec<f64> = (0..n).map(|i| c * f[i] - psi[i] - d[i]).collect();
+            let dy: Vec<f64> = match lu.solve(&Tensor::new(b)) {
+                Ok(x) => x.iter().collect(),
+                Err(_) => return (false, k + 1, y, d),
+            };
+            let dy_norm = rms(&(0..n).map(|i| dy[i] / scale[i]).collect::<Vec<f64>>());
+            let rate = dy_norm_old.map(|old| dy_norm / old);
+            if let Some(rate) = rate {
+                if rate >= 1.0 ||
+                   rate.powi((NEWTON_MAXITER - k) as i32) / (1.0 - rate) * dy_norm > self.newton_tol {
+                    return (false, k + 1, y, d);
+                }
+            }
+            for i in 0..n {
+                y[i] += dy[i];
+                d[i] += dy[i];
+            }
+            let done = match rate {
+                Some(rate) => rate / (1.0 - rate) * dy_norm < self.newton_tol,
+                None => false,
+            };
+            if dy_norm == 0.0 || done {
+                return (true, k + 1, y, d);
+            }
+            dy_norm_old = Some(dy_norm);
+        }
+        (false, NEWTON_MAXITER, y, d)
+    }
+
+    /// Factorizes `I - c J`.
+    fn factorize(&mut self, rhs: &Rhs, c: f64) {
+        let n = self.d[0].len();
+        let mut m = Tensor::zeros(&[n, n]);
+        {
+            let data = m.slice_mut();
+            for i in 0..n * n {
+                data[i] = -c * self.jac[i];
+            }
+            for i in 0..n {
+                data[i * n + i] += 1.0;
+            }
+        }
+        rhs.nlu.set(rhs.nlu.get() + 1);
+        self.lu = Some(m.lu());
+    }
+}
+
+/// Approximates the Jacobian of `f` at `(t, y)` with forward differences, where `fy` is
+/// `f(t, y)`. Returns it in row-major order.
+fn jacobian(rhs: &Rhs, t: f64, y: &[f64], fy: &[f64]) -> Vec<f64> {
+    let n = y.len();
+    rhs.njev.set(rhs.njev.get() + 1);
+    let mut jac = vec![0.0; n * n];
+    let mut yh = y.to_vec();
+    for j in 0..n {
+        let h = f64::EPSILON.sqrt() * y[j].abs().max(1.0);
+        yh[j] = y[j] + h;
+        let h = yh[j] - y[j];
+        let fh = rhs.eval(t, &yh);
+        for i in 0..n {
+            jac[i * n + j] = (fh[i] - fy[i]) / h;
+        }
+        yh[j] = y[j];
+    }
+    jac
+}
+
+impl Stepper for Bdf {
+    fn step(&mut self, rhs: &Rhs, t: f64, _y: &[f64], _fy: &[f64], t_bound: f64)
+            -> Option<(f64, Vec<f64>, Vec<f64>)> {
+        let n = self.d[0].len();
+        let min_step = min_step(t);
+        let mut order = self.order;
+        let mut h_abs = self.h_abs;
+        if h_abs > self.max_step {
+            h_abs = self.max_step;
+            change_d(&mut self.d, order, self.max_step / self.h_abs);
+            self.n_equal_steps = 0;
+        } else if h_abs < min_step {
+            h_abs = min_step;
+            change_d(&mut self.d, order, min_step / self.h_abs);
+            self.n_equal_steps = 0;
+        }
+        self.current_jac = false;
+
+        let (t_new, y_new, d, error_norm, safety, scale) = loop {
+            if h_abs < min_step {
+                return None;
+            }
+            let mut t_new = t + h_abs * self.direction;
+            if (t_new - t_bound) * self.direction > 0.0 {
+                t_new = t_bound;
+                change_d(&mut self.d, order, (t_new - t).abs() / h_abs);
+                self.n_equal_steps = 0;
+                self.lu = None;
+            }
+            let h = t_new - t;
+            h_abs = h.abs();
+
+            let y_predict: Vec<f64> = (0..n).map(|i| (0..order + 1).map(|k| self.d[k][i]).sum())
+                                            .collect();
+            let scale: Vec<f64> = y_predict.iter().map(|y| self.atol + self.rtol * y.abs())
+                                           .collect();
+            let psi: Vec<f64> = (0..n).map(|i| {
+                (1..order + 1).map(|k| self.d[k][i] * self.gamma[k]).sum::<f64>() / self.alpha[order]
+            }).collect();
+            let c = h / self.alpha[order];
+
+            let mut result;
+            loop {
+                if self.lu.is_none() {
+                    self.factorize(rhs, c);
+                }
+                result = self.solve_system(rhs, t_new, &y_predict, c, &psi, &scale);
+                if result.0 || self.current_jac {
+                    break;
+                }
+                let f = rhs.eval(t_new, &y_predict);
+                self.jac = jacobian(rhs, t_new, &y_predict, &f);
+                self.current_jac = true;
+                self.lu = None;
+            }
+            let (converged, n_iter, y_new, d) = result;
+            if !converged {
+                h_abs *= 0.5;
+                change_d(&mut self.d, order, 0.5);
+                self.n_equal_steps = 0;
+                self.lu = None;
+                continue;
+            }
+
+            let safety = 0.9 * (2 * NEWTON_MAXITER + 1) as f64 / (2 * NEWTON_MAXITER + n_iter) as f64;
+            let scale: Vec<f64> = y_new.iter().map(|y| self.atol + self.rtol * y.abs()).collect();
+            let error_norm = rms(&(0..n).map(|i| self.error_const[order] * d[i] / scale[i])
+                                        .collect::<Vec<f64>>());
+            if error_norm > 1.0 {
+                let factor = MIN_FACTOR.max(safety * error_norm.powf(-1.0 / (order + 1) as f64));
+                h_abs *= factor;
+                change_d(&mut self.d, order, factor);
+                self.n_equal_steps = 0;
+                // The Newton iteration converged, so the factorization is kept
+                continue;
+            }
+            break (t_new, y_new, d, error_norm, safety, scale);
+        };
+
+        self.n_equal_steps += 1;
+        self.h_abs = h_abs;
+        for i in 0..n {
+            self.d[order + 2][i] = d[i] - self.d[order + 1][i];
+            self.d[order + 1][i] = d[i];
+        }
+        for k in (0..order + 1).rev() {
+            for i in 0..n {
+                self.d[k][i] += self.d[k + 1][i];
+            }
+        }
+
+        // Consider changing the order once enough steps of the same size have been taken
+        if self.n_equal_steps >= order + 1 {
+            let norm = |k: usize, row: usize| {
+                rms(&(0..n).map(|i| self.error_const[k] * self.d[row][i] / scale[i])
+                           .collect::<Vec<f64>>())
+            };
+            let error_m = if order > 1 { norm(order - 1, order) } else { f64::INFINITY };
+            let error_p = if order < MAX_ORDER { norm(order + 1, order + 2) } else { f64::INFINITY };
+            let norms = [error_m, error_norm, error_p];
+            let mut best = 1;
+            let mut best_factor = 0.0;
+            for (j, e) in norms.iter().enumerate() {
+                let f = e.powf(-1.0 / (order + j) as f64);
+                if f > best_factor {
+                    best = j;
+                    best_factor = f;
+                }
+            }
+            order = order + best - 1;
+            let factor = MAX_FACTOR.min(safety * best_factor);
+            self.h_abs *= factor;
+            change_d(&mut self.d, order, factor);
+            self.n_equal_steps = 0;
+            self.lu = None;
+        }
+        self.order = order;
+
+        let f_new = rhs.eval(t_new, &y_new);
+        Some((t_new, y_new, f_new))
+    }
+}
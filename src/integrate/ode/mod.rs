@@ -0,0 +1,386 @@
+//! Initial value problems for systems of ordinary differential equations.
+
+use std::cell::Cell;
+use std::f64;
+use tensor::Tensor;
+
+mod dop853;
+mod rk;
+mod bdf;
+
+use self::rk::{Rk4, AdaptiveRk};
+use self::bdf::Bdf;
+
+/// The integration method used by `solve_ivp`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum OdeMethod {
+    /// The classical 4th order Runge-Kutta method with a fixed step. The step is the largest
+    /// one not exceeding the given value that divides the interval evenly.
+    Rk4(f64),
+    /// The explicit Runge-Kutta method of order 5(4) of Dormand and Prince, with adaptive steps.
+    Rk45,
+    /// The explicit Runge-Kutta method of order 8 of Dormand and Prince, with adaptive steps.
+    /// Recommended for tight tolerances.
+    Dop853,
+    /// An implicit multistep method of variable order (1 to 5), based on backward
+    /// differentiation formulas. Suited for stiff problems. The Jacobian is approximated with
+    /// finite differences.
+    Bdf,
+}
+
+/// A function of `(t, y)` whose zeros are located during integration.
+pub struct Event {
+    function: Box<dyn Fn(f64, &Tensor<f64>) -> f64>,
+    terminal: bool,
+    direction: f64,
+}
+
+impl Event {
+    /// Creates a non-terminal event that triggers on zeros in either direction.
+    pub fn new<G: Fn(f64, &Tensor<f64>) -> f64 + 'static>(function: G) -> Event {
+        Event { function: Box::new(function), terminal: false, direction: 0.0 }
+    }
+
+    /// Sets whether the integration stops at the first occurrence of the event.
+    pub fn terminal(mut self, terminal: bool) -> Event {
+        self.terminal = terminal;
+        self
+    }
+
+    /// Restricts the event to zeros where the function goes from negative to positive
+    /// (`direction > 0`) or from positive to negative (`direction < 0`).
+    pub fn direction(mut self, direction: f64) -> Event {
+        self.direction = direction;
+        self
+    }
+
+    fn triggers(&self, g0: f64, g1: f64) -> bool {
+        let up = g0 < 0.0 && g1 >= 0.0;
+        let down = g0 > 0.0 && g1 <= 0.0;
+        (up && self.direction >= 0.0) || (down && self.direction <= 0.0)
+    }
+}
+
+/// Options for `solve_ivp_options`.
+pub struct OdeOptions {
+    /// Relative tolerance of the adaptive methods.
+    pub rtol: f64,
+    /// Absolute tolerance of the adaptive methods.
+    pub atol: f64,
+    /// The largest allowed step size of the adaptive methods.
+    pub max_step: f64,
+    /// The initial step size. Chosen automatically if `None`.
+    pub first_step: Option<f64>,
+    /// Times at which to store the solution. If `None`, every step is stored.
+    pub t_eval: Option<Vec<f64>>,
+    /// Whether to return a continuous solution in `OdeResult::sol`.
+    pub dense_output: bool,
+    /// Events to locate.
+    pub events: Vec<Event>,
+}
+
+impl Default for OdeOptions {
+    fn default() -> OdeOptions {
+        OdeOptions {
+            rtol: 1e-3,
+            atol: 1e-6,
+            max_step: f64::INFINITY,
+            first_step: None,
+            t_eval: None,
+            dense_output: false,
+            events: vec![],
+        }
+    }
+}
+
+/// Why the integration stopped.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum OdeStatus {
+    /// The end of the interval was reached.
+    Finished,
+    /// A terminal event occurred.
+    Terminated,
+    /// The step size became too small (or the Newton iteration of `Bdf` kept failing).
+    StepTooSmall,
+}
+
+/// A continuous solution, interpolating between the steps with cubic Hermite polynomials.
+pub struct OdeSolution {
+    t: Vec<f64>,
+    y: Vec<Vec<f64>>,
+    f: Vec<Vec<f64>>,
+}
+
+impl OdeSolution {
+    /// Evaluates the solution at `t`. Times outside of the integrated interval are
+    /// extrapolated from the first or last step.
+    pub fn eval(&self, t: f64) -> Tensor<f64> {
+        Tensor::new(self.eval_vec(t))
+    }
+
+    /// Evaluates the solution at each time in `t`, returning a tensor of shape `[m, n_state]`.
+    pub fn eval_many(&self, t: &Tensor<f64>) -> Tensor<f64> {
+        let n = self.y[0].len();
+        let mut data = Vec::with_capacity(t.size() * n);
+        for ti in t.iter() {
+            data.extend(self.eval_vec(ti));
+        }
+        Tensor::new(data).reshape(&[t.size() as isize, n as isize])
+    }
+
+    fn eval_vec(&self, t: f64) -> Vec<f64> {
+        let m = self.t.len();
+        if m == 1 {
+            return self.y[0].clone();
+        }
+        let dir = if self.t[m - 1] >= self.t[0] { 1.0 } else { -1.0 };
+        // The number of steps at or before t
+        let (mut lo, mut hi) = (0, m);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if (self.t[mid] - t) * dir <= 0.0 {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        let i = if lo == 0 { 0 } else if lo >= m - 1 { m - 2 } else { lo - 1 };
+        hermite(self.t[i], &self.y[i], &self.f[i], self.t[i + 1], &self.y[i + 1],
+                &self.f[i + 1], t)
+    }
+}
+
+/// The result of `solve_ivp`.
+pub struct OdeResult {
+    /// The times of the stored solution.
+    pub t: Tensor<f64>,
+    /// The solution, with shape `[n_steps, n_state]`.
+    pub y: Tensor<f64>,
+    /// For each event, the times at which it occurred.
+    pub t_events: Vec<Vec<f64>>,
+    /// For each event, the state at each occurrence.
+    pub y_events: Vec<Vec<Tensor<f64>>>,
+    /// The continuous solution, if `dense_output` was requested.
+    pub sol: Option<OdeSolution>,
+    /// The number of evaluations of the right-hand side (including for the Jacobian).
+    pub nfev: usize,
+    /// The number of Jacobian approximations.
+    pub njev: usize,
+    /// The number of LU factorizations.
+    pub nlu: usize,
+    /// Why the integration stopped.
+    pub status: OdeStatus,
+}
+
+/// Wraps the right-hand side and counts evaluations.
+struct Rhs<'a> {
+    f: &'a dyn Fn(f64, &Tensor<f64>) -> Tensor<f64>,
+    nfev: Cell<usize>,
+    njev: Cell<usize>,
+    nlu: Cell<usize>,
+}
+
+impl<'a> Rhs<'a> {
+    fn eval(&self, t: f64, y: &[f64]) -> Vec<f64> {
+        self.nfev.set(self.nfev.get() + 1);
+        let dy = (self.f)(t, &Tensor::new(y.to_vec()));
+        assert!(dy.size() == y.len(), "Right-hand side must have the same size as the state");
+        dy.iter().collect()
+    }
+}
+
+/// Advances the solution by one step.
+trait Stepper {
+    /// Takes a step from `(t, y)`, where `fy` is the derivative, without passing `t_bound`.
+    /// Returns the new time, state and derivative, or `None` if the step size became too small.
+    fn step(&mut self, rhs: &Rhs, t: f64, y: &[f64], fy: &[f64], t_bound: f64)
+            -> Option<(f64, Vec<f64>, Vec<f64>)>;
+}
+
+/// The root mean square norm.
+fn rms(x: &[f64]) -> f64 {
+    (x.iter().map(|v| v * v).sum::<f64>() / x.len().max(1) as f64).sqrt()
+}
+
+/// The smallest allowed step size at `t`.
+fn min_step(t: f64) -> f64 {
+    10.0 * f64::EPSILON * t.abs().max(f64::MIN_POSITIVE)
+}
+
+/// Chooses an initial step size for a method of the given order, as suggested by Hairer,
+/// Norsett and Wanner. Returns the absolute value.
+fn initial_step(rhs: &Rhs, t0: f64, y0: &[f64], f0: &[f64], direction: f64, order: f64,
+                rtol: f64, atol: f64) -> f64 {
+    if y0.is_empty() {
+        return f64::INFINITY;
+    }
+    let scale: Vec<f64> = y0.iter().map(|y| atol + y.abs() * rtol).collect();
+    let d0 = rms(&y0.iter().zip(scale.iter()).map(|(y, s)| y / s).collect::<Vec<f64>>());
+    let d1 = rms(&f0.iter().zip(scale.iter()).map(|(f, s)| f / s).collect::<Vec<f64>>());
+    let h0 = if d0 < 1e-5 || d1 < 1e-5 { 1e-6 } else { 0.01 * d0 / d1 };
+    let y1: Vec<f64> = y0.iter().zip(f0.iter()).map(|(y, f)| y + h0 * direction * f).collect();
+    let f1 = rhs.eval(t0 + h0 * direction, &y1);
+    let d2 = rms(&f1.iter().zip(f0.iter()).zip(scale.iter())
+                    .map(|((a, b), s)| (a - b) / s).collect::<Vec<f64>>()) / h0;
+    let h1 = if d1 <= 1e-15 && d2 <= 1e-15 {
+        (h0 * 1e-3).max(1e-6)
+    } else {
+        (0.01 / d1.max(d2)).powf(1.0 / (order + 1.0))
+    };
+    (100.0 * h0).min(h1)
+}
+
+/// Cubic Hermite interpolation between `(t0, y0)` and `(t1, y1)` with derivatives `f0`, `f1`.
+fn hermite(t0: f64, y0: &[f64], f0: &[f64], t1: f64, y1: &[f64], f1: &[f64], t: f64)
+        -> Vec<f64> {
+    let h = t1 - t0;
+    let s = (t - t0) / h;
+    let h00 = (1.0 + 2.0 * s) * (1.0 - s) * (1.0 - s);
+    let h10 = s * (1.0 - s) * (1.0 - s);
+    let h01 = s * s * (3.0 - 2.0 * s);
+    let h11 = s * s * (s - 1.0);
+    (0..y0.len()).map(|i| h00 * y0[i] + h10 * h * f0[i] + h01 * y1[i] + h11 * h * f1[i])
+                 .collect()
+}
+
+/// Locates a zero of `g` between `t0` and `t1`, where it changes sign, by bisection.
+fn find_root<G: Fn(f64) -> f64>(g: G, mut t0: f64, mut t1: f64, mut g0: f64) -> f64 {
+    for _ in 0..200 {
+        let mid = 0.5 * (t0 + t1);
+        if mid == t0 || mid == t1 {
+            break;
+        }
+        let gm = g(mid);
+        if (gm <= 0.0) == (g0 <= 0.0) && gm != 0.0 {
+            t0 = mid;
+            g0 = gm;
+        } else {
+            t1 = mid;
+        }
+    }
+    t1
+}
+
+/// Solves the initial value problem `dy/dt = f(t, y)`, `y(t_span.0) = y0`, on the interval
+/// `t_span` with the default options. Integration backwards in time is supported.
+pub fn solve_ivp<F>(f: F, t_span: (f64, f64), y0: &Tensor<f64>, method: OdeMethod) -> OdeResult
+        where F: Fn(f64, &Tensor<f64>) -> Tensor<f64> {
+    solve_ivp_options(f, t_span, y0, method, OdeOptions::default())
+}
+
+/// Solves the initial value problem `dy/dt = f(t, y)`, `y(t_span.0) = y0`, on the interval
+/// `t_span`.
+pub fn solve_ivp_options<F>(f: F, t_span: (f64, f64), y0: &Tensor<f64>, method: OdeMethod,
+                            options: OdeOptions) -> OdeResult
+        where F: Fn(f64, &Tensor<f64>) -> Tensor<f64> {
+    assert!(y0.ndim() == 1, "Initial state must be a vector (1D)");
+    assert!(options.rtol > 0.0 && options.atol >= 0.0, "Tolerances must be positive");
+    let (t0, t_end) = t_span;
+    let direction = if t_end >= t0 { 1.0 } else { -1.0 };
+    let rhs = Rhs { f: &f, nfev: Cell::new(0), njev: Cell::new(0), nlu: Cell::new(0) };
+    let n = y0.size();
+    let mut y: Vec<f64> = y0.iter().collect();
+    let mut fy = rhs.eval(t0, &y);
+    let mut t = t0;
+
+    let first_step = |order: f64| match options.first_step {
+        Some(h) => h.abs(),
+        None => initial_step(&rhs, t0, &y, &fy, direction, order, options.rtol, options.atol),
+    };
+    let mut stepper: Box<dyn Stepper> = match method {
+        OdeMethod::Rk4(h) => Box::new(Rk4::new(h, t0, t_end)),
+        OdeMethod::Rk45 => Box::new(AdaptiveRk::rk45(first_step(4.0), &options, direction)),
+        OdeMethod::Dop853 => Box::new(AdaptiveRk::dop853(first_step(7.0), &options, direction)),
+        OdeMethod::Bdf => Box::new(Bdf::new(&rhs, t0, &y, &fy, first_step(1.0), &options,
+                                            direction)),
+    };
+
+    let events = &options.events;
+    let eval_event = |i: usize, t: f64, y: &[f64]| (events[i].function)(t, &Tensor::new(y.to_vec()));
+    let mut g: Vec<f64> = (0..events.len()).map(|i| eval_event(i, t, &y)).collect();
+    let mut t_events = vec![vec![]; events.len()];
+    let mut y_events = vec![vec![]; events.len()];
+
+    let mut ts = vec![t];
+    let mut ys = vec![y.clone()];
+    let mut fs = vec![fy.clone()];
+    let mut status = OdeStatus::Finished;
+    while (t_end - t) * direction > 0.0 {
+        let (mut t_new, mut y_new, mut f_new) = match stepper.step(&rhs, t, &y, &fy, t_end) {
+            Some(s) => s,
+            None => {
+                status = OdeStatus::StepTooSmall;
+                break;
+            },
+        };
+
+        // Locate the events that occurred during the step, in order of time
+        let g_new: Vec<f64> = (0..events.len()).map(|i| eval_event(i, t_new, &y_new)).collect();
+        let mut found = vec![];
+        for i in 0..events.len() {
+            if events[i].triggers(g[i], g_new[i]) {
+                let te = find_root(|s| eval_event(i, s, &hermite(t, &y, &fy, t_new, &y_new,
+                                                                 &f_new, s)),
+                                   t, t_new, g[i]);
+                found.push((te, i));
+            }
+        }
+        found.sort_by(|a, b| ((a.0 - b.0) * direction).partial_cmp(&0.0).unwrap());
+        let mut terminal = None;
+        for &(te, i) in found.iter() {
+            let ye = hermite(t, &y, &fy, t_new, &y_new, &f_new, te);
+            t_events[i].push(te);
+            y_events[i].push(Tensor::new(ye));
+            if events[i].terminal {
+                terminal = Some(te);
+                break;
+            }
+        }
+        if let Some(te) = terminal {
+            y_new = hermite(t, &y, &fy, t_new, &y_new, &f_new, te);
+            f_new = rhs.eval(te, &y_new);
+            t_new = te;
+            status = OdeStatus::Terminated;
+        }
+
+        t = t_new;
+        y = y_new;
+        fy = f_new;
+        g = g_new;
+        ts.push(t);
+        ys.push(y.clone());
+        fs.push(fy.clone());
+        if status == OdeStatus::Terminated {
+            break;
+        }
+    }
+
+    let sol = OdeSolution { t: ts, y: ys, f: fs };
+    let (t_out, y_out) = match options.t_eval {
+        Some(ref t_eval) => {
+            let t_last = sol.t[sol.t.len() - 1];
+            let t_out: Vec<f64> = t_eval.iter().cloned()
+                                        .filter(|&s| (s - t0) * direction >= 0.0 &&
+                                                     (t_last - s) * direction >= 0.0)
+                                        .collect();
+            let mut y_out = Vec::with_capacity(t_out.len() * n);
+            for &s in t_out.iter() {
+                y_out.extend(sol.eval_vec(s));
+            }
+            (t_out, y_out)
+        },
+        None => (sol.t.clone(), sol.y.iter().flat_map(|v| v.iter().cloned()).collect()),
+    };
+    let steps = t_out.len();
+    OdeResult {
+        t: Tensor::new(t_out),
+        y: Tensor::new(y_out).reshape(&[steps as isize, n as isize]),
+        t_events: t_events,
+        y_events: y_events,
+        sol: if options.dense_output { Some(sol) } else { None },
+        nfev: rhs.nfev.get(),
+        njev: rhs.njev.get(),
+        nlu: rhs.nlu.get(),
+        status: status,
+    }
+}
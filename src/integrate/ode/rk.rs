@@ -0,0 +1,200 @@
+use std::f64;
+use super::{Rhs, Stepper, OdeOptions, rms, min_step};
+use super::dop853;
+
+/// Coefficients of the Dormand-Prince 5(4) method.
+const RK45_C: [f64; 6] = [0.0, 1.0 / 5.0, 3.0 / 10.0, 4.0 / 5.0, 8.0 / 9.0, 1.0];
+const RK45_A: [&'static [f64]; 6] = [
+    &[],
+    &[1.0 / 5.0],
+    &[3.0 / 40.0, 9.0 / 40.0],
+    &[44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0],
+    &[19372.0 / 6561.0, -25360.0 / 2187.0, 64448.0 / 6561.0, -212.0 / 729.0],
+    &[9017.0 / 3168.0, -355.0 / 33.0, 46732.0 / 5247.0, 49.0 / 176.0, -5103.0 / 18656.0],
+];
+const RK45_B: [f64; 6] = [35.0 / 384.0, 0.0, 500.0 / 1113.0, 125.0 / 192.0, -2187.0 / 6784.0,
+                          11.0 / 84.0];
+/// Difference between the 5th and 4th order weights. The last entry belongs to the derivative
+/// at the end of the step.
+const RK45_E: [f64; 7] = [71.0 / 57600.0, 0.0, -71.0 / 16695.0, 71.0 / 1920.0,
+                          -17253.0 / 339200.0, 22.0 / 525.0, -1.0 / 40.0];
+
+/// Step size controller constants.
+const SAFETY: f64 = 0.9;
+const MIN_FACTOR: f64 = 0.2;
+const MAX_FACTOR: f64 = 10.0;
+
+/// Computes the stages `k` of an explicit Runge-Kutta method with nodes `c` and matrix `a`,
+/// and returns `y + h sum_i b[i] k[i]`.
+fn rk_step(rhs: &Rhs, t: f64, y: &[f64], fy: &[f64], h: f64, c: &[f64], a: &[&[f64]],
+           b: &[f64], k: &mut Vec<Vec<f64>>) -> Vec<f64> {
+    k.clear();
+    k.push(fy.to_vec());
+    for s in 1..c.len() {
+        let ys: Vec<f64> = (0..y.len()).map(|i| {
+            y[i] + h * a[s].iter().enumerate().map(|(j, aj)| aj * k[j][i]).sum::<f64>()
+        }).collect();
+        let ks = rhs.eval(t + c[s] * h, &ys);
+        k.push(ks);
+    }
+    (0..y.len()).map(|i| y[i] + h * b.iter().enumerate().map(|(j, bj)| bj * k[j][i]).sum::<f64>())
+                .collect()
+}
+
+/// The classical Runge-Kutta method with a fixed step.
+pub struct Rk4 {
+    h: f64,
+}
+
+impl Rk4 {
+    pub fn new(h: f64, t0: f64, t_end: f64) -> Rk4 {
+        assert!(h > 0.0, "Step size must be positive");
+        let steps = ((t_end - t0).abs() / h).ceil().max(1.0);
+        Rk4 { h: (t_end - t0) / steps }
+    }
+}
+
+impl Stepper for Rk4 {
+    fn step(&mut self, rhs: &Rhs, t: f64, y: &[f64], fy: &[f64], t_bound: f64)
+            -> Option<(f64, Vec<f64>, Vec<f64>)> {
+        // Land exactly on the end point, despite rounding in the accumulated time
+        let h = if ((t_bound - t) - self.h).abs() < 1e-8 * self.h.abs() { t_bound - t }
+                else { self.h };
+        let mut k = vec![];
+        let y_new = rk_step(rhs, t, y, fy, h, &[0.0, 0.5, 0.5, 1.0],
+                            &[&[], &[0.5], &[0.0, 0.5], &[0.0, 0.0, 1.0]],
+                            &[1.0 / 6.0, 1.0 / 3.0, 1.0 / 3.0, 1.0 / 6.0], &mut k);
+        let t_new = if h == t_bound - t { t_bound } else { t + h };
+        let f_new = rhs.eval(t_new, &y_new);
+        Some((t_new, y_new, f_new))
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum Tableau {
+    Rk45,
+    Dop853,
+}
+
+/// An explicit Runge-Kutta method with an embedded error estimate and adaptive steps.
+pub struct AdaptiveRk {
+    tableau: Tableau,
+    h_abs: f64,
+    rtol: f64,
+    atol: f64,
+    max_step: f64,
+    direction: f64,
+    /// The exponent used to compute the step size factor from the error norm.
+    exponent: f64,
+    k: Vec<Vec<f64>>,
+}
+
+impl AdaptiveRk {
+    pub fn rk45(h_abs: f64, options: &OdeOptions, direction: f64) -> AdaptiveRk {
+        AdaptiveRk::new(Tableau::Rk45, h_abs, options, direction, -1.0 / 5.0)
+    }
+
+    pub fn dop853(h_abs: f64, options: &OdeOptions, direction: f64) -> AdaptiveRk {
+        AdaptiveRk::new(Tableau::Dop853, h_abs, options, direction, -1.0 / 8.0)
+    }
+
+    fn new(tableau: Tableau, h_abs: f64, options: &OdeOptions, direction: f64, exponent: f64)
+            -> AdaptiveRk {
+        AdaptiveRk {
+            tableau: tableau,
+            h_abs: h_abs.min(options.max_step),
+            rtol: options.rtol,
+            atol: options.atol,
+            max_step: options.max_step,
+            direction: direction,
+            exponent: exponent,
+            k: vec![],
+        }
+    }
+
+    /// Takes a single step of size `h`, returning the new state, its derivative and the norm
+    /// of the error estimate relative to the tolerances.
+    fn attempt(&mut self, rhs: &Rhs, t: f64, y: &[f64], fy: &[f64], h: f64)
+            -> (Vec<f64>, Vec<f64>, f64) {
+        let n = y.len();
+        let y_new = match self.tableau {
+            Tableau::Rk45 => rk_step(rhs, t, y, fy, h, &RK45_C, &RK45_A, &RK45_B, &mut self.k),
+            Tableau::Dop853 => rk_step(rhs, t, y, fy, h, &dop853::C, &dop853::A, &dop853::B,
+                                       &mut self.k),
+        };
+        let f_new = rhs.eval(t + h, &y_new);
+        let scale: Vec<f64> = (0..n).map(|i| self.atol + y[i].abs().max(y_new[i].abs()) * self.rtol)
+                                    .collect();
+        let k = &self.k;
+        let error_norm = match self.tableau {
+            Tableau::Rk45 => {
+                let err: Vec<f64> = (0..n).map(|i| {
+                    let e = (0..6).map(|j| RK45_E[j] * k[j][i]).sum::<f64>() + RK45_E[6] * f_new[i];
+                    h * e / scale[i]
+                }).collect();
+                rms(&err)
+            },
+            Tableau::Dop853 => {
+                // Combines the 5th and 3rd order estimates, as in Hairer's DOP853
+                let (mut err5, mut err3) = (0.0, 0.0);
+                for i in 0..n {
+                    let e5 = (0..12).map(|j| dop853::E5[j] * k[j][i]).sum::<f64>() / scale[i];
+                    let mut e3 = (0..12).map(|j| dop853::B[j] * k[j][i]).sum::<f64>();
+                    for &(j, bhh) in dop853::BHH.iter() {
+                        e3 -= bhh * k[j][i];
+                    }
+                    let e3 = e3 / scale[i];
+                    err5 += e5 * e5;
+                    err3 += e3 * e3;
+                }
+                if err5 == 0.0 && err3 == 0.0 {
+                    0.0
+                } else {
+                    h.abs() * err5 / ((err5 + 0.01 * err3) * n as f64).sqrt()
+                }
+            },
+        };
+        (y_new, f_new, error_norm)
+    }
+}
+
+impl Stepper for AdaptiveRk {
+    fn step(&mut self, rhs: &Rhs, t: f64, y: &[f64], fy: &[f64], t_bound: f64)
+            -> Option<(f64, Vec<f64>, Vec<f64>)> {
+        let min_step = min_step(t);
+        let mut h_abs = self.h_abs.min(self.max_step).max(min_step);
+        let mut rejected = false;
+        loop {
+            if h_abs < min_step {
+                return None;
+            }
+            let mut h = h_abs * self.direction;
+            let mut t_new = t + h;
+            if (t_new - t_bound) * self.direction > 0.0 {
+                t_new = t_bound;
+            }
+            h = t_new - t;
+            h_abs = h.abs();
+
+            let (y_new, f_new, error_norm) = self.attempt(rhs, t, y, fy, h);
+            if error_norm < 1.0 {
+                let mut factor = if error_norm == 0.0 {
+                    MAX_FACTOR
+                } else {
+                    MAX_FACTOR.min(SAFETY * error_norm.powf(self.exponent))
+                };
+                if rejected {
+                    factor = factor.min(1.0);
+                }
+                self.h_abs = h_abs * factor;
+                return Some((t_new, y_new, f_new));
+            }
+            if !error_norm.is_finite() {
+                h_abs *= MIN_FACTOR;
+            } else {
+                h_abs *= MIN_FACTOR.max(SAFETY * error_norm.powf(self.exponent));
+            }
+            rejected = true;
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use std::f64;
+use std::f64::consts::PI;
+use tensor::Tensor;
+
+/// Nodes of the 15-point Kronrod rule on `[-1, 1]` (non-negative half). The odd-indexed nodes
+/// are those of the embedded 7-point Gauss rule.
+const XGK: [f64; 8] = [0.991455371120812639206854697526329, 0.949107912342758524526189684047851,
+                       0.864864423359769072789712788640926, 0.741531185599394439863864773280788,
+                       0.586087235467691130294144845693013, 0.405845151377397166906606412076961,
+                       0.207784955007898467600689403773245, 0.0];
+
+/// Weights of the 15-point Kronrod rule.
+const WGK: [f64; 8] = [0.022935322010529224963732008058970, 0.063092092629978553290700663189204,
+                       0.104790010322250183839876322541518, 0.140653259715525918745189590510238,
+                       0.169004726639267902826583426598550, 0.190350578064785409913256402421014,
+                       0.204432940075298892414161999234649, 0.209482141084727828012999174891714];
+
+/// Weights of the 7-point Gauss rule.
+const WG: [f64; 4] = [0.129484966168869693270611432679082, 0.279705391489276667901467771423780,
+                      0.381830050505118944950369775488975, 0.417959183673469387755102040816327];
+
+/// The result of `quad`.
+#[derive(Clone, Copy, Debug)]
+pub struct QuadResult {
+    /// The estimated integral.
+    pub value: f64,
+    /// An estimate of the absolute error.
+    pub error: f64,
+    /// The number of function evaluations.
+    pub evaluations: usize,
+    /// Whether the requested tolerance was reached within the subdivision limit.
+    pub converged: bool,
+}
+
+/// Applies the 15-point Gauss-Kronrod rule on `[a, b]`, returning the integral and an error
+/// estimate (following QUADPACK).
+fn gauss_kronrod<F: Fn(f64) -> f64>(f: &F, a: f64, b: f64) -> (f64, f64) {
+    let center = 0.5 * (a + b);
+    let half = 0.5 * (b - a);
+    let fc = f(center);
+    let mut kronrod = fc * WGK[7];
+    let mut gauss = fc * WG[3];
+    let mut resabs = kronrod.abs();
+    let mut fv = [0.0; 14];
+    for j in 0..7 {
+        let dx = half * XGK[j];
+        let (f1, f2) = (f(center - dx), f(center + dx));
+        fv[2 * j] = f1;
+        fv[2 * j + 1] = f2;
+        kronrod += WGK[j] * (f1 + f2);
+        resabs += WGK[j] * (f1.abs() + f2.abs());
+        if j % 2 == 1 {
+            gauss += WG[j / 2] * (f1 + f2);
+        }
+    }
+    let mean = 0.5 * kronrod;
+    let mut resasc = WGK[7] * (fc - mean).abs();
+    for j in 0..7 {
+        resasc += WGK[j] * ((fv[2 * j] - mean).abs() + (fv[2 * j + 1] - mean).abs());
+    }
+    let value = kronrod * half;
+    let (resabs, resasc) = (resabs * half.abs(), resasc * half.abs());
+    let mut err = ((kronrod - gauss) * half).abs();
+    if resasc != 0.0 && err != 0.0 {
+        err = resasc * (200.0 * err / resasc).powf(1.5).min(1.0);
+    }
+    if resabs > f64::MIN_POSITIVE / (50.0 * f64::EPSILON) {
+        err = err.max(50.0 * f64::EPSILON * resabs);
+    }
+    (value, err)
+}
+
+/// Integrates `f` over `[a, b]` with adaptive Gauss-Kronrod quadrature, using the default
+/// tolerances `epsabs = epsrel = 1.49e-8` and at most 50 subintervals. Either bound may be
+/// infinite.
+pub fn quad<F: Fn(f64) -> f64>(f: F, a: f64, b: f64) -> QuadResult {
+    quad_tol(f, a, b, 1.49e-8, 1.49e-8, 50)
+}
+
+/// Integrates `f` over `[a, b]` with adaptive Gauss-Kronrod quadrature. The interval with the
+/// largest error estimate is bisected until the total error is below
+/// `max(epsabs, epsrel * |value|)` or there are `limit` subintervals. Either bound may be
+/// infinite, in which case the integral is first mapped onto a finite interval.
+pub fn quad_tol<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, epsabs: f64, epsrel: f64,
+                                   limit: usize) -> QuadResult {
+    if a == b {
+        return QuadResult { value: 0.0, error: 0.0, evaluations: 0, converged: true };
+    }
+    if a > b {
+        let r = quad_tol(f, b, a, epsabs, epsrel, limit);
+        return QuadResult { value: -r.value, ..r };
+    }
+    match (a.is_infinite(), b.is_infinite()) {
+        (true, true) => {
+            // x = t / (1 - t^2)
+            let g = |t: f64| {
+                let s = 1.0 - t * t;
+                f(t / s) * (1.0 + t * t) / (s * s)
+            };
+            adaptive(&g, -1.0, 1.0, epsabs, epsrel, limit)
+        },
+        (false, true) => {
+            // x = a + t / (1 - t)
+            let g = |t: f64| {
+                let s = 1.0 - t;
+                f(a + t / s) / (s * s)
+            };
+            adaptive(&g, 0.0, 1.0, epsabs, epsrel, limit)
+        },
+        (true, false) => {
+            // x = b - (1 - t) / t
+            let g = |t: f64| f(b - (1.0 - t) / t) / (t * t);
+            adaptive(&g, 0.0, 1.0, epsabs, epsrel, limit)
+        },
+        (false, false) => adaptive(&f, a, b, epsabs, epsrel, limit),
+    }
+}
+
+fn adaptive<F: Fn(f64) -> f64>(f: &F, a: f64, b: f64, epsabs: f64, epsrel: f64,
+                               limit: usize) -> QuadResult {
+    // Each entry is (a, b, value, error)
+    let (value, error) = gauss_kronrod(f, a, b);
+    let mut intervals = vec![(a, b, value, error)];
+    let mut evaluations = 15;
+    let mut total = value;
+    let mut total_error = error;
+    while total_error > epsabs.max(epsrel * total.abs()) && intervals.len() < limit.max(1) {
+        let worst = intervals.iter().enumerate()
+                             .max_by(|x, y| (x.1).3.partial_cmp(&(y.1).3).unwrap())
+                             .map(|(i, _)| i).unwrap();
+        let (lo, hi, v, e) = intervals.swap_remove(worst);
+        let mid = 0.5 * (lo + hi);
+        if !(mid > lo && mid < hi) {
+            // The interval cannot be split any further
+            intervals.push((lo, hi, v, e));
+            break;
+        }
+        let (v1, e1) = gauss_kronrod(f, lo, mid);
+        let (v2, e2) = gauss_kronrod(f, mid, hi);
+        evaluations += 30;
+        intervals.push((lo, mid, v1, e1));
+        intervals.push((mid, hi, v2, e2));
+        // Recompute the sums from scratch to avoid accumulating rounding errors
+        total = intervals.iter().map(|i| i.2).sum();
+        total_error = intervals.iter().map(|i| i.3).sum();
+    }
+    QuadResult {
+        value: total,
+        error: total_error,
+        evaluations: evaluations,
+        converged: total_error <= epsabs.max(epsrel * total.abs()),
+    }
+}
+
+/// Evaluates the Legendre polynomial `P_n` and its derivative at `z`.
+fn legendre(n: usize, z: f64) -> (f64, f64) {
+    let (mut p0, mut p1) = (1.0, z);
+    for k in 2..n + 1 {
+        let k = k as f64;
+        let p2 = ((2.0 * k - 1.0) * z * p1 - (k - 1.0) * p0) / k;
+        p0 = p1;
+        p1 = p2;
+    }
+    (p1, n as f64 * (z * p1 - p0) / (z * z - 1.0))
+}
+
+/// Computes the nodes and weights of the `n`-point Gauss-Legendre rule on `[-1, 1]`, which
+/// integrates polynomials of degree up to `2n - 1` exactly. The nodes are in increasing order.
+pub fn gauss_legendre(n: usize) -> (Tensor<f64>, Tensor<f64>) {
+    assert!(n > 0, "Number of points must be positive");
+    let mut x = vec![0.0; n];
+    let mut w = vec![0.0; n];
+    for i in 0..(n + 1) / 2 {
+        // Initial guess for the i-th largest root, refined by Newton's method
+        let mut z = (PI * (i as f64 + 0.75) / (n as f64 + 0.5)).cos();
+        for _ in 0..100 {
+            let (p, dp) = legendre(n, z);
+            let dz = p / dp;
+            z -= dz;
+            if dz.abs() < 1e-15 {
+                break;
+            }
+        }
+        let (_, dp) = legendre(n, z);
+        x[i] = -z;
+        x[n - 1 - i] = z;
+        w[i] = 2.0 / ((1.0 - z * z) * dp * dp);
+        w[n - 1 - i] = w[i];
+    }
+    (Tensor::new(x), Tensor::new(w))
+}
+
+/// Integrates `f` over `[a, b]` with the `n`-point Gauss-Legendre rule.
+pub fn fixed_quad<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, n: usize) -> f64 {
+    let (x, w) = gauss_legendre(n);
+    let (center, half) = (0.5 * (a + b), 0.5 * (b - a));
+    half * x.iter().zip(w.iter()).map(|(xi, wi)| wi * f(center + half * xi)).sum::<f64>()
+}
@@ -0,0 +1,89 @@
+use tensor::Tensor;
+use traits::NumericTrait;
+use num::traits::Float;
+use super::{check_points, find_interval};
+
+/// Multilinear interpolation of data on a regular (possibly unevenly spaced) N-D grid. Points
+/// outside of the grid evaluate to the fill value, which is NaN unless set with `fill_value`.
+pub struct RegularGridInterpolator<T> {
+    points: Vec<Vec<T>>,
+    values: Tensor<T>,
+    fill: T,
+}
+
+impl<T: NumericTrait + Float> RegularGridInterpolator<T> {
+    /// Creates an interpolator from the grid coordinates along each axis and the values at the
+    /// grid points. The shape of `values` must be the lengths of `points`, such as the grid
+    /// given by `linspace` along each axis.
+    pub fn new(points: &[Tensor<T>], values: &Tensor<T>) -> RegularGridInterpolator<T> {
+        assert!(points.len() == values.ndim(),
+                "There must be one set of grid points per dimension of `values`");
+        let points: Vec<Vec<T>> = points.iter().enumerate().map(|(k, p)| {
+            assert!(p.ndim() == 1, "Grid points must be vectors (1D)");
+            assert!(p.size() == values.dim(k), "Grid points must match the shape of `values`");
+            let p: Vec<T> = p.iter().collect();
+            check_points(&p, 2);
+            p
+        }).collect();
+        RegularGridInterpolator { points: points, values: values.canonize(), fill: T::nan() }
+    }
+
+    /// Sets the value returned for points outside of the grid.
+    pub fn fill_value(mut self, fill: T) -> RegularGridInterpolator<T> {
+        self.fill = fill;
+        self
+    }
+
+    /// Interpolates at a single point with one coordinate per dimension.
+    pub fn eval_point(&self, xi: &[T]) -> T {
+        let d = self.points.len();
+        assert!(xi.len() == d, "Point must have one coordinate per dimension");
+        let mut index = vec![0; d];
+        let mut weight = vec![T::zero(); d];
+        for k in 0..d {
+            let p = &self.points[k];
+            if !(xi[k] >= p[0] && xi[k] <= p[p.len() - 1]) {
+                return self.fill;
+            }
+            let i = find_interval(p, xi[k]);
+            index[k] = i;
+            weight[k] = (xi[k] - p[i]) / (p[i + 1] - p[i]);
+        }
+
+        // Sum over the 2^d corners of the enclosing cell
+        let shape = self.values.shape();
+        let data = self.values.slice();
+        let mut result = T::zero();
+        for corner in 0..(1usize << d) {
+            let mut w = T::one();
+            let mut offset = 0;
+            for k in 0..d {
+                let upper = (corner >> k) & 1 == 1;
+                w = w * if upper { weight[k] } else { T::one() - weight[k] };
+                offset = offset * shape[k] + index[k] + if upper { 1 } else { 0 };
+            }
+            if w != T::zero() {
+                result = result + w * data[offset];
+            }
+        }
+        result
+    }
+
+    /// Interpolates at the points given by the rows of `xi`, which has shape `[m, d]`. Returns
+    /// a vector of length `m`.
+    pub fn eval(&self, xi: &Tensor<T>) -> Tensor<T> {
+        let d = self.points.len();
+        assert!(xi.ndim() == 2 && xi.dim(1) == d, "`xi` must have shape [m, d]");
+        let xi = xi.canonize();
+        let src = xi.slice();
+        let m = xi.dim(0);
+        let mut y = Tensor::zeros(&[m]);
+        {
+            let data = y.slice_mut();
+            for i in 0..m {
+                data[i] = self.eval_point(&src[i * d..(i + 1) * d]);
+            }
+        }
+        y
+    }
+}
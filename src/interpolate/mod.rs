@@ -0,0 +1,158 @@
+//! Interpolation of one-dimensional and gridded data.
+//!
+//! ```
+//! use numeric::Tensor;
+//! use numeric::interpolate::{interp, CubicSpline, SplineBoundary};
+//!
+//! let xp = Tensor::new(vec![0.0, 1.0, 2.0]);
+//! let fp = Tensor::new(vec![0.0, 10.0, 0.0]);
+//! let y = interp(&Tensor::new(vec![0.5, 1.5, 3.0]), &xp, &fp, None, None);
+//! assert!(y == Tensor::new(vec![5.0, 5.0, 0.0]));
+//!
+//! let spline = CubicSpline::new(&xp, &fp, SplineBoundary::Natural);
+//! let dy = spline.derivative(&Tensor::new(vec![1.0]), 1);
+//! ```
+
+use tensor::Tensor;
+use traits::NumericTrait;
+use num::traits::Float;
+
+mod spline;
+mod grid;
+
+pub use self::spline::{CubicSpline, SplineBoundary};
+pub use self::grid::RegularGridInterpolator;
+
+/// Checks that `xp` is a vector of at least `min` strictly increasing points.
+fn check_points<T: NumericTrait>(xp: &[T], min: usize) {
+    assert!(xp.len() >= min, "Too few data points");
+    assert!(xp.windows(2).all(|w| w[0] < w[1]), "Data points must be strictly increasing");
+}
+
+/// Returns the index `i` of the interval `[xp[i], xp[i + 1]]` containing `x`, clamped to the
+/// first and last interval. Requires at least two points.
+fn find_interval<T: NumericTrait>(xp: &[T], x: T) -> usize {
+    // The number of points that are <= x
+    let (mut lo, mut hi) = (0, xp.len());
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        if xp[mid] <= x {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    if lo == 0 { 0 } else if lo >= xp.len() - 1 { xp.len() - 2 } else { lo - 1 }
+}
+
+/// Applies `f` to each element of `x`, returning a tensor of the same shape.
+fn map<T: NumericTrait, F: Fn(T) -> T>(x: &Tensor<T>, f: F) -> Tensor<T> {
+    let mut y = Tensor::zeros(&x.shape());
+    {
+        let data = y.slice_mut();
+        for (i, v) in x.iter().enumerate() {
+            data[i] = f(v);
+        }
+    }
+    y
+}
+
+/// One-dimensional linear interpolation of the data points `(xp, fp)` at `x`, where `xp` must
+/// be increasing. Values below `xp[0]` are set to `left` and values above the last point to
+/// `right`, which default to the first and last value of `fp`.
+pub fn interp<T: NumericTrait + Float>(x: &Tensor<T>, xp: &Tensor<T>, fp: &Tensor<T>,
+                                       left: Option<T>, right: Option<T>) -> Tensor<T> {
+    assert!(xp.ndim() == 1 && fp.ndim() == 1, "Data points must be vectors (1D)");
+    assert!(xp.size() == fp.size(), "`xp` and `fp` must have the same length");
+    let xp: Vec<T> = xp.iter().collect();
+    let fp: Vec<T> = fp.iter().collect();
+    check_points(&xp, 1);
+    let n = xp.len();
+    let left = left.unwrap_or(fp[0]);
+    let right = right.unwrap_or(fp[n - 1]);
+    map(x, |v| {
+        if v.is_nan() {
+            v
+        } else if v < xp[0] {
+            left
+        } else if v > xp[n - 1] {
+            right
+        } else if n == 1 {
+            fp[0]
+        } else {
+            let i = find_interval(&xp, v);
+            let t = (v - xp[i]) / (xp[i + 1] - xp[i]);
+            fp[i] + t * (fp[i + 1] - fp[i])
+        }
+    })
+}
+
+/// The kind of interpolation used by `Interp1d`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum InterpKind {
+    /// The value of the nearest data point.
+    Nearest,
+    /// Piecewise linear interpolation.
+    Linear,
+    /// A natural cubic spline.
+    Cubic,
+}
+
+/// An interpolating function of one-dimensional data. Points outside of the data range evaluate
+/// to the fill value, which is NaN unless set with `fill_value`.
+pub struct Interp1d<T> {
+    x: Vec<T>,
+    y: Vec<T>,
+    kind: InterpKind,
+    fill: T,
+    spline: Option<CubicSpline<T>>,
+}
+
+impl<T: NumericTrait + Float> Interp1d<T> {
+    /// Creates an interpolating function through the points `(x, y)`, where `x` is strictly
+    /// increasing.
+    pub fn new(x: &Tensor<T>, y: &Tensor<T>, kind: InterpKind) -> Interp1d<T> {
+        assert!(x.ndim() == 1 && y.ndim() == 1, "Data points must be vectors (1D)");
+        assert!(x.size() == y.size(), "`x` and `y` must have the same length");
+        let xs: Vec<T> = x.iter().collect();
+        check_points(&xs, if kind == InterpKind::Nearest { 1 } else { 2 });
+        let spline = if kind == InterpKind::Cubic {
+            Some(CubicSpline::new(x, y, SplineBoundary::Natural))
+        } else {
+            None
+        };
+        Interp1d { x: xs, y: y.iter().collect(), kind: kind, fill: T::nan(), spline: spline }
+    }
+
+    /// Sets the value returned for points outside of the data range.
+    pub fn fill_value(mut self, fill: T) -> Interp1d<T> {
+        self.fill = fill;
+        self
+    }
+
+    /// Evaluates the interpolating function at each element of `x`.
+    pub fn eval(&self, x: &Tensor<T>) -> Tensor<T> {
+        let n = self.x.len();
+        map(x, |v| {
+            if !(v >= self.x[0] && v <= self.x[n - 1]) {
+                return self.fill;
+            }
+            match (self.kind, &self.spline) {
+                (_, &Some(ref s)) => s.eval_point(v, 0),
+                (InterpKind::Nearest, _) => {
+                    if n == 1 {
+                        return self.y[0];
+                    }
+                    let i = find_interval(&self.x, v);
+                    // Halfway points round down, as in Scipy
+                    if v - self.x[i] <= self.x[i + 1] - v { self.y[i] } else { self.y[i + 1] }
+                },
+                _ => {
+                    let i = find_interval(&self.x, v);
+                    let t = (v - self.x[i]) / (self.x[i + 1] - self.x[i]);
+                    self.y[i] + t * (self.y[i + 1] - self.y[i])
+                },
+            }
+        })
+    }
+}
@@ -0,0 +1,108 @@
+use tensor::Tensor;
+use traits::NumericTrait;
+use num::traits::Float;
+use super::{check_points, find_interval, map};
+
+/// The end conditions of a `CubicSpline`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SplineBoundary<T> {
+    /// The second derivative is zero at both ends.
+    Natural,
+    /// The first derivative takes the given values at the first and last point.
+    Clamped(T, T),
+}
+
+/// A piecewise cubic polynomial with continuous first and second derivatives, passing through
+/// the data points. Points outside of the data range are extrapolated using the first and last
+/// piece.
+#[derive(Clone, Debug)]
+pub struct CubicSpline<T> {
+    x: Vec<T>,
+    /// Coefficients of each piece, `y + b t + c t^2 + d t^3` with `t = x - x[i]`.
+    coeffs: Vec<[T; 4]>,
+}
+
+impl<T: NumericTrait + Float> CubicSpline<T> {
+    /// Creates a spline through the points `(x, y)`, where `x` is strictly increasing and has at
+    /// least two points.
+    pub fn new(x: &Tensor<T>, y: &Tensor<T>, boundary: SplineBoundary<T>) -> CubicSpline<T> {
+        assert!(x.ndim() == 1 && y.ndim() == 1, "Data points must be vectors (1D)");
+        assert!(x.size() == y.size(), "`x` and `y` must have the same length");
+        let x: Vec<T> = x.iter().collect();
+        let y: Vec<T> = y.iter().collect();
+        check_points(&x, 2);
+        let n = x.len();
+        let two = T::one() + T::one();
+        let six = two + two + two;
+        let h: Vec<T> = (0..n - 1).map(|i| x[i + 1] - x[i]).collect();
+        let slope: Vec<T> = (0..n - 1).map(|i| (y[i + 1] - y[i]) / h[i]).collect();
+
+        // Tridiagonal system for the second derivatives m, with sub-diagonal a, diagonal b,
+        // super-diagonal c and right-hand side r
+        let mut a = vec![T::zero(); n];
+        let mut b = vec![T::one(); n];
+        let mut c = vec![T::zero(); n];
+        let mut r = vec![T::zero(); n];
+        for i in 1..n - 1 {
+            a[i] = h[i - 1];
+            b[i] = two * (h[i - 1] + h[i]);
+            c[i] = h[i];
+            r[i] = six * (slope[i] - slope[i - 1]);
+        }
+        if let SplineBoundary::Clamped(d0, d1) = boundary {
+            b[0] = two * h[0];
+            c[0] = h[0];
+            r[0] = six * (slope[0] - d0);
+            a[n - 1] = h[n - 2];
+            b[n - 1] = two * h[n - 2];
+            r[n - 1] = six * (d1 - slope[n - 2]);
+        }
+
+        // Thomas algorithm
+        for i in 1..n {
+            let w = a[i] / b[i - 1];
+            b[i] = b[i] - w * c[i - 1];
+            r[i] = r[i] - w * r[i - 1];
+        }
+        let mut m = vec![T::zero(); n];
+        m[n - 1] = r[n - 1] / b[n - 1];
+        for i in (0..n - 1).rev() {
+            m[i] = (r[i] - c[i] * m[i + 1]) / b[i];
+        }
+
+        let coeffs = (0..n - 1).map(|i| {
+            [y[i],
+             slope[i] - h[i] * (two * m[i] + m[i + 1]) / six,
+             m[i] / two,
+             (m[i + 1] - m[i]) / (six * h[i])]
+        }).collect();
+        CubicSpline { x: x, coeffs: coeffs }
+    }
+
+    /// Evaluates the `nu`-th derivative at a single point.
+    pub fn eval_point(&self, x: T, nu: usize) -> T {
+        let i = find_interval(&self.x, x);
+        let t = x - self.x[i];
+        let p = &self.coeffs[i];
+        let two = T::one() + T::one();
+        let three = two + T::one();
+        let six = three + three;
+        match nu {
+            0 => p[0] + t * (p[1] + t * (p[2] + t * p[3])),
+            1 => p[1] + t * (two * p[2] + t * three * p[3]),
+            2 => two * p[2] + six * p[3] * t,
+            3 => six * p[3],
+            _ => T::zero(),
+        }
+    }
+
+    /// Evaluates the spline at each element of `x`.
+    pub fn eval(&self, x: &Tensor<T>) -> Tensor<T> {
+        map(x, |v| self.eval_point(v, 0))
+    }
+
+    /// Evaluates the `nu`-th derivative at each element of `x`.
+    pub fn derivative(&self, x: &Tensor<T>, nu: usize) -> Tensor<T> {
+        map(x, |v| self.eval_point(v, nu))
+    }
+}
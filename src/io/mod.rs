@@ -38,6 +38,25 @@
 //! saved the file as `i32`, we have to specify how to load it. The way this is done is that it
 //! will load the `i32` natively and then convert it to `f64`. If you do not want your data to be
 //! converted, you simply have to load it as the same type as you know is in the file.
+//!
+//! ## Random state
+//!
+//! The state of a `RandomState` can be stored in the same file, so that a restarted job draws
+//! exactly the same samples. Since `save_hdf5` on a tensor overwrites the file, save the tensor
+//! first:
+//!
+//! ```no_run
+//! use std::path::Path;
+//! use numeric::{Tensor, RandomState};
+//!
+//! let path = Path::new("checkpoint.h5");
+//! let mut rs = RandomState::new(1234);
+//! let t: Tensor<f64> = rs.uniform(0.0, 1.0, &[100]);
+//! t.save_hdf5(&path).unwrap();
+//! rs.save_hdf5(&path, "random_state").unwrap();
+//!
+//! let rs2: RandomState = RandomState::load_hdf5(&path, "random_state").unwrap();
+//! ```
 
 extern crate std;
 
@@ -46,6 +65,7 @@ use std::path::Path;
 use hdf5_sys as ffi;
 
 use tensor::Tensor;
+use random::{RandomState, BitGenerator};
 
 extern fn error_handler(_: ffi::hid_t, _: *const c_void) {
     // Suppress errors. We will rely on return statuses alone.
@@ -269,3 +289,111 @@ add_load!(load_hdf5_as_f32, f32);
 add_load!(load_hdf5_as_f64, f64);
 add_load!(load_hdf5_as_isize, isize);
 add_load!(load_hdf5_as_usize, usize);
+
+impl<G: BitGenerator> RandomState<G> {
+    /// Saves the state of the generator to the dataset `name` of an HDF5 file, adding it to the
+    /// file if it already exists (for instance after saving a tensor with `save_hdf5`). An
+    /// existing dataset `name` is overwritten only if it has the size and type of this state,
+    /// such as a state saved earlier from the same type of generator.
+    ///
+    /// **Warning**: This function is not thread-safe (unless you compiled HDF5 to be
+    /// thread-safe). Do no call this function concurrently from multiple threads.
+    pub fn save_hdf5(&self, path: &Path, name: &str) -> std::io::Result<()> {
+        let filename = match path.to_str() {
+            Some(v) => v,
+            None => {
+                let msg = format!("Path could not be converted to string: {:?}", path);
+                let err = std::io::Error::new(std::io::ErrorKind::InvalidInput, msg);
+                return Err(err);
+            },
+        };
+        let state = self.get_state();
+
+        unsafe {
+            let filename_cstr = try!(::std::ffi::CString::new(filename));
+            let name_cstr = try!(::std::ffi::CString::new(name));
+
+            ffi::H5Eset_auto2(0, error_handler, 0 as *const c_void);
+
+            let file = if path.exists() {
+                ffi::H5Fopen(filename_cstr.as_ptr() as *const c_char,
+                             ffi::H5F_ACC_RDWR, ffi::H5P_DEFAULT)
+            } else {
+                ffi::H5Fcreate(filename_cstr.as_ptr() as *const c_char,
+                               ffi::H5F_ACC_TRUNC, ffi::H5P_DEFAULT, ffi::H5P_DEFAULT)
+            };
+
+            if file < 0 {
+                let msg = format!("Could not open file for writing: {:?}", path);
+                let err = std::io::Error::new(std::io::ErrorKind::Other, msg);
+                return Err(err);
+            }
+
+            // Overwrite the state of an earlier checkpoint if there is one
+            let mut dset = ffi::H5Dopen2(file, name_cstr.as_ptr() as *const c_char,
+                                         ffi::H5P_DEFAULT);
+            if dset < 0 {
+                let shape = [state.size() as u64];
+                let space = ffi::H5Screate_simple(1, shape.as_ptr(), std::ptr::null());
+                dset = ffi::H5Dcreate2(file, name_cstr.as_ptr() as *const c_char,
+                                       ffi::H5T_NATIVE_UINT64, space,
+                                       ffi::H5P_DEFAULT,
+                                       ffi::H5P_DEFAULT,
+                                       ffi::H5P_DEFAULT);
+            } else {
+                // Writing to a dataset of a different size would read past the end of the state
+                // or leave part of the old one behind
+                let datatype = ffi::H5Dget_type(dset);
+                let space = ffi::H5Dget_space(dset);
+                let mut shape: [ffi::hsize_t; 1] = [0];
+                let matches = ffi::H5Sget_simple_extent_ndims(space) == 1 &&
+                    ffi::H5Sget_simple_extent_dims(space, shape.as_mut_ptr(),
+                                                   0 as *mut ffi::hsize_t) == 1 &&
+                    shape[0] == state.size() as ffi::hsize_t &&
+                    ffi::H5Tequal(datatype, ffi::H5T_NATIVE_UINT64) == 1;
+                ffi::H5Tclose(datatype);
+
+                if !matches {
+                    ffi::H5Dclose(dset);
+                    ffi::H5Fclose(file);
+                    let msg = format!("'{}' exists but does not match the shape or type of the \
+                                       state: {:?}", name, path);
+                    let err = std::io::Error::new(std::io::ErrorKind::InvalidInput, msg);
+                    return Err(err);
+                }
+            }
+
+            let status = if dset < 0 {
+                -1
+            } else {
+                ffi::H5Dwrite(dset, ffi::H5T_NATIVE_UINT64, ffi::H5S_ALL, ffi::H5S_ALL,
+                              ffi::H5P_DEFAULT, state.as_ptr() as *const c_void)
+            };
+
+            if dset >= 0 {
+                ffi::H5Dclose(dset);
+            }
+            ffi::H5Fclose(file);
+
+            if status < 0 {
+                let msg = format!("Failed to write '{}': {:?}", name, path);
+                let err = std::io::Error::new(std::io::ErrorKind::Other, msg);
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    /// Loads a random state saved with `save_hdf5`. The state must come from the same type of
+    /// generator.
+    pub fn load_hdf5(path: &Path, name: &str) -> std::io::Result<RandomState<G>> {
+        let state = try!(load_hdf5_as_u64(path, name));
+        match G::from_state(state.slice()) {
+            Some(rng) => Ok(RandomState::from_generator(rng)),
+            None => {
+                let msg = format!("'{}' is not a valid generator state: {:?}", name, path);
+                Err(std::io::Error::new(std::io::ErrorKind::InvalidData, msg))
+            },
+        }
+    }
+}
@@ -6,15 +6,28 @@
 extern crate blas;
 extern crate lapack;
 extern crate num;
-extern crate rand;
 extern crate hdf5_sys;
 extern crate libc;
+#[cfg(feature = "serde-serialize")]
+extern crate serde;
+#[cfg(feature = "serde-serialize")]
+#[macro_use]
+extern crate serde_derive;
 
 pub mod traits;
 pub mod tensor;
 pub mod math;
 pub mod random;
+pub mod qmc;
 pub mod linalg;
+pub mod sparse;
+pub mod fft;
+pub mod signal;
+pub mod special;
+pub mod polynomial;
+pub mod interpolate;
+pub mod integrate;
+pub mod optimize;
 pub mod io;
 
 // Lift commonly used functions into the numeric namespace
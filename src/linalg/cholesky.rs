@@ -0,0 +1,49 @@
+use tensor::Tensor;
+use lapack;
+use linalg::LinalgError;
+
+macro_rules! add_cholesky_impl {
+    ($t:ty, $potrf:ident) => (
+        impl Tensor<$t> {
+            /// Computes the Cholesky factorization of a symmetric positive-definite matrix. If
+            /// `lower` is true, `L` is returned such that `A = LL^T`, otherwise `U` is returned
+            /// such that `A = U^T U`. Only the corresponding triangle of `A` is referenced.
+            ///
+            /// Returns `Err(LinalgError::NotPositiveDefinite)` if the factorization fails.
+            pub fn cholesky(&self, lower: bool) -> Result<Tensor<$t>, LinalgError> {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                assert!(self.dim(0) == self.dim(1), "`A` must be a square matrix");
+
+                let n = self.dim(0);
+                let mut a = self.canonize();
+                let mut info = 0;
+
+                // LAPACK sees the transpose, so the upper triangle in LAPACK's column-major
+                // layout is our lower triangle.
+                let uplo = if lower { b'U' } else { b'L' };
+                lapack::$potrf(uplo, n, a.slice_mut(), n, &mut info);
+                if info < 0 {
+                    return Err(LinalgError::IllegalArgument(-info));
+                } else if info > 0 {
+                    return Err(LinalgError::NotPositiveDefinite);
+                }
+
+                // Zero out the triangle that was not referenced
+                {
+                    let mut data = a.slice_mut();
+                    for i in 0..n {
+                        for j in 0..n {
+                            if (lower && j > i) || (!lower && j < i) {
+                                data[i * n + j] = 0.0;
+                            }
+                        }
+                    }
+                }
+                Ok(a)
+            }
+        }
+    )
+}
+
+add_cholesky_impl!(f64, dpotrf);
+add_cholesky_impl!(f32, spotrf);
@@ -0,0 +1,452 @@
+//! Linear algebra for complex matrices, implemented natively in Rust using LU with partial
+//! pivoting and Jacobi rotations.
+
+use tensor::Tensor;
+use linalg::LinalgError;
+use num::complex::Complex;
+use num::traits::{Zero, One, Float, cast};
+
+/// Factorizes a row-major `n x n` matrix in-place as `PA = LU`. Returns the row permutation and
+/// its sign, or `None` if the matrix is singular.
+fn lu_inplace<T>(a: &mut [Complex<T>], n: usize) -> Option<(Vec<usize>, T)>
+        where T: Float {
+    let mut perm: Vec<usize> = (0..n).collect();
+    let mut sign = T::one();
+    for j in 0..n {
+        let mut p = j;
+        for i in (j + 1)..n {
+            if a[i * n + j].norm() > a[p * n + j].norm() {
+                p = i;
+            }
+        }
+        if a[p * n + j].is_zero() {
+            return None;
+        }
+        if p != j {
+            for k in 0..n {
+                a.swap(j * n + k, p * n + k);
+            }
+            perm.swap(j, p);
+            sign = -sign;
+        }
+        let pivot = a[j * n + j];
+        for i in (j + 1)..n {
+            let l = a[i * n + j] / pivot;
+            a[i * n + j] = l;
+            for k in (j + 1)..n {
+                let v = a[j * n + k];
+                a[i * n + k] = a[i * n + k] - l * v;
+            }
+        }
+    }
+    Some((perm, sign))
+}
+
+/// Applies a rotation to columns `p` and `q` of a row-major matrix with `n` columns, after
+/// multiplying column `q` by `phase`.
+fn rotate<T: Float>(w: &mut [Complex<T>], n: usize, p: usize, q: usize, phase: Complex<T>,
+                    c: T, s: T) {
+    for i in 0..(w.len() / n) {
+        let wp = w[i * n + p];
+        let wq = w[i * n + q] * phase;
+        w[i * n + p] = wp.scale(c) - wq.scale(s);
+        w[i * n + q] = wp.scale(s) + wq.scale(c);
+    }
+}
+
+/// One-sided Jacobi SVD of a row-major `m x n` matrix with `m >= n`. Returns `(U, s, V)` with
+/// `A = U diag(s) V^H`, where `U` is `m x n` (with zero columns for zero singular values) and
+/// the singular values are sorted in descending order.
+fn jacobi_svd<T: Float>(a: &[Complex<T>], m: usize, n: usize)
+        -> (Vec<Complex<T>>, Vec<T>, Vec<Complex<T>>) {
+    let mut u = a.to_vec();
+    let mut v = vec![Complex::zero(); n * n];
+    for i in 0..n {
+        v[i * n + i] = Complex::one();
+    }
+
+    for _ in 0..100 {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in (p + 1)..n {
+                let (mut alpha, mut beta, mut gamma) = (T::zero(), T::zero(), Complex::zero());
+                for i in 0..m {
+                    let (up, uq) = (u[i * n + p], u[i * n + q]);
+                    alpha = alpha + up.norm_sqr();
+                    beta = beta + uq.norm_sqr();
+                    gamma = gamma + up.conj() * uq;
+                }
+                let g = gamma.norm();
+                if g == T::zero() || g <= T::epsilon() * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+
+                // Remove the phase of `gamma` from column q, then apply a real rotation
+                let phase = gamma.unscale(g).conj();
+                let zeta = (beta - alpha) / (g + g);
+                let t = zeta.signum() / (zeta.abs() + (T::one() + zeta * zeta).sqrt());
+                let c = T::one() / (T::one() + t * t).sqrt();
+                let s = c * t;
+                rotate(&mut u, n, p, q, phase, c, s);
+                rotate(&mut v, n, p, q, phase, c, s);
+            }
+        }
+        if !rotated {
+            break;
+        }
+    }
+
+    let mut sv: Vec<T> = (0..n).map(|j| {
+        (0..m).fold(T::zero(), |acc, i| acc + u[i * n + j].norm_sqr()).sqrt()
+    }).collect();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| sv[j].partial_cmp(&sv[i]).unwrap());
+
+    // Columns for negligible singular values are left as zero, since they are dominated by
+    // rounding errors
+    let tol = sv.iter().fold(T::zero(), |acc, &v| acc.max(v)) * T::epsilon() *
+              cast(m).unwrap();
+    let mut us = vec![Complex::zero(); m * n];
+    let mut vs = vec![Complex::zero(); n * n];
+    for (k, &j) in order.iter().enumerate() {
+        for i in 0..m {
+            if sv[j] > tol {
+                us[i * n + k] = u[i * n + j].unscale(sv[j]);
+            }
+        }
+        for i in 0..n {
+            vs[i * n + k] = v[i * n + j];
+        }
+    }
+    sv = order.iter().map(|&j| sv[j]).collect();
+    (us, sv, vs)
+}
+
+/// Applies a rotation to rows `p` and `q` of a row-major `n x n` matrix, after multiplying row
+/// `q` by `phase`. Together with `rotate`, this performs the similarity transform `G^H A G`
+/// when `phase` is conjugated.
+fn rotate_rows<T: Float>(w: &mut [Complex<T>], n: usize, p: usize, q: usize, phase: Complex<T>,
+                         c: T, s: T) {
+    for j in 0..n {
+        let wp = w[p * n + j];
+        let wq = w[q * n + j] * phase;
+        w[p * n + j] = wp.scale(c) - wq.scale(s);
+        w[q * n + j] = wp.scale(s) + wq.scale(c);
+    }
+}
+
+/// Two-sided Jacobi eigenvalue decomposition of a Hermitian row-major `n x n` matrix, using
+/// only its lower triangle. Returns the eigenvalues in ascending order and the eigenvectors as
+/// the columns of a row-major matrix, or `None` if the rotations did not converge.
+fn jacobi_eigh<T: Float>(a: &[Complex<T>], n: usize) -> Option<(Vec<T>, Vec<Complex<T>>)> {
+    let mut h = vec![Complex::zero(); n * n];
+    for i in 0..n {
+        for j in 0..i {
+            h[i * n + j] = a[i * n + j];
+            h[j * n + i] = a[i * n + j].conj();
+        }
+        h[i * n + i] = Complex::new(a[i * n + i].re, T::zero());
+    }
+    let mut v = vec![Complex::zero(); n * n];
+    for i in 0..n {
+        v[i * n + i] = Complex::one();
+    }
+
+    let mut converged = false;
+    for _ in 0..100 {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in (p + 1)..n {
+                let (alpha, beta, gamma) = (h[p * n + p].re, h[q * n + q].re, h[p * n + q]);
+                let g = gamma.norm();
+                if g == T::zero() || g <= T::epsilon() * (alpha.abs() * beta.abs()).sqrt() {
+                    continue;
+                }
+                rotated = true;
+
+                // Remove the phase of `gamma` from row and column q, then apply a real rotation
+                let phase = gamma.unscale(g).conj();
+                let zeta = (beta - alpha) / (g + g);
+                let t = zeta.signum() / (zeta.abs() + (T::one() + zeta * zeta).sqrt());
+                let c = T::one() / (T::one() + t * t).sqrt();
+                let s = c * t;
+                rotate(&mut h, n, p, q, phase, c, s);
+                rotate_rows(&mut h, n, p, q, phase.conj(), c, s);
+                rotate(&mut v, n, p, q, phase, c, s);
+            }
+        }
+        if !rotated {
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        return None;
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| h[i * n + i].re.partial_cmp(&h[j * n + j].re).unwrap());
+    let w = order.iter().map(|&j| h[j * n + j].re).collect();
+    let mut vs = vec![Complex::zero(); n * n];
+    for (k, &j) in order.iter().enumerate() {
+        for i in 0..n {
+            vs[i * n + k] = v[i * n + j];
+        }
+    }
+    Some((w, vs))
+}
+
+/// Builds an `m x cols` matrix with orthonormal columns from the `m x n` matrix `u`, replacing
+/// zero columns and adding new ones as needed (by orthogonalizing unit vectors).
+fn complete_basis<T: Float>(u: &[Complex<T>], m: usize, n: usize, cols: usize)
+        -> Vec<Complex<T>> {
+    let mut w = vec![Complex::zero(); m * cols];
+    let mut valid = vec![false; cols];
+    for j in 0..n {
+        if (0..m).any(|i| !u[i * n + j].is_zero()) {
+            for i in 0..m {
+                w[i * cols + j] = u[i * n + j];
+            }
+            valid[j] = true;
+        }
+    }
+    let mut e = 0;
+    for j in 0..cols {
+        while !valid[j] && e < m {
+            let mut x = vec![Complex::zero(); m];
+            x[e] = Complex::one();
+            e += 1;
+            // Orthogonalize twice for numerical stability
+            for _ in 0..2 {
+                for k in (0..cols).filter(|&k| valid[k]) {
+                    let mut d = Complex::zero();
+                    for i in 0..m {
+                        d = d + w[i * cols + k].conj() * x[i];
+                    }
+                    for i in 0..m {
+                        x[i] = x[i] - w[i * cols + k] * d;
+                    }
+                }
+            }
+            let norm = x.iter().fold(T::zero(), |acc, v| acc + v.norm_sqr()).sqrt();
+            if norm > cast(0.5).unwrap() {
+                for i in 0..m {
+                    w[i * cols + j] = x[i].unscale(norm);
+                }
+                valid[j] = true;
+            }
+        }
+    }
+    w
+}
+
+/// Returns the conjugate transpose of a row-major `m x n` matrix.
+fn conj_transpose<T: Float>(a: &[Complex<T>], m: usize, n: usize) -> Vec<Complex<T>> {
+    let mut b = vec![Complex::zero(); m * n];
+    for i in 0..m {
+        for j in 0..n {
+            b[j * m + i] = a[i * n + j].conj();
+        }
+    }
+    b
+}
+
+macro_rules! add_complex_impl {
+    ($t:ty) => (
+        impl Tensor<Complex<$t>> {
+            /// Solves the linear equation `Ax = b` and returns `x`. The input `b` can either be
+            /// a vector or a matrix with one right-hand side per column.
+            ///
+            /// Panics if matrix is singular.
+            pub fn solve(&self, b: &Tensor<Complex<$t>>) -> Tensor<Complex<$t>> {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                assert!(self.dim(0) == self.dim(1), "`A` must be a square matrix");
+                assert!(b.ndim() == 1 || b.ndim() == 2, "`b` must be a vector or a matrix");
+                assert!(self.dim(0) == b.dim(0), "`A` and `b` must match");
+
+                let n = self.dim(0);
+                let mut lu = self.canonize();
+                let perm = match lu_inplace(lu.slice_mut(), n) {
+                    Some((perm, _)) => perm,
+                    None => panic!("Singular matrix"),
+                };
+
+                let k = if b.ndim() == 1 { 1 } else { b.dim(1) };
+                let b = b.canonize();
+                let mut x: Tensor<Complex<$t>> = Tensor::zeros(b.shape());
+                {
+                    let a = lu.slice();
+                    let src = b.slice();
+                    let mut data = x.slice_mut();
+                    for col in 0..k {
+                        for i in 0..n {
+                            let mut v = src[perm[i] * k + col];
+                            for j in 0..i {
+                                v = v - a[i * n + j] * data[j * k + col];
+                            }
+                            data[i * k + col] = v;
+                        }
+                        for i in (0..n).rev() {
+                            let mut v = data[i * k + col];
+                            for j in (i + 1)..n {
+                                v = v - a[i * n + j] * data[j * k + col];
+                            }
+                            data[i * k + col] = v / a[i * n + i];
+                        }
+                    }
+                }
+                x
+            }
+
+            /// Performs a singular value decomposition `A = U diag(s) V^H`, returned as
+            /// `(U, s, V^H)`. The singular values are real and sorted in descending order. If
+            /// `full_matrices` is true, `U` and `V^H` are square, otherwise they are reduced to
+            /// `min(m, n)` columns and rows, respectively.
+            ///
+            /// This uses one-sided Jacobi rotations, which is accurate but slower than LAPACK
+            /// for large matrices.
+            pub fn svd(&self, full_matrices: bool)
+                    -> (Tensor<Complex<$t>>, Tensor<$t>, Tensor<Complex<$t>>) {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                let (m, n) = (self.dim(0), self.dim(1));
+                let a = self.canonize();
+                // Work on the tall orientation, using A^H = V diag(s) U^H otherwise
+                let (rows, cols, tall) = if m >= n {
+                    (m, n, a.slice().to_vec())
+                } else {
+                    (n, m, conj_transpose(a.slice(), m, n))
+                };
+                let (u, s, v) = jacobi_svd(&tall, rows, cols);
+                let ucols = if full_matrices { rows } else { cols };
+                let u = complete_basis(&u, rows, cols, ucols);
+
+                let (u, uc, vh, vr) = if m >= n {
+                    (u, ucols, conj_transpose(&v, cols, cols), cols)
+                } else {
+                    (v, cols, conj_transpose(&u, rows, ucols), ucols)
+                };
+                (Tensor::new(u).reshape(&[m as isize, uc as isize]), Tensor::new(s),
+                 Tensor::new(vh).reshape(&[vr as isize, n as isize]))
+            }
+            /// Computes the inverse of a square matrix using an LU factorization.
+            ///
+            /// Returns `Err(LinalgError::Singular)` if the matrix is singular.
+            pub fn inv(&self) -> Result<Tensor<Complex<$t>>, LinalgError> {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                assert!(self.dim(0) == self.dim(1), "`A` must be a square matrix");
+
+                let n = self.dim(0);
+                let mut lu = self.canonize();
+                let perm = match lu_inplace(lu.slice_mut(), n) {
+                    Some((perm, _)) => perm,
+                    None => return Err(LinalgError::Singular),
+                };
+
+                let mut x: Tensor<Complex<$t>> = Tensor::zeros(&[n, n]);
+                {
+                    let a = lu.slice();
+                    let mut data = x.slice_mut();
+                    // Solve LU x = P e_col for each column
+                    for col in 0..n {
+                        for i in 0..n {
+                            let mut v = if perm[i] == col {
+                                Complex::one()
+                            } else {
+                                Complex::zero()
+                            };
+                            for k in 0..i {
+                                v = v - a[i * n + k] * data[k * n + col];
+                            }
+                            data[i * n + col] = v;
+                        }
+                        for i in (0..n).rev() {
+                            let mut v = data[i * n + col];
+                            for k in (i + 1)..n {
+                                v = v - a[i * n + k] * data[k * n + col];
+                            }
+                            data[i * n + col] = v / a[i * n + i];
+                        }
+                    }
+                }
+                Ok(x)
+            }
+
+            /// Computes the determinant of a square matrix using an LU factorization.
+            pub fn det(&self) -> Complex<$t> {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                assert!(self.dim(0) == self.dim(1), "`A` must be a square matrix");
+
+                let n = self.dim(0);
+                let mut lu = self.canonize();
+                match lu_inplace(lu.slice_mut(), n) {
+                    Some((_, sign)) => {
+                        let a = lu.slice();
+                        let mut d = Complex::new(sign, 0.0);
+                        for i in 0..n {
+                            d = d * a[i * n + i];
+                        }
+                        d
+                    },
+                    None => Complex::zero(),
+                }
+            }
+
+            /// Computes the sign and the natural logarithm of the absolute value of the
+            /// determinant, returned as `(sign, logdet)`. The sign is a complex number of unit
+            /// length and the determinant is `sign * exp(logdet)`.
+            ///
+            /// If the matrix is singular, the sign is `0` and `logdet` is negative infinity.
+            pub fn slogdet(&self) -> (Complex<$t>, $t) {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                assert!(self.dim(0) == self.dim(1), "`A` must be a square matrix");
+
+                let n = self.dim(0);
+                let mut lu = self.canonize();
+                match lu_inplace(lu.slice_mut(), n) {
+                    Some((_, sign)) => {
+                        let a = lu.slice();
+                        let mut s = Complex::new(sign, 0.0);
+                        let mut logdet: $t = 0.0;
+                        for i in 0..n {
+                            let v = a[i * n + i];
+                            let r = v.norm();
+                            s = s * (v / r);
+                            logdet += r.ln();
+                        }
+                        (s, logdet)
+                    },
+                    None => (Complex::zero(), <$t as Float>::neg_infinity()),
+                }
+            }
+            /// Computes the eigenvalues and eigenvectors of a Hermitian matrix, returned as
+            /// `(w, v)`. The eigenvalues `w` are real and in ascending order and column `i` of
+            /// `v` is the normalized eigenvector corresponding to `w[i]`. Only the lower
+            /// triangle of the matrix is referenced.
+            ///
+            /// This uses two-sided Jacobi rotations, which is accurate but slower than LAPACK
+            /// for large matrices.
+            pub fn eigh(&self) -> Result<(Tensor<$t>, Tensor<Complex<$t>>), LinalgError> {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                assert!(self.dim(0) == self.dim(1), "`A` must be a square matrix");
+
+                let n = self.dim(0);
+                let a = self.canonize();
+                match jacobi_eigh(a.slice(), n) {
+                    Some((w, v)) => Ok((Tensor::new(w),
+                                        Tensor::new(v).reshape(&[n as isize, n as isize]))),
+                    None => Err(LinalgError::NotConverged),
+                }
+            }
+
+            /// Computes the eigenvalues of a Hermitian matrix in ascending order.
+            pub fn eigvalsh(&self) -> Result<Tensor<$t>, LinalgError> {
+                let (w, _) = try!(self.eigh());
+                Ok(w)
+            }
+        }
+    )
+}
+
+add_complex_impl!(f32);
+add_complex_impl!(f64);
@@ -0,0 +1,76 @@
+use tensor::Tensor;
+use lapack;
+use num::traits::Float;
+
+macro_rules! add_det_impl {
+    ($t:ty, $getrf:ident) => (
+        impl Tensor<$t> {
+            /// Computes the determinant of a square matrix using an LU factorization.
+            ///
+            /// For large matrices, the determinant can easily overflow or underflow. Use
+            /// `slogdet` in that case.
+            pub fn det(&self) -> $t {
+                match self.lu_diagonal() {
+                    Some((diag, sign)) => diag.iter().fold(sign, |acc, &v| acc * v),
+                    None => 0.0,
+                }
+            }
+
+            /// Computes the sign and the natural logarithm of the absolute value of the
+            /// determinant, returned as `(sign, logdet)`. The determinant is `sign * exp(logdet)`.
+            ///
+            /// If the matrix is singular, the sign is `0` and `logdet` is negative infinity.
+            pub fn slogdet(&self) -> ($t, $t) {
+                match self.lu_diagonal() {
+                    Some((diag, mut sign)) => {
+                        let mut logdet: $t = 0.0;
+                        for &v in diag.iter() {
+                            if v < 0.0 {
+                                sign = -sign;
+                            }
+                            logdet += v.abs().ln();
+                        }
+                        (sign, logdet)
+                    },
+                    None => (0.0, <$t as Float>::neg_infinity()),
+                }
+            }
+
+            /// Returns the diagonal of `U` from an LU factorization together with the sign of the
+            /// permutation, or `None` if the matrix is singular.
+            fn lu_diagonal(&self) -> Option<(Vec<$t>, $t)> {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                assert!(self.dim(0) == self.dim(1), "`A` must be a square matrix");
+
+                // det(A^T) = det(A), so there is no need to transpose for LAPACK.
+                let n = self.dim(0);
+                let mut a = self.canonize();
+                let mut ipiv: Tensor<i32> = Tensor::empty(&[n]);
+                let mut info = 0;
+
+                lapack::$getrf(n, n, a.slice_mut(), n, ipiv.slice_mut(), &mut info);
+                if info < 0 {
+                    panic!("Illegal input ({})", -info);
+                } else if info > 0 {
+                    return None;
+                }
+
+                let mut sign: $t = 1.0;
+                let mut diag = Vec::with_capacity(n);
+                let data = a.slice();
+                let piv = ipiv.slice();
+                for i in 0..n {
+                    diag.push(data[i * n + i]);
+                    // LAPACK pivots are 1-based
+                    if piv[i] != (i + 1) as i32 {
+                        sign = -sign;
+                    }
+                }
+                Some((diag, sign))
+            }
+        }
+    )
+}
+
+add_det_impl!(f64, dgetrf);
+add_det_impl!(f32, sgetrf);
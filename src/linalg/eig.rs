@@ -0,0 +1,190 @@
+use tensor::{Tensor, StridedSlice};
+use lapack;
+use linalg::LinalgError;
+use num::complex::Complex;
+
+macro_rules! add_eig_impl {
+    ($t:ty, $syevd:ident, $syevr:ident, $geev:ident) => (
+        impl Tensor<$t> {
+            /// Computes the eigenvalues and eigenvectors of a symmetric matrix, returned as
+            /// `(w, v)`. The eigenvalues `w` are in ascending order and column `i` of `v` is the
+            /// normalized eigenvector corresponding to `w[i]`. Only the lower triangle of the
+            /// matrix is referenced.
+            pub fn eigh(&self) -> Result<(Tensor<$t>, Tensor<$t>), LinalgError> {
+                let (w, v) = try!(self.syevd(true));
+                Ok((w, v.unwrap()))
+            }
+
+            /// Computes the eigenvalues of a symmetric matrix in ascending order, without
+            /// computing the eigenvectors.
+            pub fn eigvalsh(&self) -> Result<Tensor<$t>, LinalgError> {
+                let (w, _) = try!(self.syevd(false));
+                Ok(w)
+            }
+
+            /// Same as `eigh`, except only the eigenvalues with (ascending) indices `lo`
+            /// through `hi - 1` and their eigenvectors are computed.
+            pub fn eigh_subset(&self, lo: usize, hi: usize)
+                    -> Result<(Tensor<$t>, Tensor<$t>), LinalgError> {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                assert!(self.dim(0) == self.dim(1), "`A` must be a square matrix");
+                assert!(lo < hi && hi <= self.dim(0), "Invalid eigenvalue range");
+
+                let n = self.dim(0);
+                let k = hi - lo;
+                let mut a = self.canonize();
+                let mut w: Tensor<$t> = Tensor::zeros(&[n]);
+                let mut z: Tensor<$t> = Tensor::zeros(&[k, n]);
+                let mut isuppz: Tensor<i32> = Tensor::zeros(&[2 * k]);
+                let mut found = 0;
+                let mut info = 0;
+
+                let mut query: Tensor<$t> = Tensor::zeros(&[1]);
+                let mut iquery: Tensor<i32> = Tensor::zeros(&[1]);
+                lapack::$syevr(b'V', b'I', b'U', n, a.slice_mut(), n, 0.0, 0.0, lo + 1, hi, 0.0,
+                               &mut found, w.slice_mut(), z.slice_mut(), n, isuppz.slice_mut(),
+                               query.slice_mut(), -1, iquery.slice_mut(), -1, &mut info);
+                let lwork = query.slice()[0] as usize;
+                let liwork = iquery.slice()[0] as usize;
+                let mut work: Tensor<$t> = Tensor::empty(&[lwork]);
+                let mut iwork: Tensor<i32> = Tensor::empty(&[liwork]);
+                lapack::$syevr(b'V', b'I', b'U', n, a.slice_mut(), n, 0.0, 0.0, lo + 1, hi, 0.0,
+                               &mut found, w.slice_mut(), z.slice_mut(), n, isuppz.slice_mut(),
+                               work.slice_mut(), lwork as isize, iwork.slice_mut(),
+                               liwork as isize, &mut info);
+                if info < 0 {
+                    return Err(LinalgError::IllegalArgument(-info));
+                } else if info > 0 {
+                    return Err(LinalgError::NotConverged);
+                }
+
+                let w = w.index(&[StridedSlice(None, Some(k as isize), 1)]).canonize();
+                Ok((w, z.transpose().canonize()))
+            }
+
+            /// Computes the eigenvalues and right eigenvectors of a general square matrix,
+            /// returned as `(w, v)`. Column `i` of `v` is the normalized eigenvector
+            /// corresponding to `w[i]`. The eigenvalues are not ordered.
+            pub fn eig(&self) -> Result<(Tensor<Complex<$t>>, Tensor<Complex<$t>>), LinalgError> {
+                let (w, v) = try!(self.geev(true));
+                Ok((w, v.unwrap()))
+            }
+
+            /// Computes the eigenvalues of a general square matrix, without computing the
+            /// eigenvectors.
+            pub fn eigvals(&self) -> Result<Tensor<Complex<$t>>, LinalgError> {
+                let (w, _) = try!(self.geev(false));
+                Ok(w)
+            }
+
+            fn syevd(&self, vectors: bool) -> Result<(Tensor<$t>, Option<Tensor<$t>>), LinalgError> {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                assert!(self.dim(0) == self.dim(1), "`A` must be a square matrix");
+
+                // LAPACK sees the transpose, so its upper triangle is our lower triangle.
+                let n = self.dim(0);
+                let jobz = if vectors { b'V' } else { b'N' };
+                let mut a = self.canonize();
+                let mut w: Tensor<$t> = Tensor::zeros(&[n]);
+                let mut info = 0;
+
+                let mut query: Tensor<$t> = Tensor::zeros(&[1]);
+                let mut iquery: Tensor<i32> = Tensor::zeros(&[1]);
+                lapack::$syevd(jobz, b'U', n, a.slice_mut(), n, w.slice_mut(),
+                               query.slice_mut(), -1, iquery.slice_mut(), -1, &mut info);
+                let lwork = query.slice()[0] as usize;
+                let liwork = iquery.slice()[0] as usize;
+                let mut work: Tensor<$t> = Tensor::empty(&[lwork]);
+                let mut iwork: Tensor<i32> = Tensor::empty(&[liwork]);
+                lapack::$syevd(jobz, b'U', n, a.slice_mut(), n, w.slice_mut(),
+                               work.slice_mut(), lwork as isize, iwork.slice_mut(),
+                               liwork as isize, &mut info);
+                if info < 0 {
+                    return Err(LinalgError::IllegalArgument(-info));
+                } else if info > 0 {
+                    return Err(LinalgError::NotConverged);
+                }
+
+                if vectors {
+                    // The eigenvectors are stored as the columns in column-major order
+                    Ok((w, Some(a.transpose().canonize())))
+                } else {
+                    Ok((w, None))
+                }
+            }
+
+            fn geev(&self, vectors: bool)
+                    -> Result<(Tensor<Complex<$t>>, Option<Tensor<Complex<$t>>>), LinalgError> {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                assert!(self.dim(0) == self.dim(1), "`A` must be a square matrix");
+
+                // A must be transposed, since LAPACK is column-major.
+                let n = self.dim(0);
+                let jobvr = if vectors { b'V' } else { b'N' };
+                let ldvr = if vectors { n } else { 1 };
+                let mut a = self.transpose().canonize();
+                let mut wr: Tensor<$t> = Tensor::zeros(&[n]);
+                let mut wi: Tensor<$t> = Tensor::zeros(&[n]);
+                let mut vl: Tensor<$t> = Tensor::zeros(&[1]);
+                let mut vr: Tensor<$t> = Tensor::zeros(&[n * ldvr]);
+                let mut info = 0;
+
+                let mut query: Tensor<$t> = Tensor::zeros(&[1]);
+                lapack::$geev(b'N', jobvr, n, a.slice_mut(), n, wr.slice_mut(), wi.slice_mut(),
+                              vl.slice_mut(), 1, vr.slice_mut(), ldvr, query.slice_mut(), -1,
+                              &mut info);
+                let lwork = query.slice()[0] as usize;
+                let mut work: Tensor<$t> = Tensor::empty(&[lwork]);
+                lapack::$geev(b'N', jobvr, n, a.slice_mut(), n, wr.slice_mut(), wi.slice_mut(),
+                              vl.slice_mut(), 1, vr.slice_mut(), ldvr, work.slice_mut(),
+                              lwork as isize, &mut info);
+                if info < 0 {
+                    return Err(LinalgError::IllegalArgument(-info));
+                } else if info > 0 {
+                    return Err(LinalgError::NotConverged);
+                }
+
+                let mut w: Tensor<Complex<$t>> = Tensor::empty(&[n]);
+                {
+                    let mut data = w.slice_mut();
+                    for (i, (re, im)) in wr.iter().zip(wi.iter()).enumerate() {
+                        data[i] = Complex::new(re, im);
+                    }
+                }
+                if !vectors {
+                    return Ok((w, None));
+                }
+
+                // Complex conjugate pairs of eigenvalues store the real and imaginary parts of
+                // their eigenvectors in two consecutive columns.
+                let mut v: Tensor<Complex<$t>> = Tensor::empty(&[n, n]);
+                {
+                    let wi = wi.slice();
+                    let vr = vr.slice();
+                    let mut data = v.slice_mut();
+                    let mut j = 0;
+                    while j < n {
+                        if wi[j] == 0.0 {
+                            for i in 0..n {
+                                data[i * n + j] = Complex::new(vr[j * n + i], 0.0);
+                            }
+                            j += 1;
+                        } else {
+                            for i in 0..n {
+                                let re = vr[j * n + i];
+                                let im = vr[(j + 1) * n + i];
+                                data[i * n + j] = Complex::new(re, im);
+                                data[i * n + j + 1] = Complex::new(re, -im);
+                            }
+                            j += 2;
+                        }
+                    }
+                }
+                Ok((w, Some(v)))
+            }
+        }
+    )
+}
+
+add_eig_impl!(f64, dsyevd, dsyevr, dgeev);
+add_eig_impl!(f32, ssyevd, ssyevr, sgeev);
@@ -0,0 +1,73 @@
+use tensor::Tensor;
+use lapack;
+use linalg::LinalgError;
+
+macro_rules! add_inv_impl {
+    ($t:ty, $getrf:ident, $getri:ident) => (
+        impl Tensor<$t> {
+            /// Computes the inverse of a square matrix using an LU factorization.
+            ///
+            /// Returns `Err(LinalgError::Singular)` if the matrix is singular.
+            pub fn inv(&self) -> Result<Tensor<$t>, LinalgError> {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                assert!(self.dim(0) == self.dim(1), "`A` must be a square matrix");
+
+                // LAPACK is column-major, so it will see the transpose of `A`. Since
+                // inv(A^T) = inv(A)^T, the result will be correct once read back as row-major.
+                let n = self.dim(0);
+                let mut a = self.canonize();
+                let mut ipiv: Tensor<i32> = Tensor::empty(&[n]);
+                let mut info = 0;
+
+                lapack::$getrf(n, n, a.slice_mut(), n, ipiv.slice_mut(), &mut info);
+                try!(LinalgError::from_info(info));
+
+                // Query optimal workspace size
+                let mut query: Tensor<$t> = Tensor::zeros(&[1]);
+                lapack::$getri(n, a.slice_mut(), n, ipiv.slice(), query.slice_mut(), -1,
+                               &mut info);
+                try!(LinalgError::from_info(info));
+
+                let lwork = query.slice()[0] as usize;
+                let mut work: Tensor<$t> = Tensor::empty(&[lwork]);
+                lapack::$getri(n, a.slice_mut(), n, ipiv.slice(), work.slice_mut(),
+                               lwork as isize, &mut info);
+                try!(LinalgError::from_info(info));
+                Ok(a)
+            }
+
+            /// Computes the Moore-Penrose pseudo-inverse of a matrix using its singular value
+            /// decomposition. Singular values smaller than `rcond` times the largest singular
+            /// value are treated as zero.
+            pub fn pinv(&self, rcond: $t) -> Tensor<$t> {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                let m = self.dim(0);
+                let n = self.dim(1);
+                if m == 0 || n == 0 {
+                    return Tensor::zeros(&[n, m]);
+                }
+
+                let (u, s, vt) = self.svd(false);
+                let k = s.size();
+                let cutoff = rcond * s.max();
+
+                // Scale the rows of U^T by the inverted singular values
+                let mut ut = u.transpose().canonize();
+                {
+                    let sdata = s.slice();
+                    let mut data = ut.slice_mut();
+                    for i in 0..k {
+                        let sinv = if sdata[i] > cutoff { 1.0 / sdata[i] } else { 0.0 };
+                        for j in 0..m {
+                            data[i * m + j] *= sinv;
+                        }
+                    }
+                }
+                vt.transpose().dot(&ut)
+            }
+        }
+    )
+}
+
+add_inv_impl!(f64, dgetrf, dgetri);
+add_inv_impl!(f32, sgetrf, sgetri);
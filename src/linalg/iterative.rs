@@ -0,0 +1,447 @@
+//! Iterative (Krylov subspace) solvers for `Ax = b`. These only need to apply `A` to a vector,
+//! so they work on anything that implements `LinearOperator`.
+
+use tensor::Tensor;
+use linalg::LinalgError;
+use sparse::{CsrMatrix, CscMatrix};
+
+/// An operator that can be applied to a vector. This is implemented for dense matrices, sparse
+/// matrices and closures.
+pub trait LinearOperator {
+    /// Computes `Ax`.
+    fn apply(&self, x: &Tensor<f64>) -> Tensor<f64>;
+}
+
+impl LinearOperator for Tensor<f64> {
+    fn apply(&self, x: &Tensor<f64>) -> Tensor<f64> {
+        self.dot(x)
+    }
+}
+
+impl LinearOperator for CsrMatrix<f64> {
+    fn apply(&self, x: &Tensor<f64>) -> Tensor<f64> {
+        self.dot(x)
+    }
+}
+
+impl LinearOperator for CscMatrix<f64> {
+    fn apply(&self, x: &Tensor<f64>) -> Tensor<f64> {
+        self.dot(x)
+    }
+}
+
+impl<F: Fn(&Tensor<f64>) -> Tensor<f64>> LinearOperator for F {
+    fn apply(&self, x: &Tensor<f64>) -> Tensor<f64> {
+        self(x)
+    }
+}
+
+/// The identity operator, used to run a solver without a preconditioner.
+pub struct Identity;
+
+impl LinearOperator for Identity {
+    fn apply(&self, x: &Tensor<f64>) -> Tensor<f64> {
+        x.clone()
+    }
+}
+
+/// Jacobi (diagonal) preconditioner, which applies the inverse of the diagonal of `A`.
+pub struct Jacobi {
+    inv_diag: Tensor<f64>,
+}
+
+impl Jacobi {
+    /// Creates a Jacobi preconditioner from a dense matrix. Returns `Err(LinalgError::Singular)`
+    /// if the diagonal contains a zero.
+    pub fn new(a: &Tensor<f64>) -> Result<Jacobi, LinalgError> {
+        assert!(a.ndim() == 2 && a.dim(0) == a.dim(1), "`A` must be a square matrix");
+        Jacobi::from_diagonal((0..a.dim(0)).map(|i| a[(i, i)]).collect())
+    }
+
+    /// Creates a Jacobi preconditioner from a sparse matrix.
+    pub fn from_csr(a: &CsrMatrix<f64>) -> Result<Jacobi, LinalgError> {
+        assert!(a.shape()[0] == a.shape()[1], "`A` must be a square matrix");
+        Jacobi::from_diagonal((0..a.shape()[0]).map(|i| a.get(i, i)).collect())
+    }
+
+    fn from_diagonal(d: Vec<f64>) -> Result<Jacobi, LinalgError> {
+        if d.iter().any(|&v| v == 0.0) {
+            return Err(LinalgError::Singular);
+        }
+        Ok(Jacobi { inv_diag: Tensor::new(d.iter().map(|&v| 1.0 / v).collect()) })
+    }
+}
+
+impl LinearOperator for Jacobi {
+    fn apply(&self, x: &Tensor<f64>) -> Tensor<f64> {
+        &self.inv_diag * x
+    }
+}
+
+/// Incomplete LU factorization with zero fill-in. The factors have the same sparsity pattern as
+/// `A`, which makes this much cheaper than a full factorization.
+pub struct Ilu0 {
+    n: usize,
+    indptr: Vec<usize>,
+    indices: Vec<usize>,
+    data: Vec<f64>,
+    diag: Vec<usize>,
+}
+
+impl Ilu0 {
+    /// Computes the factorization. Returns `Err(LinalgError::Singular)` if a diagonal element is
+    /// missing or a zero pivot is encountered.
+    pub fn new(a: &CsrMatrix<f64>) -> Result<Ilu0, LinalgError> {
+        let n = a.shape()[0];
+        assert!(n == a.shape()[1], "`A` must be a square matrix");
+        let indptr = a.indptr().to_vec();
+        let indices = a.indices().to_vec();
+        let mut data = a.data().to_vec();
+
+        let mut diag = Vec::with_capacity(n);
+        for i in 0..n {
+            match indices[indptr[i]..indptr[i + 1]].binary_search(&i) {
+                Ok(p) => diag.push(indptr[i] + p),
+                Err(_) => return Err(LinalgError::Singular),
+            }
+        }
+
+        for i in 0..n {
+            for p in indptr[i]..diag[i] {
+                let k = indices[p];
+                if data[diag[k]] == 0.0 {
+                    return Err(LinalgError::Singular);
+                }
+                data[p] /= data[diag[k]];
+                let lik = data[p];
+                // Update the rest of row i, restricted to the existing pattern
+                for q in (p + 1)..indptr[i + 1] {
+                    let j = indices[q];
+                    if let Ok(r) = indices[diag[k]..indptr[k + 1]].binary_search(&j) {
+                        data[q] -= lik * data[diag[k] + r];
+                    }
+                }
+            }
+            if data[diag[i]] == 0.0 {
+                return Err(LinalgError::Singular);
+            }
+        }
+        Ok(Ilu0 { n: n, indptr: indptr, indices: indices, data: data, diag: diag })
+    }
+}
+
+impl LinearOperator for Ilu0 {
+    fn apply(&self, x: &Tensor<f64>) -> Tensor<f64> {
+        assert!(x.size() == self.n, "Preconditioner and vector must match");
+        let mut y: Vec<f64> = x.iter().collect();
+        // Forward substitution with the unit lower triangle
+        for i in 0..self.n {
+            for p in self.indptr[i]..self.diag[i] {
+                y[i] -= self.data[p] * y[self.indices[p]];
+            }
+        }
+        // Backward substitution with the upper triangle
+        for i in (0..self.n).rev() {
+            for p in (self.diag[i] + 1)..self.indptr[i + 1] {
+                y[i] -= self.data[p] * y[self.indices[p]];
+            }
+            y[i] /= self.data[self.diag[i]];
+        }
+        Tensor::new(y)
+    }
+}
+
+/// The result of an iterative solver.
+pub struct IterativeResult {
+    /// The approximate solution `x`.
+    pub solution: Tensor<f64>,
+    /// Whether the tolerance was reached within the maximum number of iterations.
+    pub converged: bool,
+    /// The number of iterations performed.
+    pub iterations: usize,
+    /// The relative residual norm `|b - Ax| / |b|` after each iteration.
+    pub residuals: Vec<f64>,
+}
+
+fn vdot(a: &Tensor<f64>, b: &Tensor<f64>) -> f64 {
+    a.iter().zip(b.iter()).fold(0.0, |acc, (x, y)| acc + x * y)
+}
+
+fn nrm2(a: &Tensor<f64>) -> f64 {
+    vdot(a, a).sqrt()
+}
+
+/// Returns `x + alpha * y`.
+fn axpy(x: &Tensor<f64>, alpha: f64, y: &Tensor<f64>) -> Tensor<f64> {
+    x + &(y * alpha)
+}
+
+/// Handles the trivial case `b = 0`, for which the solution is `x = 0`.
+fn zero_rhs(b: &Tensor<f64>) -> Option<IterativeResult> {
+    if nrm2(b) == 0.0 {
+        Some(IterativeResult { solution: Tensor::zeros(&[b.size()]), converged: true,
+                               iterations: 0, residuals: vec![] })
+    } else {
+        None
+    }
+}
+
+/// Solves `Ax = b` using the conjugate gradient method. `A` must be symmetric and positive
+/// definite, as must the preconditioner `m` (which approximates `A^-1`; pass `&Identity` for no
+/// preconditioning).
+///
+/// Iterates until `|b - Ax| <= tol * |b|` or `maxiter` iterations have been performed.
+///
+/// ```
+/// use numeric::Tensor;
+/// use numeric::linalg::{cg, Identity};
+///
+/// let a = Tensor::new(vec![4.0, 1.0, 1.0, 3.0]).reshape(&[2, 2]);
+/// let b = Tensor::new(vec![1.0, 2.0]);
+/// let res = cg(&a, &b, &Identity, 1e-10, 100);
+/// assert!(res.converged);
+/// ```
+pub fn cg<A, M>(a: &A, b: &Tensor<f64>, m: &M, tol: f64, maxiter: usize) -> IterativeResult
+        where A: LinearOperator, M: LinearOperator {
+    assert!(b.ndim() == 1, "`b` must be a vector");
+    if let Some(res) = zero_rhs(b) {
+        return res;
+    }
+    let bnorm = nrm2(b);
+    let mut x = Tensor::zeros(&[b.size()]);
+    let mut r = b.canonize();
+    let mut z = m.apply(&r);
+    let mut p = z.clone();
+    let mut rz = vdot(&r, &z);
+    let mut residuals = vec![];
+
+    for _ in 0..maxiter {
+        let ap = a.apply(&p);
+        let alpha = rz / vdot(&p, &ap);
+        x = axpy(&x, alpha, &p);
+        r = axpy(&r, -alpha, &ap);
+        residuals.push(nrm2(&r) / bnorm);
+        if residuals[residuals.len() - 1] <= tol {
+            break;
+        }
+        z = m.apply(&r);
+        let rz_new = vdot(&r, &z);
+        p = axpy(&z, rz_new / rz, &p);
+        rz = rz_new;
+    }
+    finish(x, residuals, tol)
+}
+
+/// Solves `Ax = b` using the biconjugate gradient stabilized method, which works for general
+/// (non-symmetric) matrices. The preconditioner `m` is applied from the right.
+///
+/// Iterates until `|b - Ax| <= tol * |b|` or `maxiter` iterations have been performed.
+pub fn bicgstab<A, M>(a: &A, b: &Tensor<f64>, m: &M, tol: f64, maxiter: usize)
+        -> IterativeResult where A: LinearOperator, M: LinearOperator {
+    assert!(b.ndim() == 1, "`b` must be a vector");
+    if let Some(res) = zero_rhs(b) {
+        return res;
+    }
+    let n = b.size();
+    let bnorm = nrm2(b);
+    let mut x = Tensor::zeros(&[n]);
+    let mut r = b.canonize();
+    let r_hat = r.clone();
+    let mut p = Tensor::zeros(&[n]);
+    let mut v = Tensor::zeros(&[n]);
+    let (mut rho, mut alpha, mut omega) = (1.0, 1.0, 1.0);
+    let mut residuals = vec![];
+
+    for _ in 0..maxiter {
+        let rho_new = vdot(&r_hat, &r);
+        if rho_new == 0.0 || omega == 0.0 {
+            // Breakdown
+            break;
+        }
+        let beta = (rho_new / rho) * (alpha / omega);
+        p = axpy(&r, beta, &axpy(&p, -omega, &v));
+        let p_hat = m.apply(&p);
+        v = a.apply(&p_hat);
+        alpha = rho_new / vdot(&r_hat, &v);
+        let s = axpy(&r, -alpha, &v);
+        if nrm2(&s) / bnorm <= tol {
+            x = axpy(&x, alpha, &p_hat);
+            residuals.push(nrm2(&s) / bnorm);
+            break;
+        }
+        let s_hat = m.apply(&s);
+        let t = a.apply(&s_hat);
+        omega = vdot(&t, &s) / vdot(&t, &t);
+        x = axpy(&axpy(&x, alpha, &p_hat), omega, &s_hat);
+        r = axpy(&s, -omega, &t);
+        residuals.push(nrm2(&r) / bnorm);
+        if residuals[residuals.len() - 1] <= tol {
+            break;
+        }
+        rho = rho_new;
+    }
+    finish(x, residuals, tol)
+}
+
+/// Solves `Ax = b` using the generalized minimal residual method, restarted every `restart`
+/// iterations. This works for general matrices. The preconditioner `m` is applied from the
+/// right.
+///
+/// Iterates until `|b - Ax| <= tol * |b|` or a total of `maxiter` (inner) iterations have been
+/// performed.
+pub fn gmres<A, M>(a: &A, b: &Tensor<f64>, m: &M, restart: usize, tol: f64, maxiter: usize)
+        -> IterativeResult where A: LinearOperator, M: LinearOperator {
+    assert!(b.ndim() == 1, "`b` must be a vector");
+    assert!(restart > 0, "`restart` must be positive");
+    if let Some(res) = zero_rhs(b) {
+        return res;
+    }
+    let bnorm = nrm2(b);
+    let mut x = Tensor::zeros(&[b.size()]);
+    let mut residuals = vec![];
+
+    while residuals.len() < maxiter {
+        let r = b - &a.apply(&x);
+        let beta = nrm2(&r);
+        if beta / bnorm <= tol {
+            break;
+        }
+
+        // Arnoldi process, with the Hessenberg matrix reduced to triangular form by Givens
+        // rotations as it is built
+        let mut v = vec![&r * (1.0 / beta)];
+        let mut h = vec![vec![0.0; restart]; restart + 1];
+        let mut g = vec![0.0; restart + 1];
+        let mut cs = vec![0.0; restart];
+        let mut sn = vec![0.0; restart];
+        g[0] = beta;
+        let mut k = 0;
+        let mut stalled = false;
+        while k < restart && residuals.len() < maxiter {
+            let mut w = a.apply(&m.apply(&v[k]));
+            for i in 0..(k + 1) {
+                h[i][k] = vdot(&w, &v[i]);
+                w = axpy(&w, -h[i][k], &v[i]);
+            }
+            h[k + 1][k] = nrm2(&w);
+            let breakdown = h[k + 1][k] == 0.0;
+            if !breakdown {
+                v.push(&w * (1.0 / h[k + 1][k]));
+            }
+
+            for i in 0..k {
+                let t = cs[i] * h[i][k] + sn[i] * h[i + 1][k];
+                h[i + 1][k] = -sn[i] * h[i][k] + cs[i] * h[i + 1][k];
+                h[i][k] = t;
+            }
+            let d = h[k][k].hypot(h[k + 1][k]);
+            if d == 0.0 {
+                // The operator is singular on the Krylov space, so the residual cannot be reduced
+                stalled = true;
+                break;
+            }
+            cs[k] = h[k][k] / d;
+            sn[k] = h[k + 1][k] / d;
+            h[k][k] = d;
+            h[k + 1][k] = 0.0;
+            g[k + 1] = -sn[k] * g[k];
+            g[k] = cs[k] * g[k];
+
+            k += 1;
+            residuals.push(g[k].abs() / bnorm);
+            if g[k].abs() / bnorm <= tol || breakdown {
+                break;
+            }
+        }
+
+        // Solve the triangular system and update the solution
+        let mut y = vec![0.0; k];
+        for i in (0..k).rev() {
+            let mut s = g[i];
+            for j in (i + 1)..k {
+                s -= h[i][j] * y[j];
+            }
+            y[i] = s / h[i][i];
+        }
+        let mut dx = Tensor::zeros(&[b.size()]);
+        for i in 0..k {
+            dx = axpy(&dx, y[i], &v[i]);
+        }
+        x = &x + &m.apply(&dx);
+
+        if stalled || residuals.last().map_or(false, |&r| r <= tol) {
+            break;
+        }
+    }
+    finish(x, residuals, tol)
+}
+
+/// Solves `Ax = b` using the minimum residual method. `A` must be symmetric, but can be
+/// indefinite. The preconditioner `m` must be symmetric and positive definite.
+///
+/// Iterates until the residual estimate drops below `tol * |b|` or `maxiter` iterations have
+/// been performed. When preconditioned, the residuals are measured in the norm induced by `m`.
+pub fn minres<A, M>(a: &A, b: &Tensor<f64>, m: &M, tol: f64, maxiter: usize)
+        -> IterativeResult where A: LinearOperator, M: LinearOperator {
+    assert!(b.ndim() == 1, "`b` must be a vector");
+    if let Some(res) = zero_rhs(b) {
+        return res;
+    }
+    let n = b.size();
+    let mut x = Tensor::zeros(&[n]);
+    let mut r1 = b.canonize();
+    let mut r2 = r1.clone();
+    let mut y = m.apply(&r1);
+    let beta1 = vdot(&r1, &y).sqrt();
+    let (mut beta, mut oldb) = (beta1, 0.0);
+    let (mut dbar, mut epsln, mut phibar) = (0.0, 0.0, beta1);
+    let (mut cs, mut sn) = (-1.0, 0.0);
+    let mut w = Tensor::zeros(&[n]);
+    let mut w2 = Tensor::zeros(&[n]);
+    let mut residuals = vec![];
+
+    for itn in 0..maxiter {
+        // Lanczos step
+        let v = &y * (1.0 / beta);
+        y = a.apply(&v);
+        if itn > 0 {
+            y = axpy(&y, -beta / oldb, &r1);
+        }
+        let alpha = vdot(&v, &y);
+        y = axpy(&y, -alpha / beta, &r2);
+        r1 = r2;
+        r2 = y;
+        y = m.apply(&r2);
+        oldb = beta;
+        beta = vdot(&r2, &y).sqrt();
+
+        // Apply the previous rotation, then compute and apply the next one
+        let oldeps = epsln;
+        let delta = cs * dbar + sn * alpha;
+        let gbar = sn * dbar - cs * alpha;
+        epsln = sn * beta;
+        dbar = -cs * beta;
+        let gamma = gbar.hypot(beta).max(::std::f64::EPSILON);
+        cs = gbar / gamma;
+        sn = beta / gamma;
+        let phi = cs * phibar;
+        phibar = sn * phibar;
+
+        let w1 = w2;
+        w2 = w;
+        w = &axpy(&axpy(&v, -oldeps, &w1), -delta, &w2) * (1.0 / gamma);
+        x = axpy(&x, phi, &w);
+
+        residuals.push(phibar / beta1);
+        if phibar / beta1 <= tol || beta == 0.0 {
+            break;
+        }
+    }
+    finish(x, residuals, tol)
+}
+
+fn finish(x: Tensor<f64>, residuals: Vec<f64>, tol: f64) -> IterativeResult {
+    let converged = residuals.last().map_or(false, |&r| r <= tol);
+    IterativeResult { solution: x, converged: converged, iterations: residuals.len(),
+                      residuals: residuals }
+}
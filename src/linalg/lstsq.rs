@@ -0,0 +1,117 @@
+use tensor::Tensor;
+use lapack;
+use linalg::LinalgError;
+use std::cmp::{min, max};
+
+/// The result of `Tensor::lstsq`.
+pub struct LstsqResult<T> {
+    /// The least-squares solution `x`. Has the same number of dimensions as `b`.
+    pub solution: Tensor<T>,
+    /// Sums of squared residuals for each column of `b`. This is empty unless `A` has full
+    /// column rank and more rows than columns.
+    pub residuals: Tensor<T>,
+    /// The effective rank of `A`.
+    pub rank: usize,
+    /// The singular values of `A` in descending order.
+    pub singular_values: Tensor<T>,
+}
+
+macro_rules! add_lstsq_impl {
+    ($t:ty, $gelsd:ident) => (
+        impl Tensor<$t> {
+            /// Finds the least-squares solution `x` that minimizes `|b - Ax|`. The input `b` can
+            /// either be a vector or a matrix with one right-hand side per column.
+            ///
+            /// Singular values smaller than `rcond` times the largest singular value are treated
+            /// as zero when determining the rank. If `rcond` is negative, machine precision is
+            /// used instead.
+            pub fn lstsq(&self, b: &Tensor<$t>, rcond: $t) -> Result<LstsqResult<$t>, LinalgError> {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                assert!(b.ndim() == 1 || b.ndim() == 2, "`b` must be a vector or a matrix");
+                assert!(self.dim(0) == b.dim(0), "`A` and `b` must match");
+
+                let m = self.dim(0);
+                let n = self.dim(1);
+                let nrhs = if b.ndim() == 1 { 1 } else { b.dim(1) };
+                let ldb = max(m, n);
+
+                // A must be transposed, since LAPACK is column-major. The right-hand sides are
+                // stored column by column in a buffer with room for the solution as well.
+                let mut a = self.transpose().canonize();
+                let mut bb: Tensor<$t> = Tensor::zeros(&[nrhs, ldb]);
+                {
+                    let b_ = if b.ndim() == 1 {
+                        b.canonize().reshape(&[-1, 1])
+                    } else {
+                        b.canonize()
+                    };
+                    let src = b_.slice();
+                    let mut data = bb.slice_mut();
+                    for i in 0..m {
+                        for j in 0..nrhs {
+                            data[j * ldb + i] = src[i * nrhs + j];
+                        }
+                    }
+                }
+
+                let mut s: Tensor<$t> = Tensor::zeros(&[min(m, n)]);
+                let mut rank = 0;
+                let mut info = 0;
+
+                let mut query: Tensor<$t> = Tensor::zeros(&[1]);
+                let mut iquery: Tensor<i32> = Tensor::zeros(&[1]);
+                lapack::$gelsd(m, n, nrhs, a.slice_mut(), m, bb.slice_mut(), ldb, s.slice_mut(),
+                               rcond, &mut rank, query.slice_mut(), -1, iquery.slice_mut(),
+                               &mut info);
+                let lwork = query.slice()[0] as usize;
+                let liwork = max(1, iquery.slice()[0] as usize);
+                let mut work: Tensor<$t> = Tensor::empty(&[lwork]);
+                let mut iwork: Tensor<i32> = Tensor::empty(&[liwork]);
+                lapack::$gelsd(m, n, nrhs, a.slice_mut(), m, bb.slice_mut(), ldb, s.slice_mut(),
+                               rcond, &mut rank, work.slice_mut(), lwork as isize,
+                               iwork.slice_mut(), &mut info);
+                if info < 0 {
+                    return Err(LinalgError::IllegalArgument(-info));
+                } else if info > 0 {
+                    return Err(LinalgError::NotConverged);
+                }
+
+                let rank = rank as usize;
+                let mut x: Tensor<$t> = Tensor::empty(&[n, nrhs]);
+                let mut residuals: Tensor<$t> = if rank == n && m > n {
+                    Tensor::zeros(&[nrhs])
+                } else {
+                    Tensor::zeros(&[0])
+                };
+                {
+                    let data = bb.slice();
+                    let mut xdata = x.slice_mut();
+                    for i in 0..n {
+                        for j in 0..nrhs {
+                            xdata[i * nrhs + j] = data[j * ldb + i];
+                        }
+                    }
+                    if residuals.size() > 0 {
+                        let mut rdata = residuals.slice_mut();
+                        for j in 0..nrhs {
+                            for i in n..m {
+                                rdata[j] += data[j * ldb + i] * data[j * ldb + i];
+                            }
+                        }
+                    }
+                }
+
+                let solution = if b.ndim() == 1 { x.reshape(&[n as isize]) } else { x };
+                Ok(LstsqResult {
+                    solution: solution,
+                    residuals: residuals,
+                    rank: rank,
+                    singular_values: s,
+                })
+            }
+        }
+    )
+}
+
+add_lstsq_impl!(f64, dgelsd);
+add_lstsq_impl!(f32, sgelsd);
@@ -0,0 +1,154 @@
+use tensor::Tensor;
+use lapack;
+use linalg::LinalgError;
+use std::cmp::min;
+
+/// A packed LU factorization `A = PLU` of a matrix, as returned by `Tensor::lu`. The
+/// factorization can be reused to solve for many right-hand sides without refactorizing.
+pub struct LuFactorization<T> {
+    /// `L` and `U` packed together, stored in column-major (LAPACK) order.
+    lu: Tensor<T>,
+    /// Row interchanges (1-based, as returned by LAPACK).
+    ipiv: Tensor<i32>,
+    m: usize,
+    n: usize,
+}
+
+impl<T> LuFactorization<T> {
+    /// Returns the row permutation as a vector `perm`, such that row `i` of `LU` is row
+    /// `perm[i]` of `A`.
+    pub fn permutation(&self) -> Vec<usize> {
+        let mut perm: Vec<usize> = (0..self.m).collect();
+        for (i, &p) in self.ipiv.data().iter().enumerate() {
+            perm.swap(i, (p - 1) as usize);
+        }
+        perm
+    }
+
+    /// Returns `true` if a diagonal element of `U` is exactly zero.
+    pub fn is_singular(&self) -> bool
+            where T: ::traits::NumericTrait {
+        let data = self.lu.data();
+        (0..min(self.m, self.n)).any(|i| data[i * self.m + i] == T::zero())
+    }
+}
+
+macro_rules! add_lu_impl {
+    ($t:ty, $getrf:ident, $getrs:ident) => (
+        impl Tensor<$t> {
+            /// Computes the LU factorization of a matrix with partial pivoting. The result can
+            /// be unpacked into `(P, L, U)` or used directly to solve linear equations.
+            ///
+            /// ```no_run
+            /// use numeric::Tensor;
+            ///
+            /// let a = Tensor::new(vec![1.0_f64, 0.5, 1.5, -1.0]).reshape(&[2, 2]);
+            /// let lu = a.lu();
+            /// let (p, l, u) = lu.unpack();
+            /// let x = lu.solve(&Tensor::ones(&[2])).unwrap();
+            /// ```
+            pub fn lu(&self) -> LuFactorization<$t> {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                let m = self.dim(0);
+                let n = self.dim(1);
+
+                // A must be transposed, since LAPACK is column-major.
+                let mut a = self.transpose().canonize();
+                let mut ipiv: Tensor<i32> = Tensor::empty(&[min(m, n)]);
+                let mut info = 0;
+
+                lapack::$getrf(m, n, a.slice_mut(), m, ipiv.slice_mut(), &mut info);
+                // A positive `info` only means that U is singular, which is not an error here.
+                if info < 0 {
+                    panic!("Illegal input ({})", -info);
+                }
+                LuFactorization { lu: a, ipiv: ipiv, m: m, n: n }
+            }
+        }
+
+        impl LuFactorization<$t> {
+            /// Returns the permutation matrix `P`.
+            pub fn p(&self) -> Tensor<$t> {
+                let perm = self.permutation();
+                let mut p: Tensor<$t> = Tensor::zeros(&[self.m, self.m]);
+                {
+                    let mut data = p.slice_mut();
+                    for (i, &j) in perm.iter().enumerate() {
+                        data[j * self.m + i] = 1.0;
+                    }
+                }
+                p
+            }
+
+            /// Returns the unit lower triangular (or trapezoidal) matrix `L`.
+            pub fn l(&self) -> Tensor<$t> {
+                let k = min(self.m, self.n);
+                let mut l: Tensor<$t> = Tensor::zeros(&[self.m, k]);
+                {
+                    let lu = self.lu.slice();
+                    let mut data = l.slice_mut();
+                    for i in 0..self.m {
+                        for j in 0..min(i, k) {
+                            data[i * k + j] = lu[j * self.m + i];
+                        }
+                        if i < k {
+                            data[i * k + i] = 1.0;
+                        }
+                    }
+                }
+                l
+            }
+
+            /// Returns the upper triangular (or trapezoidal) matrix `U`.
+            pub fn u(&self) -> Tensor<$t> {
+                let k = min(self.m, self.n);
+                let mut u: Tensor<$t> = Tensor::zeros(&[k, self.n]);
+                {
+                    let lu = self.lu.slice();
+                    let mut data = u.slice_mut();
+                    for i in 0..k {
+                        for j in i..self.n {
+                            data[i * self.n + j] = lu[j * self.m + i];
+                        }
+                    }
+                }
+                u
+            }
+
+            /// Unpacks the factorization into `(P, L, U)`, such that `A = PLU`.
+            pub fn unpack(&self) -> (Tensor<$t>, Tensor<$t>, Tensor<$t>) {
+                (self.p(), self.l(), self.u())
+            }
+
+            /// Solves `Ax = b` using the factorization. The input `b` can either be a vector or
+            /// a matrix with one right-hand side per column.
+            ///
+            /// Returns `Err(LinalgError::Singular)` if the matrix is singular.
+            pub fn solve(&self, b: &Tensor<$t>) -> Result<Tensor<$t>, LinalgError> {
+                assert!(self.m == self.n, "`A` must be a square matrix");
+                assert!(b.ndim() == 1 || b.ndim() == 2, "`b` must be a vector or a matrix");
+                assert!(b.dim(0) == self.n, "`A` and `b` must match");
+                if self.is_singular() {
+                    return Err(LinalgError::Singular);
+                }
+
+                let n = self.n;
+                let nrhs = if b.ndim() == 1 { 1 } else { b.dim(1) };
+                let mut x = if b.ndim() == 1 { b.canonize() } else { b.transpose().canonize() };
+                let mut info = 0;
+
+                lapack::$getrs(b'N', n, nrhs, self.lu.slice(), n, self.ipiv.slice(),
+                               x.slice_mut(), n, &mut info);
+                try!(LinalgError::from_info(info));
+                if b.ndim() == 1 {
+                    Ok(x)
+                } else {
+                    Ok(x.transpose().canonize())
+                }
+            }
+        }
+    )
+}
+
+add_lu_impl!(f64, dgetrf, dgetrs);
+add_lu_impl!(f32, sgetrf, sgetrs);
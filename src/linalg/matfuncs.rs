@@ -0,0 +1,356 @@
+//! Matrix functions. Apart from `expm` and `matrix_power`, these operate on the complex Schur
+//! form `A = ZTZ^H` (obtained from the real Schur form), where `T` is upper triangular.
+
+use tensor::Tensor;
+use linalg::{LinalgError, NormType};
+use num::complex::Complex;
+use num::traits::{Float, Zero, One, cast};
+
+/// Padé coefficients for the matrix exponential (Higham, 2005).
+const PADE3: [f64; 4] = [120., 60., 12., 1.];
+const PADE5: [f64; 6] = [30240., 15120., 3360., 420., 30., 1.];
+const PADE7: [f64; 8] = [17297280., 8648640., 1995840., 277200., 25200., 1512., 56., 1.];
+const PADE9: [f64; 10] = [17643225600., 8821612800., 2075673600., 302702400., 30270240.,
+                          2162160., 110880., 3960., 90., 1.];
+const PADE13: [f64; 14] = [64764752532480000., 32382376266240000., 7771770303897600.,
+                           1187353796428800., 129060195264000., 10559470521600.,
+                           670442572800., 33522128640., 1323241920., 40840800., 960960.,
+                           16380., 182., 1.];
+
+/// The largest 1-norm for which each Padé approximant is accurate to double precision.
+const THETA: [(usize, f64); 4] = [(3, 1.495585217958292e-2), (5, 2.539398330063230e-1),
+                                  (7, 9.504178996162932e-1), (9, 2.097847961257068e0)];
+const THETA13: f64 = 5.371920351148152;
+
+/// A complex Schur decomposition `A = ZTZ^H`, with `T` and `Z` stored as row-major `n x n`
+/// matrices.
+struct ComplexSchur<T> {
+    t: Vec<Complex<T>>,
+    z: Vec<Complex<T>>,
+    n: usize,
+}
+
+impl<T: Float> ComplexSchur<T> {
+    /// Converts a real Schur form to a complex Schur form, by applying Givens rotations that
+    /// eliminate the subdiagonal elements of the 2x2 blocks.
+    fn from_real(t: &[T], z: &[T], n: usize) -> ComplexSchur<T> {
+        let mut t: Vec<Complex<T>> = t.iter().map(|&v| Complex::new(v, T::zero())).collect();
+        let mut z: Vec<Complex<T>> = z.iter().map(|&v| Complex::new(v, T::zero())).collect();
+        let half: T = cast(0.5).unwrap();
+        for m in (1..n).rev() {
+            let sub = t[m * n + m - 1];
+            if sub.norm() > T::epsilon() * (t[(m - 1) * n + m - 1].norm() + t[m * n + m].norm()) {
+                // Shifted eigenvalue of the 2x2 block
+                let a = t[(m - 1) * n + m - 1];
+                let b = t[(m - 1) * n + m];
+                let d = t[m * n + m];
+                let disc = ((a - d).scale(half) * (a - d).scale(half) + b * sub).sqrt();
+                let mu = (a + d).scale(half) + disc - d;
+                let r = (mu.norm_sqr() + sub.norm_sqr()).sqrt();
+                let c = mu.unscale(r);
+                let s = sub.unscale(r);
+
+                // T = G T and T = T G^H, with G = [c* s; -s c]
+                for j in (m - 1)..n {
+                    let x = t[(m - 1) * n + j];
+                    let y = t[m * n + j];
+                    t[(m - 1) * n + j] = c.conj() * x + s * y;
+                    t[m * n + j] = -s * x + c * y;
+                }
+                for i in 0..(m + 1) {
+                    let x = t[i * n + m - 1];
+                    let y = t[i * n + m];
+                    t[i * n + m - 1] = x * c + y * s.conj();
+                    t[i * n + m] = -x * s.conj() + y * c.conj();
+                }
+                for i in 0..n {
+                    let x = z[i * n + m - 1];
+                    let y = z[i * n + m];
+                    z[i * n + m - 1] = x * c + y * s.conj();
+                    z[i * n + m] = -x * s.conj() + y * c.conj();
+                }
+            }
+            t[m * n + m - 1] = Complex::zero();
+        }
+        ComplexSchur { t: t, z: z, n: n }
+    }
+
+    /// Computes `Z F Z^H` and returns its real part.
+    fn transform_back(&self, f: &[Complex<T>]) -> Tensor<T> where T: Copy {
+        let n = self.n;
+        let zf = matmul(&self.z, f, n);
+        let mut y = Tensor::zeros(&[n, n]);
+        {
+            let mut data = y.slice_mut();
+            for i in 0..n {
+                for j in 0..n {
+                    let mut v = Complex::zero();
+                    for k in 0..n {
+                        v = v + zf[i * n + k] * self.z[j * n + k].conj();
+                    }
+                    data[i * n + j] = v.re;
+                }
+            }
+        }
+        y
+    }
+}
+
+fn matmul<T: Float>(a: &[Complex<T>], b: &[Complex<T>], n: usize) -> Vec<Complex<T>> {
+    let mut c = vec![Complex::zero(); n * n];
+    for i in 0..n {
+        for k in 0..n {
+            let v = a[i * n + k];
+            if v.is_zero() {
+                continue;
+            }
+            for j in 0..n {
+                c[i * n + j] = c[i * n + j] + v * b[k * n + j];
+            }
+        }
+    }
+    c
+}
+
+/// Square root of an upper triangular matrix.
+fn sqrt_triangular<T: Float>(t: &[Complex<T>], n: usize) -> Vec<Complex<T>> {
+    let mut r = vec![Complex::zero(); n * n];
+    for j in 0..n {
+        r[j * n + j] = t[j * n + j].sqrt();
+        for i in (0..j).rev() {
+            let mut s = t[i * n + j];
+            for k in (i + 1)..j {
+                s = s - r[i * n + k] * r[k * n + j];
+            }
+            let d = r[i * n + i] + r[j * n + j];
+            r[i * n + j] = if s.is_zero() { Complex::zero() } else { s / d };
+        }
+    }
+    r
+}
+
+/// Inverse of an upper triangular matrix.
+fn inv_triangular<T: Float>(t: &[Complex<T>], n: usize) -> Vec<Complex<T>> {
+    let mut r: Vec<Complex<T>> = vec![Complex::zero(); n * n];
+    for j in 0..n {
+        r[j * n + j] = Complex::<T>::one() / t[j * n + j];
+        for i in (0..j).rev() {
+            let mut s: Complex<T> = Complex::zero();
+            for k in (i + 1)..(j + 1) {
+                s = s + t[i * n + k] * r[k * n + j];
+            }
+            r[i * n + j] = -s / t[i * n + i];
+        }
+    }
+    r
+}
+
+/// Applies a scalar function to an upper triangular matrix using the Parlett recurrence.
+fn funm_triangular<T, F>(t: &[Complex<T>], n: usize, f: F) -> Vec<Complex<T>>
+        where T: Float, F: Fn(Complex<T>) -> Complex<T> {
+    let mut r = vec![Complex::zero(); n * n];
+    for i in 0..n {
+        r[i * n + i] = f(t[i * n + i]);
+    }
+    for p in 1..n {
+        for i in 0..(n - p) {
+            let j = i + p;
+            let mut s = t[i * n + j] * (r[j * n + j] - r[i * n + i]);
+            for k in (i + 1)..j {
+                s = s + t[i * n + k] * r[k * n + j] - r[i * n + k] * t[k * n + j];
+            }
+            r[i * n + j] = if s.is_zero() {
+                Complex::zero()
+            } else {
+                s / (t[j * n + j] - t[i * n + i])
+            };
+        }
+    }
+    r
+}
+
+/// Logarithm of an upper triangular matrix, using inverse scaling and squaring.
+fn logm_triangular<T: Float>(t: &[Complex<T>], n: usize) -> Vec<Complex<T>> {
+    let one: Complex<T> = Complex::one();
+    let two = one + one;
+    let quarter: T = cast(0.25).unwrap();
+
+    // Take square roots until T is close to the identity
+    let mut t = t.to_vec();
+    let mut k = 0;
+    loop {
+        let mut norm = T::zero();
+        for j in 0..n {
+            let mut s = T::zero();
+            for i in 0..(j + 1) {
+                let v = if i == j { t[i * n + j] - one } else { t[i * n + j] };
+                s = s + v.norm();
+            }
+            norm = norm.max(s);
+        }
+        if norm <= quarter || k >= 64 {
+            break;
+        }
+        t = sqrt_triangular(&t, n);
+        k += 1;
+    }
+
+    // log(I + X) = 2 atanh(Z), where Z = X (2I + X)^-1
+    let mut x = t.clone();
+    let mut x2 = t;
+    for i in 0..n {
+        x[i * n + i] = x[i * n + i] - one;
+        x2[i * n + i] = x2[i * n + i] + one;
+    }
+    let z = matmul(&x, &inv_triangular(&x2, n), n);
+    let zz = matmul(&z, &z, n);
+    let mut term = z.clone();
+    let mut sum = z;
+    for j in 1..30 {
+        term = matmul(&term, &zz, n);
+        let d: T = cast(2 * j + 1).unwrap();
+        for i in 0..(n * n) {
+            sum[i] = sum[i] + term[i].unscale(d);
+        }
+    }
+    let scale = two.re.powi(k as i32 + 1);
+    sum.iter().map(|v| v.scale(scale)).collect()
+}
+
+macro_rules! add_matfuncs_impl {
+    ($t:ty) => (
+        impl Tensor<$t> {
+            /// Raises a square matrix to the integer power `n`. For negative `n`, the inverse is
+            /// raised to `-n`, which fails if the matrix is singular.
+            pub fn matrix_power(&self, n: isize) -> Result<Tensor<$t>, LinalgError> {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                assert!(self.dim(0) == self.dim(1), "`A` must be a square matrix");
+
+                let mut base = if n < 0 { try!(self.inv()) } else { self.canonize() };
+                let mut e = n.unsigned_abs();
+                let mut result: Tensor<$t> = Tensor::eye(self.dim(0));
+                // Exponentiation by squaring
+                while e > 0 {
+                    if e & 1 == 1 {
+                        result = result.dot(&base);
+                    }
+                    e >>= 1;
+                    if e > 0 {
+                        base = base.dot(&base);
+                    }
+                }
+                Ok(result)
+            }
+
+            /// Computes the matrix exponential using a Padé approximation with scaling and
+            /// squaring.
+            pub fn expm(&self) -> Tensor<$t> {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                assert!(self.dim(0) == self.dim(1), "`A` must be a square matrix");
+
+                let n = self.dim(0);
+                let ident: Tensor<$t> = Tensor::eye(n);
+                let norm = self.norm(NormType::P(1.0)) as f64;
+
+                let a = self.canonize();
+                let a2 = a.dot(&a);
+                for &(m, theta) in THETA.iter() {
+                    if norm <= theta {
+                        let b: &[f64] = match m {
+                            3 => &PADE3,
+                            5 => &PADE5,
+                            7 => &PADE7,
+                            _ => &PADE9,
+                        };
+                        let mut u = &ident * (b[1] as $t);
+                        let mut v = &ident * (b[0] as $t);
+                        let mut p = ident.clone();
+                        for k in 1..(m / 2 + 1) {
+                            p = p.dot(&a2);
+                            u = u + &(&p * (b[2 * k + 1] as $t));
+                            v = v + &(&p * (b[2 * k] as $t));
+                        }
+                        let u = a.dot(&u);
+                        return (&v - &u).solve(&(&v + &u));
+                    }
+                }
+
+                // Scale so that the norm is within the range of the degree 13 approximant
+                let s = if norm > THETA13 { (norm / THETA13).log2().ceil() as i32 } else { 0 };
+                let scale = (2.0 as $t).powi(-s);
+                let a = &a * scale;
+                let a2 = &a2 * (scale * scale);
+                let a4 = a2.dot(&a2);
+                let a6 = a4.dot(&a2);
+                let b = PADE13;
+
+                let u = a6.dot(&(&(&a6 * (b[13] as $t)) + &(&(&a4 * (b[11] as $t)) +
+                                                            &(&a2 * (b[9] as $t)))));
+                let u = u + &(&a6 * (b[7] as $t)) + &(&a4 * (b[5] as $t)) +
+                        &(&a2 * (b[3] as $t)) + &(&ident * (b[1] as $t));
+                let u = a.dot(&u);
+                let v = a6.dot(&(&(&a6 * (b[12] as $t)) + &(&(&a4 * (b[10] as $t)) +
+                                                            &(&a2 * (b[8] as $t)))));
+                let v = v + &(&a6 * (b[6] as $t)) + &(&a4 * (b[4] as $t)) +
+                        &(&a2 * (b[2] as $t)) + &(&ident * (b[0] as $t));
+
+                let mut r = (&v - &u).solve(&(&v + &u));
+                for _ in 0..s {
+                    r = r.dot(&r);
+                }
+                r
+            }
+
+            /// Computes the principal square root of a matrix, such that `sqrtm(A)^2 = A`.
+            ///
+            /// Matrices with eigenvalues on the negative real axis do not have a real square
+            /// root. In that case, only the real part of the complex principal square root is
+            /// returned.
+            pub fn sqrtm(&self) -> Result<Tensor<$t>, LinalgError> {
+                let cs = try!(self.complex_schur());
+                Ok(cs.transform_back(&sqrt_triangular(&cs.t, cs.n)))
+            }
+
+            /// Computes the principal logarithm of a matrix, the inverse of `expm`.
+            ///
+            /// Returns `Err(LinalgError::Singular)` if the matrix is singular. Matrices with
+            /// eigenvalues on the negative real axis do not have a real logarithm. In that case,
+            /// only the real part of the complex principal logarithm is returned.
+            pub fn logm(&self) -> Result<Tensor<$t>, LinalgError> {
+                let cs = try!(self.complex_schur());
+                for i in 0..cs.n {
+                    if cs.t[i * cs.n + i].is_zero() {
+                        return Err(LinalgError::Singular);
+                    }
+                }
+                Ok(cs.transform_back(&logm_triangular(&cs.t, cs.n)))
+            }
+
+            /// Evaluates a general matrix function, defined by its scalar counterpart `f`, using
+            /// the Schur-Parlett algorithm. Only the real part of the result is returned.
+            ///
+            /// ```no_run
+            /// use numeric::Tensor;
+            ///
+            /// let a = Tensor::new(vec![1.0_f64, 0.5, 0.0, 2.0]).reshape(&[2, 2]);
+            /// let c = a.funm(|z| z.cos()).unwrap();
+            /// ```
+            ///
+            /// The Parlett recurrence breaks down if the matrix has repeated eigenvalues that do
+            /// not decouple (e.g. a non-diagonalizable matrix), producing non-finite values.
+            pub fn funm<F>(&self, f: F) -> Result<Tensor<$t>, LinalgError>
+                    where F: Fn(Complex<$t>) -> Complex<$t> {
+                let cs = try!(self.complex_schur());
+                Ok(cs.transform_back(&funm_triangular(&cs.t, cs.n, f)))
+            }
+
+            fn complex_schur(&self) -> Result<ComplexSchur<$t>, LinalgError> {
+                let (t, z) = try!(self.schur());
+                Ok(ComplexSchur::from_real(t.slice(), z.slice(), self.dim(0)))
+            }
+        }
+    )
+}
+
+add_matfuncs_impl!(f64);
+add_matfuncs_impl!(f32);
@@ -13,13 +13,88 @@
 //!
 //! let x = a.solve(&b);
 //! ```
+//!
+//! # Inverse and determinant
+//!
+//! Functions that can fail due to a singular matrix return a `Result` with a `LinalgError`:
+//!
+//! ```no_run
+//! use numeric::Tensor;
+//!
+//! let a = Tensor::new(vec![4.0_f64, 7.0, 2.0, 6.0]).reshape(&[2, 2]);
+//!
+//! let ainv = a.inv().unwrap();
+//! let d = a.det();
+//! ```
 
 use tensor::Tensor;
 use std::cmp::min;
+use std::error::Error;
+use std::fmt;
 use num::traits::Zero;
+use traits::NumericTrait;
 
 mod solve;
 mod svd;
+mod inv;
+mod det;
+mod complex;
+mod lu;
+mod qr;
+mod cholesky;
+mod eig;
+mod lstsq;
+mod norm;
+mod schur;
+mod matfuncs;
+mod iterative;
+
+pub use self::lu::LuFactorization;
+pub use self::qr::QrMode;
+pub use self::lstsq::LstsqResult;
+pub use self::norm::NormType;
+pub use self::iterative::{LinearOperator, Identity, Jacobi, Ilu0, IterativeResult,
+                          cg, bicgstab, gmres, minres};
+
+/// Errors that can occur in linear algebra routines.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LinalgError {
+    /// The matrix is singular, so the operation is not defined.
+    Singular,
+    /// The matrix is not positive definite.
+    NotPositiveDefinite,
+    /// An iterative algorithm failed to converge.
+    NotConverged,
+    /// An argument passed to LAPACK had an illegal value. Holds the (1-based) argument number.
+    IllegalArgument(i32),
+}
+
+impl LinalgError {
+    /// Converts a LAPACK `info` return value to a `Result`, treating positive values as a
+    /// singular matrix.
+    fn from_info(info: i32) -> Result<(), LinalgError> {
+        if info < 0 {
+            Err(LinalgError::IllegalArgument(-info))
+        } else if info > 0 {
+            Err(LinalgError::Singular)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl fmt::Display for LinalgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LinalgError::Singular => write!(f, "Singular matrix"),
+            LinalgError::NotPositiveDefinite => write!(f, "Matrix is not positive definite"),
+            LinalgError::NotConverged => write!(f, "Did not converge"),
+            LinalgError::IllegalArgument(i) => write!(f, "Illegal input ({})", i),
+        }
+    }
+}
+
+impl Error for LinalgError {}
 
 /// If passed a vector, creates a diagonal matrix with the vector as its diagonal.
 /// If passed a matrix, the diagonal is extracted and returned.
@@ -40,3 +115,19 @@ pub fn diag<T: Copy + Zero>(a: &Tensor<T>) -> Tensor<T> {
         b
     }
 }
+
+impl<T: NumericTrait> Tensor<T> {
+    /// Returns the sum along a diagonal of a matrix. A positive `offset` refers to a diagonal
+    /// above the main diagonal and a negative `offset` to one below.
+    pub fn trace(&self, offset: isize) -> T {
+        assert!(self.ndim() == 2, "Can only compute trace of a matrix (2D)");
+        let (i0, j0) = if offset >= 0 { (0, offset as usize) } else { ((-offset) as usize, 0) };
+        let mut s = T::zero();
+        let mut k = 0;
+        while i0 + k < self.dim(0) && j0 + k < self.dim(1) {
+            s = s + self[(i0 + k, j0 + k)];
+            k += 1;
+        }
+        s
+    }
+}
@@ -0,0 +1,168 @@
+use tensor::Tensor;
+use num::traits::{Float, cast};
+use linalg::LinalgError;
+use std::cmp::max;
+
+/// The order of a norm, used by `Tensor::norm` and `Tensor::cond`.
+///
+/// | Order      | Vector norm                   | Matrix norm                 |
+/// |------------|-------------------------------|-----------------------------|
+/// | `Fro`      | 2-norm                        | Frobenius norm              |
+/// | `Nuc`      | -                             | Nuclear norm                |
+/// | `Inf`      | `max(abs(x))`                 | Maximum absolute row sum    |
+/// | `NegInf`   | `min(abs(x))`                 | Minimum absolute row sum    |
+/// | `P(0.0)`   | Number of non-zero elements   | -                           |
+/// | `P(1.0)`   | `sum(abs(x))`                 | Maximum absolute column sum |
+/// | `P(-1.0)`  | `sum(abs(x)^-1)^-1`           | Minimum absolute column sum |
+/// | `P(2.0)`   | 2-norm                        | Largest singular value      |
+/// | `P(-2.0)`  | `sum(abs(x)^-2)^(-1/2)`       | Smallest singular value     |
+/// | `P(p)`     | `sum(abs(x)^p)^(1/p)`         | -                           |
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum NormType {
+    Fro,
+    Nuc,
+    Inf,
+    NegInf,
+    P(f64),
+}
+
+fn vector_norm<T: Float>(data: &[T], ord: NormType) -> T {
+    match ord {
+        NormType::Fro => vector_norm(data, NormType::P(2.0)),
+        NormType::P(p) if p == 2.0 => {
+            data.iter().fold(T::zero(), |acc, &v| acc + v * v).sqrt()
+        },
+        NormType::Inf => data.iter().fold(T::zero(), |acc, &v| acc.max(v.abs())),
+        NormType::NegInf => data.iter().fold(T::infinity(), |acc, &v| acc.min(v.abs())),
+        NormType::P(p) if p.is_infinite() => {
+            vector_norm(data, if p > 0.0 { NormType::Inf } else { NormType::NegInf })
+        },
+        NormType::P(p) if p == 0.0 => {
+            cast(data.iter().filter(|&&v| v != T::zero()).count()).unwrap()
+        },
+        NormType::P(p) if p == 1.0 => data.iter().fold(T::zero(), |acc, &v| acc + v.abs()),
+        NormType::P(p) => {
+            let p: T = cast(p).unwrap();
+            data.iter().fold(T::zero(), |acc, &v| acc + v.abs().powf(p)).powf(T::one() / p)
+        },
+        NormType::Nuc => panic!("Nuclear norm is only defined for matrices"),
+    }
+}
+
+macro_rules! add_norm_impl {
+    ($t:ty) => (
+        impl Tensor<$t> {
+            /// Computes the norm of a vector or a matrix. See `NormType` for the supported
+            /// norms.
+            ///
+            /// ```no_run
+            /// use numeric::Tensor;
+            /// use numeric::linalg::NormType;
+            ///
+            /// let a = Tensor::new(vec![3.0_f64, 4.0]);
+            /// let n = a.norm(NormType::P(2.0)); // 5
+            /// ```
+            pub fn norm(&self, ord: NormType) -> $t {
+                if self.ndim() == 1 {
+                    vector_norm(self.canonize().slice(), ord)
+                } else if self.ndim() == 2 {
+                    let abs = self.abs_values();
+                    match ord {
+                        NormType::Fro => vector_norm(abs.slice(), NormType::P(2.0)),
+                        NormType::Nuc => self.svdvals().sum(),
+                        NormType::Inf => abs.sum_axis(1).max(),
+                        NormType::NegInf => abs.sum_axis(1).min(),
+                        NormType::P(p) if p == 1.0 => abs.sum_axis(0).max(),
+                        NormType::P(p) if p == -1.0 => abs.sum_axis(0).min(),
+                        NormType::P(p) if p == 2.0 => self.svdvals().max(),
+                        NormType::P(p) if p == -2.0 => self.svdvals().min(),
+                        NormType::P(p) if p.is_infinite() => {
+                            self.norm(if p > 0.0 { NormType::Inf } else { NormType::NegInf })
+                        },
+                        NormType::P(_) => panic!("Invalid norm order for matrices"),
+                    }
+                } else {
+                    panic!("Can only compute norm of vectors and matrices");
+                }
+            }
+
+            /// Computes the vector norm along an axis. The axis is removed from the result.
+            pub fn norm_axis(&self, ord: NormType, axis: usize) -> Tensor<$t> {
+                assert!(axis < self.ndim(), "Reduced axis must exist");
+                let last = self.ndim() - 1;
+                let t = if axis == last {
+                    self.canonize()
+                } else {
+                    self.swapaxes(axis, last).canonize()
+                };
+                let d = t.dim(last);
+                let shape: Vec<usize> = t.shape()[..last].to_vec();
+
+                let mut y: Tensor<$t> = Tensor::empty(&shape);
+                {
+                    let src = t.slice();
+                    let mut data = y.slice_mut();
+                    for i in 0..data.len() {
+                        data[i] = vector_norm(&src[i * d..(i + 1) * d], ord);
+                    }
+                }
+                y
+            }
+
+            /// Computes the condition number of a matrix using the given norm. `NormType::P(2.0)`
+            /// is the most common choice and is computed as the ratio of the largest and the
+            /// smallest singular value.
+            ///
+            /// Singular matrices have an infinite condition number.
+            pub fn cond(&self, ord: NormType) -> $t {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                match ord {
+                    NormType::P(p) if p == 2.0 || p == -2.0 => {
+                        let s = self.svdvals();
+                        let c = s.max() / s.min();
+                        if p > 0.0 { c } else { 1.0 / c }
+                    },
+                    _ => {
+                        match self.inv() {
+                            Ok(ainv) => self.norm(ord) * ainv.norm(ord),
+                            Err(LinalgError::Singular) => <$t as Float>::infinity(),
+                            Err(e) => panic!("{}", e),
+                        }
+                    },
+                }
+            }
+
+            /// Returns the rank of a matrix, computed as the number of singular values greater
+            /// than `tol`. If `tol` is `None`, `max(s) * max(m, n) * eps` is used.
+            pub fn matrix_rank(&self, tol: Option<$t>) -> usize {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                let s = self.svdvals();
+                if s.size() == 0 {
+                    return 0;
+                }
+                let tol = match tol {
+                    Some(v) => v,
+                    None => {
+                        let mn = max(self.dim(0), self.dim(1)) as $t;
+                        s.max() * mn * <$t as Float>::epsilon()
+                    },
+                };
+                s.iter().filter(|&v| v > tol).count()
+            }
+
+            fn abs_values(&self) -> Tensor<$t> {
+                let mut t = self.canonize();
+                {
+                    let mut data = t.mem_slice_mut();
+                    for v in data.iter_mut() {
+                        *v = v.abs();
+                    }
+                }
+                t
+            }
+        }
+    )
+}
+
+add_norm_impl!(f64);
+add_norm_impl!(f32);
@@ -0,0 +1,86 @@
+use tensor::Tensor;
+use lapack;
+use std::cmp::min;
+
+/// Determines what `Tensor::qr` computes.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum QrMode {
+    /// `Q` is `m x k` and `R` is `k x n`, where `k = min(m, n)`.
+    Reduced,
+    /// `Q` is `m x m` and `R` is `m x n`.
+    Complete,
+    /// Only `R` (`k x n`) is computed.
+    R,
+}
+
+macro_rules! add_qr_impl {
+    ($t:ty, $geqrf:ident, $orgqr:ident) => (
+        impl Tensor<$t> {
+            /// Computes the QR factorization of a matrix, such that `A = QR`, where `Q` is
+            /// orthonormal and `R` is upper triangular. `Q` is `None` if `mode` is `QrMode::R`.
+            pub fn qr(&self, mode: QrMode) -> (Option<Tensor<$t>>, Tensor<$t>) {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                let m = self.dim(0);
+                let n = self.dim(1);
+                let k = min(m, n);
+
+                // A must be transposed, since LAPACK is column-major.
+                let mut a = self.transpose().canonize();
+                let mut tau: Tensor<$t> = Tensor::zeros(&[k]);
+                let mut info = 0;
+
+                let mut query: Tensor<$t> = Tensor::zeros(&[1]);
+                lapack::$geqrf(m, n, a.slice_mut(), m, tau.slice_mut(), query.slice_mut(), -1,
+                               &mut info);
+                let lwork = query.slice()[0] as usize;
+                let mut work: Tensor<$t> = Tensor::empty(&[lwork]);
+                lapack::$geqrf(m, n, a.slice_mut(), m, tau.slice_mut(), work.slice_mut(),
+                               lwork as isize, &mut info);
+                if info < 0 {
+                    panic!("Illegal input ({})", -info);
+                }
+
+                let rrows = if mode == QrMode::Complete { m } else { k };
+                let mut r: Tensor<$t> = Tensor::zeros(&[rrows, n]);
+                {
+                    let adata = a.slice();
+                    let mut data = r.slice_mut();
+                    for i in 0..k {
+                        for j in i..n {
+                            data[i * n + j] = adata[j * m + i];
+                        }
+                    }
+                }
+
+                if mode == QrMode::R {
+                    return (None, r);
+                }
+
+                let qcols = if mode == QrMode::Complete { m } else { k };
+                let mut q: Tensor<$t> = Tensor::zeros(&[qcols, m]);
+                {
+                    let adata = a.slice();
+                    let mut data = q.slice_mut();
+                    for i in 0..min(qcols, n) * m {
+                        data[i] = adata[i];
+                    }
+                }
+
+                lapack::$orgqr(m, qcols, k, q.slice_mut(), m, tau.slice(), query.slice_mut(), -1,
+                               &mut info);
+                let lwork = query.slice()[0] as usize;
+                let mut work: Tensor<$t> = Tensor::empty(&[lwork]);
+                lapack::$orgqr(m, qcols, k, q.slice_mut(), m, tau.slice(), work.slice_mut(),
+                               lwork as isize, &mut info);
+                if info < 0 {
+                    panic!("Illegal input ({})", -info);
+                }
+
+                (Some(q.transpose().canonize()), r)
+            }
+        }
+    )
+}
+
+add_qr_impl!(f64, dgeqrf, dorgqr);
+add_qr_impl!(f32, sgeqrf, sorgqr);
@@ -0,0 +1,78 @@
+use tensor::Tensor;
+use lapack;
+use linalg::LinalgError;
+
+macro_rules! add_schur_impl {
+    ($t:ty, $gehrd:ident, $orghr:ident, $hseqr:ident) => (
+        impl Tensor<$t> {
+            /// Computes the real Schur decomposition `A = ZTZ^T`, returned as `(T, Z)`. The
+            /// matrix `Z` is orthogonal and `T` is quasi upper triangular, with 1x1 blocks for
+            /// real eigenvalues and 2x2 blocks for complex conjugate pairs along the diagonal.
+            pub fn schur(&self) -> Result<(Tensor<$t>, Tensor<$t>), LinalgError> {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                assert!(self.dim(0) == self.dim(1), "`A` must be a square matrix");
+
+                let n = self.dim(0);
+                if n == 0 {
+                    return Ok((Tensor::zeros(&[0, 0]), Tensor::zeros(&[0, 0])));
+                }
+
+                // A must be transposed, since LAPACK is column-major.
+                let mut h = self.transpose().canonize();
+                let mut tau: Tensor<$t> = Tensor::zeros(&[n]);
+                let mut info = 0;
+
+                // Reduce to upper Hessenberg form
+                let mut query: Tensor<$t> = Tensor::zeros(&[1]);
+                lapack::$gehrd(n, 1, n, h.slice_mut(), n, tau.slice_mut(), query.slice_mut(), -1,
+                               &mut info);
+                let lwork = query.slice()[0] as usize;
+                let mut work: Tensor<$t> = Tensor::empty(&[lwork]);
+                lapack::$gehrd(n, 1, n, h.slice_mut(), n, tau.slice_mut(), work.slice_mut(),
+                               lwork as isize, &mut info);
+                try!(LinalgError::from_info(info));
+
+                // Form the orthogonal matrix from the elementary reflectors stored below the
+                // subdiagonal, then clear them so that `h` is a proper Hessenberg matrix.
+                let mut z = h.clone();
+                lapack::$orghr(n, 1, n, z.slice_mut(), n, tau.slice(), query.slice_mut(), -1,
+                               &mut info);
+                let lwork = query.slice()[0] as usize;
+                let mut work: Tensor<$t> = Tensor::empty(&[lwork]);
+                lapack::$orghr(n, 1, n, z.slice_mut(), n, tau.slice(), work.slice_mut(),
+                               lwork as isize, &mut info);
+                try!(LinalgError::from_info(info));
+                {
+                    let mut data = h.slice_mut();
+                    for j in 0..n {
+                        for i in (j + 2)..n {
+                            data[j * n + i] = 0.0;
+                        }
+                    }
+                }
+
+                // Compute the Schur form of the Hessenberg matrix
+                let mut wr: Tensor<$t> = Tensor::zeros(&[n]);
+                let mut wi: Tensor<$t> = Tensor::zeros(&[n]);
+                lapack::$hseqr(b'S', b'V', n, 1, n, h.slice_mut(), n, wr.slice_mut(),
+                               wi.slice_mut(), z.slice_mut(), n, query.slice_mut(), -1,
+                               &mut info);
+                let lwork = query.slice()[0] as usize;
+                let mut work: Tensor<$t> = Tensor::empty(&[lwork]);
+                lapack::$hseqr(b'S', b'V', n, 1, n, h.slice_mut(), n, wr.slice_mut(),
+                               wi.slice_mut(), z.slice_mut(), n, work.slice_mut(),
+                               lwork as isize, &mut info);
+                if info < 0 {
+                    return Err(LinalgError::IllegalArgument(-info));
+                } else if info > 0 {
+                    return Err(LinalgError::NotConverged);
+                }
+
+                Ok((h.transpose().canonize(), z.transpose().canonize()))
+            }
+        }
+    )
+}
+
+add_schur_impl!(f64, dgehrd, dorghr, dhseqr);
+add_schur_impl!(f32, sgehrd, sorghr, shseqr);
@@ -1,27 +1,54 @@
-use tensor::Tensor;
+use tensor::{Tensor, Index};
+use linalg::LinalgError;
 use lapack;
 
+/// Converts `b` (a vector or a matrix with one right-hand side per column) to the column-major
+/// layout expected by LAPACK. Returns the buffer and the number of right-hand sides.
+fn to_lapack_rhs<T: Copy>(b: &Tensor<T>) -> (Tensor<T>, usize) {
+    assert!(b.ndim() == 1 || b.ndim() == 2, "`b` must be a vector or a matrix");
+    if b.ndim() == 1 {
+        (b.canonize(), 1)
+    } else {
+        (b.transpose().canonize(), b.dim(1))
+    }
+}
+
+/// Converts the solution back from the column-major layout produced by LAPACK.
+fn from_lapack_rhs<T: Copy>(x: Tensor<T>, b: &Tensor<T>) -> Tensor<T> {
+    if b.ndim() == 1 {
+        x
+    } else {
+        x.transpose().canonize()
+    }
+}
+
 macro_rules! add_solve_impl {
-    ($t:ty, $gesv:ident) => (
+    ($t:ty, $gesv:ident, $trtrs:ident, $potrs:ident, $posv:ident, $gbsv:ident) => (
         impl Tensor<$t> {
             /// Solves the linear equation `Ax = b` and returns `x`. The matrix `A` is `self` and
-            /// must be a square matrix. The input `b` must be a vector.
+            /// must be a square matrix. The input `b` can either be a vector or a matrix with
+            /// one right-hand side per column.
+            ///
+            /// A batch of equations can be solved at once by passing `A` of shape `[B, n, n]`
+            /// together with `b` of shape `[B, n]` or `[B, n, k]`.
             ///
             /// Panics if matrix is singular.
             pub fn solve(&self, b: &Tensor<$t>) -> Tensor<$t> {
+                if self.ndim() == 3 {
+                    return self.solve_batched(b);
+                }
                 assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
                 assert!(self.shape()[0] == self.shape()[1], "`A` must be a square matrix");
-                assert!(b.ndim() == 1, "`b` must be a vector (1D)");
-                assert!(self.shape()[0] == b.size(), "`A` and `b` must match");
+                assert!(self.shape()[0] == b.dim(0), "`A` and `b` must match");
 
                 // A must be transposed, since LAPACK is column-major.
                 let mut a_ = self.transpose().canonize();
-                let mut b_ = b.canonize();
+                let (mut b_, nrhs) = to_lapack_rhs(b);
                 let mut info = 0;
 
                 let n = self.shape()[0];
                 let mut ipiv: Tensor<i32> = Tensor::empty(&[n]);
-                lapack::$gesv(n, 1, a_.slice_mut(), n, ipiv.slice_mut(), b_.slice_mut(), n,
+                lapack::$gesv(n, nrhs, a_.slice_mut(), n, ipiv.slice_mut(), b_.slice_mut(), n,
                               &mut info);
                 // TODO: Change this to a recoverable failure instead of a panic?
                 if info < 0 {
@@ -29,11 +56,134 @@ macro_rules! add_solve_impl {
                 } else if info > 0 {
                     panic!("Singular matrix");
                 }
-                b_
+                from_lapack_rhs(b_, b)
+            }
+
+            fn solve_batched(&self, b: &Tensor<$t>) -> Tensor<$t> {
+                assert!(b.ndim() == 2 || b.ndim() == 3, "`b` must have 2 or 3 dimensions");
+                assert!(self.dim(0) == b.dim(0), "`A` and `b` must have the same batch size");
+
+                let mut x: Tensor<$t> = Tensor::empty(b.shape());
+                if self.dim(0) == 0 {
+                    return x;
+                }
+                let step = x.size() / x.dim(0);
+                {
+                    let mut data = x.slice_mut();
+                    for i in 0..self.dim(0) {
+                        let xi = self.index(&[Index(i as isize)])
+                                     .solve(&b.index(&[Index(i as isize)]));
+                        data[i * step..(i + 1) * step].copy_from_slice(xi.slice());
+                    }
+                }
+                x
+            }
+
+            /// Solves `Ax = b`, where `A` is a triangular matrix. Only the lower (if `lower` is
+            /// true) or upper triangle of `A` is referenced.
+            ///
+            /// Returns `Err(LinalgError::Singular)` if the matrix is singular.
+            pub fn solve_triangular(&self, b: &Tensor<$t>, lower: bool)
+                    -> Result<Tensor<$t>, LinalgError> {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                assert!(self.dim(0) == self.dim(1), "`A` must be a square matrix");
+                assert!(self.dim(0) == b.dim(0), "`A` and `b` must match");
+
+                // LAPACK sees the transpose of `A`, so we flip the triangle and solve the
+                // transposed system instead of copying.
+                let n = self.dim(0);
+                let a = self.canonize();
+                let (mut b_, nrhs) = to_lapack_rhs(b);
+                let uplo = if lower { b'U' } else { b'L' };
+                let mut info = 0;
+
+                lapack::$trtrs(uplo, b'T', b'N', n, nrhs, a.slice(), n, b_.slice_mut(), n,
+                               &mut info);
+                try!(LinalgError::from_info(info));
+                Ok(from_lapack_rhs(b_, b))
+            }
+
+            /// Solves `Ax = b` given the Cholesky factor of `A` (as returned by
+            /// `cholesky(lower)`). The factor is `self` and `lower` must match the one used to
+            /// compute it.
+            pub fn cho_solve(&self, b: &Tensor<$t>, lower: bool)
+                    -> Result<Tensor<$t>, LinalgError> {
+                assert!(self.ndim() == 2, "Cholesky factor must be a matrix (2D)");
+                assert!(self.dim(0) == self.dim(1), "Cholesky factor must be a square matrix");
+                assert!(self.dim(0) == b.dim(0), "Cholesky factor and `b` must match");
+
+                let n = self.dim(0);
+                let c = self.canonize();
+                let (mut b_, nrhs) = to_lapack_rhs(b);
+                let uplo = if lower { b'U' } else { b'L' };
+                let mut info = 0;
+
+                lapack::$potrs(uplo, n, nrhs, c.slice(), n, b_.slice_mut(), n, &mut info);
+                try!(LinalgError::from_info(info));
+                Ok(from_lapack_rhs(b_, b))
+            }
+
+            /// Solves `Ax = b`, where `A` is symmetric and positive definite. This uses a
+            /// Cholesky factorization, which is about twice as fast as `solve`. Only the lower
+            /// triangle of `A` is referenced.
+            ///
+            /// Returns `Err(LinalgError::NotPositiveDefinite)` if `A` is not positive definite.
+            pub fn solve_posdef(&self, b: &Tensor<$t>) -> Result<Tensor<$t>, LinalgError> {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                assert!(self.dim(0) == self.dim(1), "`A` must be a square matrix");
+                assert!(self.dim(0) == b.dim(0), "`A` and `b` must match");
+
+                let n = self.dim(0);
+                let mut a = self.canonize();
+                let (mut b_, nrhs) = to_lapack_rhs(b);
+                let mut info = 0;
+
+                lapack::$posv(b'U', n, nrhs, a.slice_mut(), n, b_.slice_mut(), n, &mut info);
+                if info < 0 {
+                    return Err(LinalgError::IllegalArgument(-info));
+                } else if info > 0 {
+                    return Err(LinalgError::NotPositiveDefinite);
+                }
+                Ok(from_lapack_rhs(b_, b))
+            }
+
+            /// Solves `Ax = b`, where `A` is a banded matrix with `kl` sub-diagonals and `ku`
+            /// super-diagonals. The matrix is `self` and is given in banded storage with shape
+            /// `[kl + ku + 1, n]`, such that `ab[ku + i - j, j] = A[i, j]`.
+            ///
+            /// Returns `Err(LinalgError::Singular)` if the matrix is singular.
+            pub fn solve_banded(&self, kl: usize, ku: usize, b: &Tensor<$t>)
+                    -> Result<Tensor<$t>, LinalgError> {
+                assert!(self.ndim() == 2, "Banded matrix must be 2D");
+                assert!(self.dim(0) == kl + ku + 1, "Banded matrix must have `kl + ku + 1` rows");
+                assert!(self.dim(1) == b.dim(0), "Banded matrix and `b` must match");
+
+                // LAPACK needs `kl` additional rows for fill-in during the factorization.
+                let n = self.dim(1);
+                let ldab = 2 * kl + ku + 1;
+                let mut ab: Tensor<$t> = Tensor::zeros(&[n, ldab]);
+                {
+                    let src = self.canonize();
+                    let src = src.slice();
+                    let mut data = ab.slice_mut();
+                    for r in 0..(kl + ku + 1) {
+                        for j in 0..n {
+                            data[j * ldab + kl + r] = src[r * n + j];
+                        }
+                    }
+                }
+                let (mut b_, nrhs) = to_lapack_rhs(b);
+                let mut ipiv: Tensor<i32> = Tensor::empty(&[n]);
+                let mut info = 0;
+
+                lapack::$gbsv(n, kl, ku, nrhs, ab.slice_mut(), ldab, ipiv.slice_mut(),
+                              b_.slice_mut(), n, &mut info);
+                try!(LinalgError::from_info(info));
+                Ok(from_lapack_rhs(b_, b))
             }
         }
     )
 }
 
-add_solve_impl!(f64, dgesv);
-add_solve_impl!(f32, sgesv);
+add_solve_impl!(f64, dgesv, dtrtrs, dpotrs, dposv, dgbsv);
+add_solve_impl!(f32, sgesv, strtrs, spotrs, sposv, sgbsv);
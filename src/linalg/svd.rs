@@ -39,6 +39,37 @@ macro_rules! add_svd_impl {
                 }
                 (ut.transpose(), s, v.transpose())
             }
+
+            /// Computes the singular values of the matrix (in descending order), without
+            /// computing the singular vectors.
+            pub fn svdvals(&self) -> Tensor<$t> {
+                assert!(self.ndim() == 2, "`A` must be a matrix (2D)");
+                let m = self.dim(0);
+                let n = self.dim(1);
+                let mn = min(m, n);
+                let mx = max(m, n);
+                let lwork = 3*mn + max(mx, 7*mn);
+
+                let mut a = self.clone().transpose().canonize();
+                let mut work: Tensor<$t> = Tensor::empty(&[lwork]);
+                let mut s: Tensor<$t> = Tensor::empty(&[mn]);
+                let mut u: Tensor<$t> = Tensor::empty(&[1]);
+                let mut vt: Tensor<$t> = Tensor::empty(&[1]);
+                let mut iwork: Tensor<i32> = Tensor::empty(&[8*mn]);
+                let mut info = 0;
+
+                lapack::$gesdd(b'N', m, n, a.slice_mut(), m, s.slice_mut(),
+                               u.slice_mut(), 1, vt.slice_mut(), 1,
+                               work.slice_mut(), lwork as isize, iwork.slice_mut(),
+                               &mut info);
+
+                if info < 0 {
+                    panic!("Illegal input ({})", -info);
+                } else if info > 0 {
+                    panic!("Did not converge");
+                }
+                s
+            }
         }
     )
 }
@@ -0,0 +1,30 @@
+//! Elementwise mathematical functions for complex tensors. These mirror their real counterparts
+//! in `math`, using the principal branch where the function is multivalued.
+
+use num::traits::Float;
+use num::complex::Complex;
+use tensor::Tensor;
+
+macro_rules! add_impl {
+    ($($f:ident, $doc:expr)*) => ($(
+        #[doc = $doc]
+        pub fn $f<T: Float>(x: Tensor<Complex<T>>) -> Tensor<Complex<T>> {
+            let mut y = x;
+            y.canonize_inplace();
+            {
+                let n = y.size();
+                let mut data = y.slice_mut();
+                for i in 0..n {
+                    data[i] = data[i].$f();
+                }
+            }
+            y
+        }
+    )*)
+}
+
+add_impl! {
+    exp, "Computes `e^z` elementwise."
+    ln, "Computes the principal natural logarithm elementwise."
+    sqrt, "Computes the principal square root elementwise."
+}
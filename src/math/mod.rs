@@ -5,6 +5,8 @@ use num::traits::Float;
 use tensor::Tensor;
 use traits::NumericTrait;
 
+pub mod complex;
+
 macro_rules! add_impl {
     ($($f:ident)*) => ($(
         pub fn $f<T: NumericTrait + Float>(x: Tensor<T>) -> Tensor<T> {
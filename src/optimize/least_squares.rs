@@ -0,0 +1,161 @@
+use std::f64;
+use tensor::Tensor;
+use super::{OptimizeResult, dot, max_abs};
+
+/// Evaluates the residuals at `x`, checking that their number does not change.
+fn residuals<F: Fn(&Tensor<f64>) -> Tensor<f64>>(f: &F, x: &[f64], nfev: &mut usize)
+        -> Vec<f64> {
+    *nfev += 1;
+    let r = f(&Tensor::new(x.to_vec()));
+    assert!(r.ndim() == 1, "Residuals must be a vector (1D)");
+    r.iter().collect()
+}
+
+/// Approximates the Jacobian of the residuals with forward differences. Returns it as an
+/// `[m, n]` matrix, together with `J^T r`.
+fn jacobian<F: Fn(&Tensor<f64>) -> Tensor<f64>>(f: &F, x: &[f64], r: &[f64], nfev: &mut usize)
+        -> (Tensor<f64>, Vec<f64>) {
+    let (m, n) = (r.len(), x.len());
+    let mut jac = Tensor::zeros(&[m, n]);
+    {
+        let data = jac.slice_mut();
+        let mut xh = x.to_vec();
+        for j in 0..n {
+            let h = f64::EPSILON.sqrt() * x[j].abs().max(1.0);
+            xh[j] = x[j] + h;
+            let h = xh[j] - x[j];
+            let rh = residuals(f, &xh, nfev);
+            assert!(rh.len() == m, "The number of residuals must not change");
+            for i in 0..m {
+                data[i * n + j] = (rh[i] - r[i]) / h;
+            }
+            xh[j] = x[j];
+        }
+    }
+    let g = jac.transpose().dot(&Tensor::new(r.to_vec())).iter().collect();
+    (jac, g)
+}
+
+/// Minimizes half the sum of squares of the residuals `f(x)` starting from `x0`, with the
+/// default tolerances `ftol = xtol = gtol = 1e-8` and at most `100 n` iterations.
+pub fn least_squares<F>(f: F, x0: &Tensor<f64>) -> OptimizeResult
+        where F: Fn(&Tensor<f64>) -> Tensor<f64> {
+    let maxiter = 100 * x0.size();
+    least_squares_tol(f, x0, 1e-8, 1e-8, 1e-8, maxiter)
+}
+
+/// Minimizes half the sum of squares of the residuals `f(x)` starting from `x0`, with the
+/// Levenberg-Marquardt method. The Jacobian is approximated with forward differences. Stops
+/// when the relative reduction of the cost is below `ftol`, the relative step size is below
+/// `xtol` or the largest gradient component is below `gtol`.
+pub fn least_squares_tol<F>(f: F, x0: &Tensor<f64>, ftol: f64, xtol: f64, gtol: f64,
+                            maxiter: usize) -> OptimizeResult
+        where F: Fn(&Tensor<f64>) -> Tensor<f64> {
+    levenberg_marquardt(&f, x0, ftol, xtol, gtol, maxiter).0
+}
+
+/// Returns the result and the Jacobian at the solution.
+fn levenberg_marquardt<F>(f: &F, x0: &Tensor<f64>, ftol: f64, xtol: f64, gtol: f64,
+                          maxiter: usize) -> (OptimizeResult, Tensor<f64>)
+        where F: Fn(&Tensor<f64>) -> Tensor<f64> {
+    assert!(x0.ndim() == 1, "Initial guess must be a vector (1D)");
+    let n = x0.size();
+    let mut nfev = 0;
+    let mut x: Vec<f64> = x0.iter().collect();
+    let mut r = residuals(f, &x, &mut nfev);
+    let mut cost = 0.5 * dot(&r, &r);
+    let (mut jac, mut g) = jacobian(f, &x, &r, &mut nfev);
+    let mut jtj = jac.transpose().dot(&jac);
+
+    // The damping parameter is updated as suggested by Nielsen
+    let mut mu = 1e-3 * (0..n).map(|i| jtj[(i, i)]).fold(0.0, f64::max).max(f64::MIN_POSITIVE);
+    let mut nu = 2.0;
+    let mut iterations = 0;
+    let mut converged = false;
+    while iterations < maxiter {
+        if max_abs(&g) <= gtol {
+            converged = true;
+            break;
+        }
+        iterations += 1;
+        let mut a = jtj.clone();
+        for i in 0..n {
+            a[(i, i)] += mu;
+        }
+        let neg_g = Tensor::new(g.iter().map(|v| -v).collect());
+        let step: Vec<f64> = match a.solve_posdef(&neg_g) {
+            Ok(s) => s.iter().collect(),
+            Err(_) => {
+                mu *= nu;
+                nu *= 2.0;
+                continue;
+            },
+        };
+        let xnorm = dot(&x, &x).sqrt();
+        if dot(&step, &step).sqrt() <= xtol * (xnorm + xtol) {
+            converged = true;
+            break;
+        }
+
+        let x_new: Vec<f64> = x.iter().zip(step.iter()).map(|(a, b)| a + b).collect();
+        let r_new = residuals(f, &x_new, &mut nfev);
+        let cost_new = 0.5 * dot(&r_new, &r_new);
+        // Ratio of the actual to the predicted reduction
+        let predicted = 0.5 * (0..n).map(|i| step[i] * (mu * step[i] - g[i])).sum::<f64>();
+        let rho = (cost - cost_new) / predicted;
+        if cost_new.is_finite() && rho > 0.0 {
+            let reduction = cost - cost_new;
+            x = x_new;
+            r = r_new;
+            cost = cost_new;
+            let jg = jacobian(f, &x, &r, &mut nfev);
+            jac = jg.0;
+            g = jg.1;
+            jtj = jac.transpose().dot(&jac);
+            mu *= (1.0 / 3.0f64).max(1.0 - (2.0 * rho - 1.0).powi(3));
+            nu = 2.0;
+            if reduction <= ftol * (cost + reduction) {
+                converged = true;
+                break;
+            }
+        } else {
+            mu *= nu;
+            nu *= 2.0;
+        }
+    }
+    let result = OptimizeResult {
+        x: Tensor::new(x),
+        fun: cost,
+        grad: Tensor::new(g),
+        iterations: iterations,
+        nfev: nfev,
+        njev: 0,
+        converged: converged,
+    };
+    (result, jac)
+}
+
+/// Fits the parameters `p` of the model `f(xdata, p)` to `ydata` by nonlinear least squares,
+/// starting from `p0`. Returns the result, where `x` holds the fitted parameters, and the
+/// estimated covariance of the parameters (infinite if it cannot be determined).
+pub fn curve_fit<F>(f: F, xdata: &Tensor<f64>, ydata: &Tensor<f64>, p0: &Tensor<f64>)
+        -> (OptimizeResult, Tensor<f64>)
+        where F: Fn(&Tensor<f64>, &Tensor<f64>) -> Tensor<f64> {
+    assert!(ydata.ndim() == 1, "`ydata` must be a vector (1D)");
+    let residual = |p: &Tensor<f64>| {
+        let y = f(xdata, p);
+        assert!(y.shape() == ydata.shape(), "The model must have the same shape as `ydata`");
+        &y - ydata
+    };
+    let n = p0.size();
+    let (result, jac) = levenberg_marquardt(&residual, p0, 1e-8, 1e-8, 1e-8, 100 * n);
+    let m = ydata.size();
+    let mut pcov = Tensor::filled(&[n, n], f64::INFINITY);
+    if m > n {
+        if let Ok(inv) = jac.transpose().dot(&jac).inv() {
+            let s2 = 2.0 * result.fun / (m - n) as f64;
+            pcov = inv * s2;
+        }
+    }
+    (result, pcov)
+}
@@ -0,0 +1,500 @@
+use std::cell::Cell;
+use std::f64;
+use tensor::Tensor;
+use super::{OptimizeResult, dot, max_abs};
+
+/// The algorithm used by `minimize`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MinimizeMethod {
+    /// The downhill simplex method, which only uses function values.
+    NelderMead,
+    /// The quasi-Newton method of Broyden, Fletcher, Goldfarb and Shanno, with a line search
+    /// satisfying the strong Wolfe conditions.
+    Bfgs,
+    /// A projected limited-memory BFGS method with bounds on the parameters. Variables at a
+    /// bound are held fixed while the gradient points outwards, and steps are projected onto
+    /// the bounds. Unlike the L-BFGS-B algorithm of Byrd et al., there is no generalized Cauchy
+    /// point or subspace minimization, so convergence can be slower when many bounds are active.
+    ProjectedLBfgs,
+}
+
+/// Options for `minimize_options`.
+pub struct MinimizeOptions {
+    /// The gradient of the objective. Approximated with central differences if `None`.
+    pub jac: Option<Box<dyn Fn(&Tensor<f64>) -> Tensor<f64>>>,
+    /// Lower and upper bound of each parameter, which may be infinite. Supported by
+    /// `NelderMead` and `ProjectedLBfgs`.
+    pub bounds: Option<Vec<(f64, f64)>>,
+    /// `Bfgs` and `ProjectedLBfgs` stop when the largest (projected) gradient component is below
+    /// `gtol`.
+    pub gtol: f64,
+    /// `ProjectedLBfgs` stops when the relative reduction of the objective in an iteration is below
+    /// `ftol`.
+    pub ftol: f64,
+    /// `NelderMead` stops when the simplex is smaller than `xatol` and the function values at
+    /// its vertices differ by less than `fatol`.
+    pub xatol: f64,
+    /// See `xatol`.
+    pub fatol: f64,
+    /// The maximum number of iterations. Defaults to `200 n` for `NelderMead` and `Bfgs`, and
+    /// 15000 for `ProjectedLBfgs`.
+    pub maxiter: Option<usize>,
+    /// The number of correction pairs stored by `ProjectedLBfgs`.
+    pub memory: usize,
+}
+
+impl Default for MinimizeOptions {
+    fn default() -> MinimizeOptions {
+        MinimizeOptions {
+            jac: None,
+            bounds: None,
+            gtol: 1e-5,
+            ftol: 2.2e-9,
+            xatol: 1e-4,
+            fatol: 1e-4,
+            maxiter: None,
+            memory: 10,
+        }
+    }
+}
+
+/// Wraps the objective and its gradient, and counts evaluations.
+struct Objective<'a> {
+    f: &'a dyn Fn(&Tensor<f64>) -> f64,
+    jac: Option<&'a dyn Fn(&Tensor<f64>) -> Tensor<f64>>,
+    bounds: &'a [(f64, f64)],
+    nfev: Cell<usize>,
+    njev: Cell<usize>,
+}
+
+impl<'a> Objective<'a> {
+    fn eval(&self, x: &[f64]) -> f64 {
+        self.nfev.set(self.nfev.get() + 1);
+        (self.f)(&Tensor::new(x.to_vec()))
+    }
+
+    /// Evaluates the gradient, using central differences (one-sided at the bounds) if no
+    /// gradient was supplied.
+    fn grad(&self, x: &[f64], fx: f64) -> Vec<f64> {
+        if let Some(jac) = self.jac {
+            self.njev.set(self.njev.get() + 1);
+            let g = jac(&Tensor::new(x.to_vec()));
+            assert!(g.size() == x.len(), "Gradient must have the same size as `x`");
+            return g.iter().collect();
+        }
+        let mut xh = x.to_vec();
+        (0..x.len()).map(|i| {
+            let h = f64::EPSILON.cbrt() * x[i].abs().max(1.0);
+            let (lo, hi) = self.bounds[i];
+            let d = if x[i] + h > hi {
+                xh[i] = x[i] - h;
+                (fx - self.eval(&xh)) / h
+            } else if x[i] - h < lo {
+                xh[i] = x[i] + h;
+                (self.eval(&xh) - fx) / h
+            } else {
+                xh[i] = x[i] + h;
+                let fp = self.eval(&xh);
+                xh[i] = x[i] - h;
+                (fp - self.eval(&xh)) / (2.0 * h)
+            };
+            xh[i] = x[i];
+            d
+        }).collect()
+    }
+}
+
+/// Clips `x` to the bounds.
+fn clip(x: &mut [f64], bounds: &[(f64, f64)]) {
+    for (v, &(lo, hi)) in x.iter_mut().zip(bounds.iter()) {
+        *v = v.max(lo).min(hi);
+    }
+}
+
+/// Minimizes `f`, a scalar function of a parameter vector, starting from `x0` with the default
+/// options.
+pub fn minimize<F>(f: F, x0: &Tensor<f64>, method: MinimizeMethod) -> OptimizeResult
+        where F: Fn(&Tensor<f64>) -> f64 {
+    minimize_options(f, x0, method, MinimizeOptions::default())
+}
+
+/// Minimizes `f`, a scalar function of a parameter vector, starting from `x0`.
+pub fn minimize_options<F>(f: F, x0: &Tensor<f64>, method: MinimizeMethod,
+                           options: MinimizeOptions) -> OptimizeResult
+        where F: Fn(&Tensor<f64>) -> f64 {
+    assert!(x0.ndim() == 1, "Initial guess must be a vector (1D)");
+    let n = x0.size();
+    let bounds = match options.bounds {
+        Some(ref b) => {
+            assert!(method != MinimizeMethod::Bfgs, "`Bfgs` does not support bounds");
+            assert!(b.len() == n, "There must be one pair of bounds per parameter");
+            assert!(b.iter().all(|&(lo, hi)| lo <= hi), "Lower bounds must not exceed upper bounds");
+            b.clone()
+        },
+        None => vec![(f64::NEG_INFINITY, f64::INFINITY); n],
+    };
+    let obj = Objective {
+        f: &f,
+        jac: options.jac.as_ref().map(|j| &**j),
+        bounds: &bounds,
+        nfev: Cell::new(0),
+        njev: Cell::new(0),
+    };
+    let mut x: Vec<f64> = x0.iter().collect();
+    clip(&mut x, &bounds);
+    let (x, fun, grad, iterations, converged) = match method {
+        MinimizeMethod::NelderMead => nelder_mead(&obj, x, &options),
+        MinimizeMethod::Bfgs => bfgs(&obj, x, &options),
+        MinimizeMethod::ProjectedLBfgs => projected_lbfgs(&obj, x, &options),
+    };
+    OptimizeResult {
+        x: Tensor::new(x),
+        fun: fun,
+        grad: Tensor::new(grad),
+        iterations: iterations,
+        nfev: obj.nfev.get(),
+        njev: obj.njev.get(),
+        converged: converged,
+    }
+}
+
+/// Returns the minimum, its value and gradient, the number of iterations and whether it
+/// converged.
+fn nelder_mead(obj: &Objective, x0: Vec<f64>, options: &MinimizeOptions)
+        -> (Vec<f64>, f64, Vec<f64>, usize, bool) {
+    let (rho, chi, psi, sigma) = (1.0, 2.0, 0.5, 0.5);
+    let n = x0.len();
+    let maxiter = options.maxiter.unwrap_or(200 * n);
+
+    let mut sim = vec![x0.clone()];
+    for k in 0..n {
+        let mut y = x0.clone();
+        y[k] = if y[k] != 0.0 { 1.05 * y[k] } else { 0.00025 };
+        clip(&mut y, obj.bounds);
+        sim.push(y);
+    }
+    let mut fsim: Vec<f64> = sim.iter().map(|x| obj.eval(x)).collect();
+    // Moves a point along the line from the centroid through the worst vertex
+    let point = |xbar: &[f64], worst: &[f64], t: f64| {
+        let mut x: Vec<f64> = (0..n).map(|i| (1.0 + t) * xbar[i] - t * worst[i]).collect();
+        clip(&mut x, obj.bounds);
+        x
+    };
+    let sort = |sim: &mut Vec<Vec<f64>>, fsim: &mut Vec<f64>| {
+        let mut order: Vec<usize> = (0..n + 1).collect();
+        order.sort_by(|&a, &b| fsim[a].partial_cmp(&fsim[b]).unwrap_or(::std::cmp::Ordering::Equal));
+        *sim = order.iter().map(|&i| sim[i].clone()).collect();
+        *fsim = order.iter().map(|&i| fsim[i]).collect();
+    };
+    sort(&mut sim, &mut fsim);
+
+    let mut iterations = 0;
+    let mut converged = false;
+    while iterations < maxiter {
+        let xspread = sim[1..].iter()
+                              .map(|x| max_abs(&(0..n).map(|i| x[i] - sim[0][i]).collect::<Vec<f64>>()))
+                              .fold(0.0, f64::max);
+        let fspread = fsim[1..].iter().map(|f| (f - fsim[0]).abs()).fold(0.0, f64::max);
+        if xspread <= options.xatol && fspread <= options.fatol {
+            converged = true;
+            break;
+        }
+
+        let xbar: Vec<f64> = (0..n).map(|i| sim[..n].iter().map(|x| x[i]).sum::<f64>() / n as f64)
+                                   .collect();
+        let xr = point(&xbar, &sim[n], rho);
+        let fxr = obj.eval(&xr);
+        let mut shrink = false;
+        if fxr < fsim[0] {
+            let xe = point(&xbar, &sim[n], rho * chi);
+            let fxe = obj.eval(&xe);
+            if fxe < fxr {
+                sim[n] = xe;
+                fsim[n] = fxe;
+            } else {
+                sim[n] = xr;
+                fsim[n] = fxr;
+            }
+        } else if fxr < fsim[n - 1] {
+            sim[n] = xr;
+            fsim[n] = fxr;
+        } else if fxr < fsim[n] {
+            // Outside contraction
+            let xc = point(&xbar, &sim[n], psi * rho);
+            let fxc = obj.eval(&xc);
+            if fxc <= fxr {
+                sim[n] = xc;
+                fsim[n] = fxc;
+            } else {
+                shrink = true;
+            }
+        } else {
+            // Inside contraction
+            let xcc = point(&xbar, &sim[n], -psi);
+            let fxcc = obj.eval(&xcc);
+            if fxcc < fsim[n] {
+                sim[n] = xcc;
+                fsim[n] = fxcc;
+            } else {
+                shrink = true;
+            }
+        }
+        if shrink {
+            for j in 1..n + 1 {
+                sim[j] = (0..n).map(|i| sim[0][i] + sigma * (sim[j][i] - sim[0][i])).collect();
+                fsim[j] = obj.eval(&sim[j]);
+            }
+        }
+        sort(&mut sim, &mut fsim);
+        iterations += 1;
+    }
+    // The gradient is not needed by the simplex, so it is only computed if it is cheap
+    let grad = if obj.jac.is_some() { obj.grad(&sim[0], fsim[0]) } else { vec![] };
+    (sim[0].clone(), fsim[0], grad, iterations, converged)
+}
+
+/// A point on the search line: the step length, the objective, its directional derivative
+/// and its gradient.
+struct LinePoint {
+    alpha: f64,
+    f: f64,
+    d: f64,
+    g: Vec<f64>,
+}
+
+/// Finds a step length along the descent direction `p` satisfying the strong Wolfe conditions,
+/// starting with `alpha` (Nocedal and Wright, algorithms 3.5 and 3.6).
+fn line_search(obj: &Objective, x: &[f64], p: &[f64], f0: f64, g0: &[f64], alpha: f64)
+        -> Option<LinePoint> {
+    let (c1, c2) = (1e-4, 0.9);
+    let d0 = dot(g0, p);
+    let phi = |alpha: f64| {
+        let xa: Vec<f64> = x.iter().zip(p.iter()).map(|(xi, pi)| xi + alpha * pi).collect();
+        let f = obj.eval(&xa);
+        let g = if f.is_finite() { obj.grad(&xa, f) } else { vec![f64::NAN; x.len()] };
+        LinePoint { alpha: alpha, f: f, d: dot(&g, p), g: g }
+    };
+    let armijo = |pt: &LinePoint| pt.f.is_finite() && pt.f <= f0 + c1 * pt.alpha * d0;
+    let curvature = |pt: &LinePoint| pt.d.abs() <= -c2 * d0;
+
+    let zoom = |mut lo: LinePoint, mut hi: LinePoint| {
+        for _ in 0..30 {
+            // Minimizer of the cubic interpolating both ends, if it is safely inside
+            let width = hi.alpha - lo.alpha;
+            let d1 = lo.d + hi.d - 3.0 * (lo.f - hi.f) / (lo.alpha - hi.alpha);
+            let disc = d1 * d1 - lo.d * hi.d;
+            let mut a = f64::NAN;
+            if disc >= 0.0 && hi.f.is_finite() {
+                let d2 = width.signum() * disc.sqrt();
+                a = hi.alpha - (hi.alpha - lo.alpha) * (hi.d + d2 - d1) / (hi.d - lo.d + 2.0 * d2);
+            }
+            let (left, right) = (lo.alpha.min(hi.alpha), lo.alpha.max(hi.alpha));
+            let margin = 0.1 * width.abs();
+            if !(a >= left + margin && a <= right - margin) {
+                a = 0.5 * (lo.alpha + hi.alpha);
+            }
+            let pt = phi(a);
+            if !armijo(&pt) || pt.f >= lo.f {
+                hi = pt;
+            } else {
+                if curvature(&pt) {
+                    return Some(pt);
+                }
+                if pt.d * (hi.alpha - lo.alpha) >= 0.0 {
+                    hi = lo;
+                }
+                lo = pt;
+            }
+            if (hi.alpha - lo.alpha).abs() <= f64::EPSILON * lo.alpha.abs() {
+                break;
+            }
+        }
+        // Settle for sufficient decrease
+        if lo.alpha > 0.0 { Some(lo) } else { None }
+    };
+
+    let mut prev = LinePoint { alpha: 0.0, f: f0, d: d0, g: g0.to_vec() };
+    let mut alpha = alpha;
+    for i in 0..30 {
+        let pt = phi(alpha);
+        if !armijo(&pt) || (i > 0 && pt.f >= prev.f) {
+            return zoom(prev, pt);
+        }
+        if curvature(&pt) {
+            return Some(pt);
+        }
+        if pt.d >= 0.0 {
+            return zoom(pt, prev);
+        }
+        alpha *= 2.0;
+        prev = pt;
+    }
+    None
+}
+
+fn bfgs(obj: &Objective, mut x: Vec<f64>, options: &MinimizeOptions)
+        -> (Vec<f64>, f64, Vec<f64>, usize, bool) {
+    let n = x.len();
+    let maxiter = options.maxiter.unwrap_or(200 * n);
+    let mut f = obj.eval(&x);
+    let mut g = obj.grad(&x, f);
+    // The inverse Hessian approximation, row-major
+    let identity = |scale: f64| {
+        let mut h = vec![0.0; n * n];
+        for i in 0..n {
+            h[i * n + i] = scale;
+        }
+        h
+    };
+    let mut h = identity(1.0);
+    let mut iterations = 0;
+    let mut converged = false;
+    loop {
+        if max_abs(&g) <= options.gtol {
+            converged = true;
+            break;
+        }
+        if iterations >= maxiter {
+            break;
+        }
+        let mut p: Vec<f64> = (0..n).map(|i| -dot(&h[i * n..(i + 1) * n], &g)).collect();
+        if dot(&p, &g) >= 0.0 {
+            h = identity(1.0);
+            p = g.iter().map(|v| -v).collect();
+        }
+        let alpha = if iterations == 0 { (1.0 / max_abs(&g)).min(1.0) } else { 1.0 };
+        let pt = match line_search(obj, &x, &p, f, &g, alpha) {
+            Some(pt) => pt,
+            None => break,
+        };
+        let s: Vec<f64> = p.iter().map(|v| pt.alpha * v).collect();
+        let y: Vec<f64> = (0..n).map(|i| pt.g[i] - g[i]).collect();
+        let sy = dot(&s, &y);
+        if sy > 0.0 {
+            if iterations == 0 {
+                h = identity(sy / dot(&y, &y));
+            }
+            let hy: Vec<f64> = (0..n).map(|i| dot(&h[i * n..(i + 1) * n], &y)).collect();
+            let a = (sy + dot(&y, &hy)) / (sy * sy);
+            for i in 0..n {
+                for j in 0..n {
+                    h[i * n + j] += a * s[i] * s[j] - (hy[i] * s[j] + s[i] * hy[j]) / sy;
+                }
+            }
+        }
+        for i in 0..n {
+            x[i] += s[i];
+        }
+        f = pt.f;
+        g = pt.g;
+        iterations += 1;
+    }
+    (x, f, g, iterations, converged)
+}
+
+/// Applies the L-BFGS approximation of the inverse Hessian to `q` (the two-loop recursion).
+fn two_loop(mut q: Vec<f64>, s: &[Vec<f64>], y: &[Vec<f64>]) -> Vec<f64> {
+    let m = s.len();
+    let rho: Vec<f64> = (0..m).map(|i| 1.0 / dot(&s[i], &y[i])).collect();
+    let mut a = vec![0.0; m];
+    for i in (0..m).rev() {
+        a[i] = rho[i] * dot(&s[i], &q);
+        for (qj, yj) in q.iter_mut().zip(y[i].iter()) {
+            *qj -= a[i] * yj;
+        }
+    }
+    if m > 0 {
+        let gamma = dot(&s[m - 1], &y[m - 1]) / dot(&y[m - 1], &y[m - 1]);
+        for qj in q.iter_mut() {
+            *qj *= gamma;
+        }
+    }
+    for i in 0..m {
+        let b = rho[i] * dot(&y[i], &q);
+        for (qj, sj) in q.iter_mut().zip(s[i].iter()) {
+            *qj += (a[i] - b) * sj;
+        }
+    }
+    q
+}
+
+fn projected_lbfgs(obj: &Objective, mut x: Vec<f64>, options: &MinimizeOptions)
+        -> (Vec<f64>, f64, Vec<f64>, usize, bool) {
+    let n = x.len();
+    let bounds = obj.bounds;
+    let maxiter = options.maxiter.unwrap_or(15000);
+    let mut f = obj.eval(&x);
+    let mut g = obj.grad(&x, f);
+    let mut s_hist: Vec<Vec<f64>> = vec![];
+    let mut y_hist: Vec<Vec<f64>> = vec![];
+    let mut iterations = 0;
+    let mut converged = false;
+    while iterations < maxiter {
+        // Variables at a bound with the gradient pointing outwards are held fixed
+        let free: Vec<bool> = (0..n).map(|i| {
+            !((x[i] <= bounds[i].0 && g[i] > 0.0) || (x[i] >= bounds[i].1 && g[i] < 0.0))
+        }).collect();
+        let pg: Vec<f64> = (0..n).map(|i| if free[i] { g[i] } else { 0.0 }).collect();
+        if max_abs(&pg) <= options.gtol {
+            converged = true;
+            break;
+        }
+
+        let mut accepted = None;
+        for attempt in 0..2 {
+            let mut d: Vec<f64> = two_loop(pg.clone(), &s_hist, &y_hist).iter().enumerate()
+                                      .map(|(i, v)| if free[i] { -v } else { 0.0 }).collect();
+            if attempt == 1 || dot(&d, &g) >= 0.0 {
+                d = pg.iter().map(|v| -v).collect();
+            }
+            let mut alpha = if iterations == 0 || attempt == 1 {
+                (1.0 / max_abs(&pg)).min(1.0)
+            } else {
+                1.0
+            };
+            // Backtracking along the projected path
+            for _ in 0..40 {
+                let mut x_new: Vec<f64> = (0..n).map(|i| x[i] + alpha * d[i]).collect();
+                clip(&mut x_new, bounds);
+                let f_new = obj.eval(&x_new);
+                let step: Vec<f64> = (0..n).map(|i| x_new[i] - x[i]).collect();
+                if f_new.is_finite() && f_new <= f + 1e-4 * dot(&g, &step) {
+                    accepted = Some((x_new, f_new, step));
+                    break;
+                }
+                alpha *= 0.5;
+            }
+            if accepted.is_some() || s_hist.is_empty() {
+                break;
+            }
+            // Retry with steepest descent, discarding the curvature information
+            s_hist.clear();
+            y_hist.clear();
+        }
+        let (x_new, f_new, s) = match accepted {
+            Some(a) => a,
+            None => break,
+        };
+
+        let g_new = obj.grad(&x_new, f_new);
+        let y: Vec<f64> = (0..n).map(|i| g_new[i] - g[i]).collect();
+        if dot(&s, &y) > f64::EPSILON * dot(&y, &y) {
+            if s_hist.len() == options.memory.max(1) {
+                s_hist.remove(0);
+                y_hist.remove(0);
+            }
+            s_hist.push(s);
+            y_hist.push(y);
+        }
+        let reduction = (f - f_new) / f.abs().max(f_new.abs()).max(1.0);
+        x = x_new;
+        f = f_new;
+        g = g_new;
+        iterations += 1;
+        if reduction <= options.ftol {
+            converged = true;
+            break;
+        }
+    }
+    (x, f, g, iterations, converged)
+}
@@ -0,0 +1,58 @@
+//! Root finding, minimization and least squares fitting.
+//!
+//! Scalar roots are found with `brentq` (bracketing) or `newton`, while functions of a parameter
+//! vector are minimized with `minimize` and sums of squares with `least_squares`:
+//!
+//! ```
+//! use numeric::Tensor;
+//! use numeric::optimize::{brentq, minimize, MinimizeMethod};
+//!
+//! let r = brentq(|x: f64| x * x - 2.0, 0.0, 2.0);
+//! assert!((r.root - 2.0f64.sqrt()).abs() < 1e-10);
+//!
+//! let rosen = |x: &Tensor<f64>| {
+//!     let (a, b) = (x[(0,)], x[(1,)]);
+//!     (1.0 - a) * (1.0 - a) + 100.0 * (b - a * a) * (b - a * a)
+//! };
+//! let res = minimize(rosen, &Tensor::new(vec![-1.2, 1.0]), MinimizeMethod::Bfgs);
+//! assert!(res.converged && (res.x[(0,)] - 1.0).abs() < 1e-4);
+//! ```
+
+use tensor::Tensor;
+
+mod root;
+mod minimize;
+mod least_squares;
+
+pub use self::root::{RootResult, brentq, brentq_tol, newton, newton_tol};
+pub use self::minimize::{MinimizeMethod, MinimizeOptions, minimize, minimize_options};
+pub use self::least_squares::{least_squares, least_squares_tol, curve_fit};
+
+/// The result of `minimize` and `least_squares`.
+pub struct OptimizeResult {
+    /// The solution.
+    pub x: Tensor<f64>,
+    /// The value of the objective at `x`. For `least_squares`, this is half the sum of squared
+    /// residuals.
+    pub fun: f64,
+    /// The gradient of the objective at `x`. For `NelderMead`, this is empty unless a gradient
+    /// was supplied.
+    pub grad: Tensor<f64>,
+    /// The number of iterations.
+    pub iterations: usize,
+    /// The number of evaluations of the objective (including for finite differences).
+    pub nfev: usize,
+    /// The number of evaluations of a user supplied gradient.
+    pub njev: usize,
+    /// Whether a convergence criterion was met.
+    pub converged: bool,
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+}
+
+/// The maximum norm.
+fn max_abs(a: &[f64]) -> f64 {
+    a.iter().fold(0.0, |m, x| m.max(x.abs()))
+}
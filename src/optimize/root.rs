@@ -0,0 +1,141 @@
+use std::f64;
+
+/// The result of `brentq` and `newton`.
+#[derive(Clone, Copy, Debug)]
+pub struct RootResult {
+    /// The estimated root.
+    pub root: f64,
+    /// The number of iterations.
+    pub iterations: usize,
+    /// The number of function evaluations.
+    pub function_calls: usize,
+    /// Whether the tolerance was reached within the iteration limit.
+    pub converged: bool,
+}
+
+/// Finds a root of `f` in the interval `[a, b]` with Brent's method, using the default
+/// tolerances `xtol = 2e-12` and `rtol = 4 eps` and at most 100 iterations. The function must
+/// have opposite signs at `a` and `b`.
+pub fn brentq<F: Fn(f64) -> f64>(f: F, a: f64, b: f64) -> RootResult {
+    brentq_tol(f, a, b, 2e-12, 4.0 * f64::EPSILON, 100)
+}
+
+/// Finds a root of `f` in the interval `[a, b]` with Brent's method, which combines bisection
+/// with secant steps and inverse quadratic interpolation. The root is located to within
+/// `xtol + rtol * |root|`. The function must have opposite signs at `a` and `b`.
+pub fn brentq_tol<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, xtol: f64, rtol: f64,
+                                     maxiter: usize) -> RootResult {
+    assert!(xtol > 0.0 && rtol >= 0.0, "Tolerances must be positive");
+    let (mut xpre, mut xcur) = (a, b);
+    let (mut fpre, mut fcur) = (f(xpre), f(xcur));
+    let mut calls = 2;
+    let done = |root: f64, iterations: usize, calls: usize, converged: bool| {
+        RootResult { root: root, iterations: iterations, function_calls: calls,
+                     converged: converged }
+    };
+    if fpre == 0.0 {
+        return done(xpre, 0, calls, true);
+    }
+    if fcur == 0.0 {
+        return done(xcur, 0, calls, true);
+    }
+    assert!(fpre.signum() != fcur.signum(), "`f(a)` and `f(b)` must have different signs");
+
+    // xblk is the other end of the bracket, spre and scur the previous two step sizes
+    let (mut xblk, mut fblk) = (0.0, 0.0);
+    let (mut spre, mut scur) = (0.0, 0.0);
+    for i in 0..maxiter {
+        if fpre.signum() != fcur.signum() {
+            xblk = xpre;
+            fblk = fpre;
+            spre = xcur - xpre;
+            scur = spre;
+        }
+        if fblk.abs() < fcur.abs() {
+            xpre = xcur;
+            xcur = xblk;
+            xblk = xpre;
+            fpre = fcur;
+            fcur = fblk;
+            fblk = fpre;
+        }
+
+        let delta = 0.5 * (xtol + rtol * xcur.abs());
+        let sbis = 0.5 * (xblk - xcur);
+        if fcur == 0.0 || sbis.abs() < delta {
+            return done(xcur, i, calls, true);
+        }
+
+        if spre.abs() > delta && fcur.abs() < fpre.abs() {
+            let stry = if xpre == xblk {
+                // Secant
+                -fcur * (xcur - xpre) / (fcur - fpre)
+            } else {
+                // Inverse quadratic interpolation
+                let dpre = (fpre - fcur) / (xpre - xcur);
+                let dblk = (fblk - fcur) / (xblk - xcur);
+                -fcur * (fblk * dblk - fpre * dpre) / (dblk * dpre * (fblk - fpre))
+            };
+            if 2.0 * stry.abs() < spre.abs().min(3.0 * sbis.abs() - delta) {
+                spre = scur;
+                scur = stry;
+            } else {
+                spre = sbis;
+                scur = sbis;
+            }
+        } else {
+            spre = sbis;
+            scur = sbis;
+        }
+
+        xpre = xcur;
+        fpre = fcur;
+        if scur.abs() > delta {
+            xcur += scur;
+        } else {
+            xcur += if sbis > 0.0 { delta } else { -delta };
+        }
+        fcur = f(xcur);
+        calls += 1;
+    }
+    done(xcur, maxiter, calls, false)
+}
+
+/// Finds a root of `f` with Newton's method starting from `x0`, where `fprime` is the
+/// derivative. Uses the tolerance `1.48e-8` and at most 50 iterations.
+pub fn newton<F, D>(f: F, fprime: D, x0: f64) -> RootResult
+        where F: Fn(f64) -> f64, D: Fn(f64) -> f64 {
+    newton_tol(f, fprime, x0, 1.48e-8, 50)
+}
+
+/// Finds a root of `f` with Newton's method starting from `x0`, where `fprime` is the
+/// derivative. Stops when a step is smaller than `tol`. Does not converge if the derivative
+/// vanishes.
+pub fn newton_tol<F, D>(f: F, fprime: D, x0: f64, tol: f64, maxiter: usize) -> RootResult
+        where F: Fn(f64) -> f64, D: Fn(f64) -> f64 {
+    let mut x = x0;
+    let mut iterations = 0;
+    let mut calls = 0;
+    let mut converged = false;
+    while iterations < maxiter {
+        let fx = f(x);
+        calls += 1;
+        if fx == 0.0 {
+            converged = true;
+            break;
+        }
+        let dfx = fprime(x);
+        if dfx == 0.0 || !dfx.is_finite() {
+            break;
+        }
+        let step = fx / dfx;
+        x -= step;
+        iterations += 1;
+        if step.abs() <= tol {
+            converged = true;
+            break;
+        }
+    }
+    RootResult { root: x, iterations: iterations, function_calls: calls,
+                 converged: converged }
+}
@@ -0,0 +1,55 @@
+use tensor::Tensor;
+use linalg::LinalgError;
+use traits::NumericTrait;
+use num::traits::Float;
+use super::lstsq_fit;
+
+/// Evaluates the Chebyshev series `c[0] T_0(x) + c[1] T_1(x) + ...` at each element of `x`,
+/// using Clenshaw's recurrence.
+pub fn chebval<T: NumericTrait + Float>(c: &Tensor<T>, x: &Tensor<T>) -> Tensor<T> {
+    assert!(c.ndim() == 1, "Coefficients must be a vector (1D)");
+    let c: Vec<T> = c.iter().collect();
+    let two = T::one() + T::one();
+    let mut y = Tensor::zeros(&x.shape());
+    {
+        let data = y.slice_mut();
+        for (i, v) in x.iter().enumerate() {
+            let (mut b1, mut b2) = (T::zero(), T::zero());
+            for k in (1..c.len()).rev() {
+                let b = c[k] + two * v * b1 - b2;
+                b2 = b1;
+                b1 = b;
+            }
+            data[i] = if c.is_empty() { T::zero() } else { c[0] + v * b1 - b2 };
+        }
+    }
+    y
+}
+
+/// Returns the pseudo-Vandermonde matrix of the Chebyshev polynomials, with columns
+/// `T_0(x), ..., T_deg(x)`.
+pub fn chebvander(x: &Tensor<f64>, deg: usize) -> Tensor<f64> {
+    assert!(x.ndim() == 1, "`x` must be a vector (1D)");
+    let m = deg + 1;
+    let mut v = Tensor::zeros(&[x.size(), m]);
+    {
+        let data = v.slice_mut();
+        for (i, xi) in x.iter().enumerate() {
+            let row = &mut data[i * m..(i + 1) * m];
+            row[0] = 1.0;
+            if m > 1 {
+                row[1] = xi;
+            }
+            for k in 2..m {
+                row[k] = 2.0 * xi * row[k - 1] - row[k - 2];
+            }
+        }
+    }
+    v
+}
+
+/// Fits a Chebyshev series of degree `deg` to the points `(x, y)` in the least-squares sense.
+/// Returns the coefficients, lowest degree first.
+pub fn chebfit(x: &Tensor<f64>, y: &Tensor<f64>, deg: usize) -> Result<Tensor<f64>, LinalgError> {
+    lstsq_fit(&chebvander(x, deg), y)
+}
@@ -0,0 +1,62 @@
+use tensor::Tensor;
+use linalg::LinalgError;
+use traits::NumericTrait;
+use num::traits::{Float, cast};
+use super::lstsq_fit;
+
+/// Evaluates the Legendre series `c[0] P_0(x) + c[1] P_1(x) + ...` at each element of `x`,
+/// using Bonnet's recurrence.
+pub fn legval<T: NumericTrait + Float>(c: &Tensor<T>, x: &Tensor<T>) -> Tensor<T> {
+    assert!(c.ndim() == 1, "Coefficients must be a vector (1D)");
+    let c: Vec<T> = c.iter().collect();
+    let mut y = Tensor::zeros(&x.shape());
+    {
+        let data = y.slice_mut();
+        for (i, v) in x.iter().enumerate() {
+            let (mut p0, mut p1) = (T::one(), v);
+            let mut s = T::zero();
+            for (k, &ck) in c.iter().enumerate() {
+                if k == 0 {
+                    s = s + ck * p0;
+                    continue;
+                }
+                s = s + ck * p1;
+                let kf: T = cast(k).unwrap();
+                let p2 = ((kf + kf + T::one()) * v * p1 - kf * p0) / (kf + T::one());
+                p0 = p1;
+                p1 = p2;
+            }
+            data[i] = s;
+        }
+    }
+    y
+}
+
+/// Returns the pseudo-Vandermonde matrix of the Legendre polynomials, with columns
+/// `P_0(x), ..., P_deg(x)`.
+pub fn legvander(x: &Tensor<f64>, deg: usize) -> Tensor<f64> {
+    assert!(x.ndim() == 1, "`x` must be a vector (1D)");
+    let m = deg + 1;
+    let mut v = Tensor::zeros(&[x.size(), m]);
+    {
+        let data = v.slice_mut();
+        for (i, xi) in x.iter().enumerate() {
+            let row = &mut data[i * m..(i + 1) * m];
+            row[0] = 1.0;
+            if m > 1 {
+                row[1] = xi;
+            }
+            for k in 2..m {
+                let kf = k as f64;
+                row[k] = ((2.0 * kf - 1.0) * xi * row[k - 1] - (kf - 1.0) * row[k - 2]) / kf;
+            }
+        }
+    }
+    v
+}
+
+/// Fits a Legendre series of degree `deg` to the points `(x, y)` in the least-squares sense.
+/// Returns the coefficients, lowest degree first.
+pub fn legfit(x: &Tensor<f64>, y: &Tensor<f64>, deg: usize) -> Result<Tensor<f64>, LinalgError> {
+    lstsq_fit(&legvander(x, deg), y)
+}
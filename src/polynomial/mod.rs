@@ -0,0 +1,160 @@
+//! Polynomials.
+//!
+//! The free functions `polyval`, `polyfit`, `roots`, `polyder` and `polyint` follow Numpy and
+//! store coefficients with the highest degree first:
+//!
+//! ```no_run
+//! use numeric::Tensor;
+//! use numeric::polynomial::{polyval, polyfit};
+//!
+//! let x = Tensor::new(vec![0.0, 1.0, 2.0, 3.0]);
+//! let y = Tensor::new(vec![1.0, 3.0, 7.0, 13.0]);
+//! // y = x^2 + x + 1
+//! let p = polyfit(&x, &y, 2).unwrap();
+//! let z = polyval(&p, &x);
+//! ```
+//!
+//! The `Polynomial` type, as well as the Chebyshev and Legendre series, store coefficients in
+//! increasing order of degree.
+
+use tensor::Tensor;
+use linalg::LinalgError;
+use traits::NumericTrait;
+use num::complex::Complex64;
+use num::traits::{Float, cast};
+
+mod power;
+mod chebyshev;
+mod legendre;
+
+pub use self::power::Polynomial;
+pub use self::chebyshev::{chebval, chebfit, chebvander};
+pub use self::legendre::{legval, legfit, legvander};
+
+/// Evaluates the polynomial with coefficients `coeffs` (highest degree first) at each element of
+/// `x`, using Horner's method.
+pub fn polyval<T: NumericTrait>(coeffs: &Tensor<T>, x: &Tensor<T>) -> Tensor<T> {
+    assert!(coeffs.ndim() == 1, "Coefficients must be a vector (1D)");
+    let mut y = Tensor::zeros(&x.shape());
+    {
+        let data = y.slice_mut();
+        for (i, v) in x.iter().enumerate() {
+            data[i] = coeffs.iter().fold(T::zero(), |acc, c| acc * v + c);
+        }
+    }
+    y
+}
+
+/// Returns the Vandermonde matrix of `x`, with columns `x^deg, ..., x, 1`.
+pub fn vander(x: &Tensor<f64>, deg: usize) -> Tensor<f64> {
+    assert!(x.ndim() == 1, "`x` must be a vector (1D)");
+    let n = x.size();
+    let mut v = Tensor::zeros(&[n, deg + 1]);
+    {
+        let data = v.slice_mut();
+        for (i, xi) in x.iter().enumerate() {
+            let mut p = 1.0;
+            for j in (0..deg + 1).rev() {
+                data[i * (deg + 1) + j] = p;
+                p *= xi;
+            }
+        }
+    }
+    v
+}
+
+/// Solves the least-squares problem `min |y - Vc|`. The columns of `V` are scaled to unit norm
+/// first, which greatly improves the conditioning of Vandermonde-like matrices.
+fn lstsq_fit(v: &Tensor<f64>, y: &Tensor<f64>) -> Result<Tensor<f64>, LinalgError> {
+    assert!(y.ndim() == 1, "`y` must be a vector (1D)");
+    assert!(v.dim(0) == y.size(), "`x` and `y` must have the same length");
+    let (n, m) = (v.dim(0), v.dim(1));
+    let mut a = v.canonize();
+    let mut scale = vec![0.0; m];
+    {
+        let data = a.slice_mut();
+        for j in 0..m {
+            let norm = (0..n).map(|i| data[i * m + j] * data[i * m + j]).sum::<f64>().sqrt();
+            scale[j] = if norm == 0.0 { 1.0 } else { norm };
+            for i in 0..n {
+                data[i * m + j] /= scale[j];
+            }
+        }
+    }
+    let mut c = try!(a.lstsq(y, -1.0)).solution;
+    {
+        let data = c.slice_mut();
+        for j in 0..m {
+            data[j] /= scale[j];
+        }
+    }
+    Ok(c)
+}
+
+/// Fits a polynomial of degree `deg` to the points `(x, y)` in the least-squares sense. Returns
+/// the coefficients with the highest degree first.
+pub fn polyfit(x: &Tensor<f64>, y: &Tensor<f64>, deg: usize) -> Result<Tensor<f64>, LinalgError> {
+    lstsq_fit(&vander(x, deg), y)
+}
+
+/// Computes the roots of the polynomial with coefficients `coeffs` (highest degree first), as
+/// the eigenvalues of its companion matrix.
+pub fn roots(coeffs: &Tensor<f64>) -> Result<Tensor<Complex64>, LinalgError> {
+    assert!(coeffs.ndim() == 1, "Coefficients must be a vector (1D)");
+    let c: Vec<f64> = coeffs.iter().collect();
+    // Leading zeros do not change the polynomial, while trailing zeros are roots at zero
+    let start = match c.iter().position(|&v| v != 0.0) {
+        Some(i) => i,
+        None => return Ok(Tensor::zeros(&[0])),
+    };
+    let end = c.iter().rposition(|&v| v != 0.0).unwrap() + 1;
+    let zeros = c.len() - end;
+    let c = &c[start..end];
+    let n = c.len() - 1;
+
+    let mut r: Vec<Complex64> = vec![];
+    if n > 0 {
+        // The first row holds -c[1..] / c[0], with ones on the subdiagonal
+        let mut companion = Tensor::zeros(&[n, n]);
+        {
+            let data = companion.slice_mut();
+            for j in 0..n {
+                data[j] = -c[j + 1] / c[0];
+            }
+            for i in 1..n {
+                data[i * n + i - 1] = 1.0;
+            }
+        }
+        r.extend(try!(companion.eigvals()).iter());
+    }
+    r.extend((0..zeros).map(|_| Complex64::new(0.0, 0.0)));
+    Ok(Tensor::new(r))
+}
+
+/// Returns the `m`-th derivative of the polynomial with coefficients `coeffs` (highest degree
+/// first).
+pub fn polyder<T: NumericTrait>(coeffs: &Tensor<T>, m: usize) -> Tensor<T> {
+    assert!(coeffs.ndim() == 1, "Coefficients must be a vector (1D)");
+    let mut c: Vec<T> = coeffs.iter().collect();
+    for _ in 0..m {
+        let n = c.len();
+        if n <= 1 {
+            return Tensor::zeros(&[1]);
+        }
+        c = (0..n - 1).map(|i| c[i] * cast(n - 1 - i).unwrap()).collect();
+    }
+    Tensor::new(c)
+}
+
+/// Returns the `m`-th antiderivative of the polynomial with coefficients `coeffs` (highest
+/// degree first). The integration constant of each step is `k`.
+pub fn polyint<T: NumericTrait + Float>(coeffs: &Tensor<T>, m: usize, k: T) -> Tensor<T> {
+    assert!(coeffs.ndim() == 1, "Coefficients must be a vector (1D)");
+    let mut c: Vec<T> = coeffs.iter().collect();
+    for _ in 0..m {
+        let n = c.len();
+        c = (0..n).map(|i| c[i] / cast(n - i).unwrap()).collect();
+        c.push(k);
+    }
+    Tensor::new(c)
+}
@@ -0,0 +1,193 @@
+use std::ops::{Add, Sub, Mul, Neg};
+use tensor::Tensor;
+use linalg::LinalgError;
+use traits::NumericTrait;
+use num::complex::Complex64;
+use num::traits::{Float, cast};
+use super::{lstsq_fit, vander, roots};
+
+/// A polynomial `c[0] + c[1] x + c[2] x^2 + ...`, with coefficients in increasing order of
+/// degree. Trailing zero coefficients are removed, so the zero polynomial has no coefficients.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polynomial<T> {
+    coeffs: Vec<T>,
+}
+
+impl<T: NumericTrait> Polynomial<T> {
+    /// Creates a polynomial from its coefficients, lowest degree first.
+    pub fn new(coeffs: Vec<T>) -> Polynomial<T> {
+        let mut coeffs = coeffs;
+        while coeffs.last().map_or(false, |c| c.is_zero()) {
+            coeffs.pop();
+        }
+        Polynomial { coeffs: coeffs }
+    }
+
+    /// Creates a polynomial from a vector of coefficients, lowest degree first.
+    pub fn from_tensor(coeffs: &Tensor<T>) -> Polynomial<T> {
+        assert!(coeffs.ndim() == 1, "Coefficients must be a vector (1D)");
+        Polynomial::new(coeffs.iter().collect())
+    }
+
+    /// The coefficients, lowest degree first.
+    pub fn coeffs(&self) -> &[T] {
+        &self.coeffs
+    }
+
+    /// The coefficients as a tensor, lowest degree first.
+    pub fn to_tensor(&self) -> Tensor<T> {
+        if self.coeffs.is_empty() {
+            Tensor::zeros(&[1])
+        } else {
+            Tensor::new(self.coeffs.clone())
+        }
+    }
+
+    /// The degree of the polynomial. The zero polynomial is considered to have degree 0.
+    pub fn degree(&self) -> usize {
+        if self.coeffs.is_empty() { 0 } else { self.coeffs.len() - 1 }
+    }
+
+    /// Evaluates the polynomial at `x`.
+    pub fn eval(&self, x: T) -> T {
+        self.coeffs.iter().rev().fold(T::zero(), |acc, &c| acc * x + c)
+    }
+
+    /// Evaluates the polynomial at each element of `x`.
+    pub fn eval_tensor(&self, x: &Tensor<T>) -> Tensor<T> {
+        let mut y = Tensor::zeros(&x.shape());
+        {
+            let data = y.slice_mut();
+            for (i, v) in x.iter().enumerate() {
+                data[i] = self.eval(v);
+            }
+        }
+        y
+    }
+
+    /// Returns the `m`-th derivative.
+    pub fn deriv(&self, m: usize) -> Polynomial<T> {
+        let mut c = self.coeffs.clone();
+        for _ in 0..m {
+            if c.is_empty() {
+                break;
+            }
+            c = (1..c.len()).map(|i| c[i] * cast(i).unwrap()).collect();
+        }
+        Polynomial::new(c)
+    }
+}
+
+impl<T: NumericTrait + Float> Polynomial<T> {
+    /// Returns the `m`-th antiderivative, where the integration constant of each step is `k`.
+    pub fn integ(&self, m: usize, k: T) -> Polynomial<T> {
+        let mut c = self.coeffs.clone();
+        for _ in 0..m {
+            let mut next = vec![k];
+            next.extend(c.iter().enumerate().map(|(i, &v)| v / cast(i + 1).unwrap()));
+            c = next;
+        }
+        Polynomial::new(c)
+    }
+
+    /// Polynomial long division, returning the quotient and the remainder.
+    pub fn div_rem(&self, other: &Polynomial<T>) -> (Polynomial<T>, Polynomial<T>) {
+        assert!(!other.coeffs.is_empty(), "Division by the zero polynomial");
+        let d = other.coeffs.len();
+        let lead = other.coeffs[d - 1];
+        let mut r = self.coeffs.clone();
+        if r.len() < d {
+            return (Polynomial::new(vec![]), self.clone());
+        }
+        let mut q = vec![T::zero(); r.len() - d + 1];
+        for i in (0..q.len()).rev() {
+            let f = r[i + d - 1] / lead;
+            q[i] = f;
+            for j in 0..d {
+                r[i + j] = r[i + j] - f * other.coeffs[j];
+            }
+        }
+        r.truncate(d - 1);
+        (Polynomial::new(q), Polynomial::new(r))
+    }
+}
+
+impl Polynomial<f64> {
+    /// Fits a polynomial of degree `deg` to the points `(x, y)` in the least-squares sense.
+    pub fn fit(x: &Tensor<f64>, y: &Tensor<f64>, deg: usize)
+            -> Result<Polynomial<f64>, LinalgError> {
+        let mut c: Vec<f64> = try!(lstsq_fit(&vander(x, deg), y)).iter().collect();
+        c.reverse();
+        Ok(Polynomial::new(c))
+    }
+
+    /// Computes the roots.
+    pub fn roots(&self) -> Result<Tensor<Complex64>, LinalgError> {
+        let mut c = self.coeffs.clone();
+        c.reverse();
+        roots(&Tensor::new(c))
+    }
+}
+
+impl<'a, T: NumericTrait> Add<&'a Polynomial<T>> for &'a Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn add(self, rhs: &Polynomial<T>) -> Polynomial<T> {
+        let n = ::std::cmp::max(self.coeffs.len(), rhs.coeffs.len());
+        let get = |c: &Vec<T>, i: usize| if i < c.len() { c[i] } else { T::zero() };
+        Polynomial::new((0..n).map(|i| get(&self.coeffs, i) + get(&rhs.coeffs, i)).collect())
+    }
+}
+
+impl<'a, T: NumericTrait> Sub<&'a Polynomial<T>> for &'a Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn sub(self, rhs: &Polynomial<T>) -> Polynomial<T> {
+        let n = ::std::cmp::max(self.coeffs.len(), rhs.coeffs.len());
+        let get = |c: &Vec<T>, i: usize| if i < c.len() { c[i] } else { T::zero() };
+        Polynomial::new((0..n).map(|i| get(&self.coeffs, i) - get(&rhs.coeffs, i)).collect())
+    }
+}
+
+impl<'a, T: NumericTrait> Mul<&'a Polynomial<T>> for &'a Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn mul(self, rhs: &Polynomial<T>) -> Polynomial<T> {
+        if self.coeffs.is_empty() || rhs.coeffs.is_empty() {
+            return Polynomial::new(vec![]);
+        }
+        let mut c = vec![T::zero(); self.coeffs.len() + rhs.coeffs.len() - 1];
+        for (i, &a) in self.coeffs.iter().enumerate() {
+            for (j, &b) in rhs.coeffs.iter().enumerate() {
+                c[i + j] = c[i + j] + a * b;
+            }
+        }
+        Polynomial::new(c)
+    }
+}
+
+impl<'a, T: NumericTrait> Mul<T> for &'a Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn mul(self, rhs: T) -> Polynomial<T> {
+        Polynomial::new(self.coeffs.iter().map(|&c| c * rhs).collect())
+    }
+}
+
+impl<T: NumericTrait + Neg<Output=T>> Neg for Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn neg(self) -> Polynomial<T> {
+        Polynomial::new(self.coeffs.iter().map(|&c| -c).collect())
+    }
+}
+
+macro_rules! add_owned_impl {
+    ($trait_name:ident, $fname:ident) => (
+        impl<T: NumericTrait> $trait_name<Polynomial<T>> for Polynomial<T> {
+            type Output = Polynomial<T>;
+            fn $fname(self, rhs: Polynomial<T>) -> Polynomial<T> {
+                (&self).$fname(&rhs)
+            }
+        }
+    )
+}
+
+add_owned_impl!(Add, add);
+add_owned_impl!(Sub, sub);
+add_owned_impl!(Mul, mul);
@@ -0,0 +1,119 @@
+//! Quasi-Monte Carlo and design-of-experiments sequences.
+//!
+//! Low-discrepancy sequences fill the unit hypercube more evenly than independent uniform
+//! samples, which speeds up the convergence of integration and sensitivity analysis. Each
+//! function returns `n` points in `[0, 1)^d` as a tensor of shape `[n, d]`:
+//!
+//! * `sobol` (or `Sobol`, to continue a sequence): the Sobol sequence in up to 21 dimensions,
+//!   with the direction numbers of Joe and Kuo
+//! * `halton` and `scrambled_halton`: the Halton sequence, with random digit permutations
+//! * `latin_hypercube`: Latin hypercube sampling, with one point in each of `n` equal strata
+//!   along every axis
+//!
+//! ```
+//! use numeric::qmc::{sobol, scrambled_halton, latin_hypercube};
+//! use numeric::random::RandomState;
+//!
+//! let x = sobol(64, 3);
+//! assert!(x.shape() == &vec![64, 3]);
+//!
+//! let mut rs = RandomState::new(1234);
+//! let h = scrambled_halton(100, 5, &mut rs);
+//! let l = latin_hypercube(10, 2, &mut rs);
+//! ```
+
+use tensor::Tensor;
+use random::{RandomState, BitGenerator};
+
+mod sobol;
+
+pub use self::sobol::{Sobol, sobol, SOBOL_MAX_DIM};
+
+/// Returns the first `d` prime numbers.
+fn primes(d: usize) -> Vec<u64> {
+    let mut primes = Vec::with_capacity(d);
+    let mut k = 2;
+    while primes.len() < d {
+        if primes.iter().take_while(|&&p| p * p <= k).all(|&p| k % p != 0) {
+            primes.push(k);
+        }
+        k += 1;
+    }
+    primes
+}
+
+/// The radical inverse of `i` in base `b`, with the digits optionally permuted. Permuted
+/// digits are used for a fixed number of places, including the leading zeros of `i`.
+fn radical_inverse(mut i: u64, b: u64, perms: Option<&[Vec<u64>]>) -> f64 {
+    let inv = 1.0 / b as f64;
+    let mut scale = inv;
+    let mut x = 0.0;
+    match perms {
+        None => {
+            while i > 0 {
+                x += (i % b) as f64 * scale;
+                i /= b;
+                scale *= inv;
+            }
+        },
+        Some(perms) => {
+            for perm in perms.iter() {
+                x += perm[(i % b) as usize] as f64 * scale;
+                i /= b;
+                scale *= inv;
+            }
+        },
+    }
+    x
+}
+
+/// Generates the first `n` points of the `d`-dimensional Halton sequence, whose `j`-th
+/// coordinate is the radical inverse of the index in the `j`-th prime base. The first point
+/// is the origin.
+pub fn halton(n: usize, d: usize) -> Tensor<f64> {
+    let bases = primes(d);
+    let mut t = Tensor::zeros(&[n, d]);
+    for (i, row) in t.slice_mut().chunks_mut(d.max(1)).enumerate() {
+        for (x, &b) in row.iter_mut().zip(bases.iter()) {
+            *x = radical_inverse(i as u64, b, None);
+        }
+    }
+    t
+}
+
+/// Generates the first `n` points of the `d`-dimensional Halton sequence, scrambled with
+/// independent random permutations of the digits at each place (in each dimension). This
+/// removes the strong correlations between coordinates in large bases.
+pub fn scrambled_halton<G>(n: usize, d: usize, rs: &mut RandomState<G>) -> Tensor<f64>
+        where G: BitGenerator {
+    let bases = primes(d);
+    let perms: Vec<Vec<Vec<u64>>> = bases.iter().map(|&b| {
+        // Enough places for the precision of a double
+        let places = (52.0 / (b as f64).log2()).floor() as usize;
+        (0..places).map(|_| rs.permutation(b as usize).iter().map(|k| k as u64).collect())
+                   .collect()
+    }).collect();
+    let mut t = Tensor::zeros(&[n, d]);
+    for (i, row) in t.slice_mut().chunks_mut(d.max(1)).enumerate() {
+        for ((x, &b), p) in row.iter_mut().zip(bases.iter()).zip(perms.iter()) {
+            *x = radical_inverse(i as u64, b, Some(p));
+        }
+    }
+    t
+}
+
+/// Generates `n` points of a Latin hypercube design in `d` dimensions. Along every axis, each
+/// of the `n` intervals `[k / n, (k + 1) / n)` contains exactly one point, placed uniformly at
+/// random within it.
+pub fn latin_hypercube<G>(n: usize, d: usize, rs: &mut RandomState<G>) -> Tensor<f64>
+        where G: BitGenerator {
+    let mut t = Tensor::zeros(&[n, d]);
+    for j in 0..d {
+        let perm = rs.permutation(n);
+        let u: Tensor<f64> = rs.uniform(0.0, 1.0, &[n]);
+        for i in 0..n {
+            t[(i, j)] = (perm[(i,)] as f64 + u[(i,)]) / n as f64;
+        }
+    }
+    t
+}
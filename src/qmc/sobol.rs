@@ -0,0 +1,100 @@
+use tensor::Tensor;
+
+/// The number of bits in each coordinate, which also limits the sequence to 2^32 points.
+const BITS: usize = 32;
+
+/// Primitive polynomials and initial direction numbers for dimensions 2 and up, from the
+/// `new-joe-kuo-6.21201` file of Joe and Kuo. Each entry is the degree `s`, the coefficients
+/// `a` of the polynomial (excluding the leading and trailing ones) and `m_1, ..., m_s`.
+const JOE_KUO: [(usize, u32, &'static [u32]); 20] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+    (5, 14, &[1, 3, 5, 5, 31]),
+    (6, 1, &[1, 3, 3, 9, 7, 49]),
+    (6, 13, &[1, 1, 1, 15, 21, 21]),
+    (6, 16, &[1, 3, 1, 13, 27, 49]),
+    (6, 19, &[1, 1, 1, 15, 7, 5]),
+    (6, 22, &[1, 3, 1, 15, 13, 25]),
+    (6, 25, &[1, 1, 5, 5, 19, 61]),
+    (7, 1, &[1, 3, 7, 11, 23, 15, 103]),
+    (7, 4, &[1, 3, 7, 13, 13, 15, 69]),
+];
+
+/// The largest supported dimension of a Sobol sequence.
+pub const SOBOL_MAX_DIM: usize = JOE_KUO.len() + 1;
+
+/// A generator of the Sobol low-discrepancy sequence, using the direction numbers of Joe and
+/// Kuo. Points are generated in Gray code order, starting with the origin. The balance
+/// properties of the sequence hold for the first `2^m` points.
+pub struct Sobol {
+    directions: Vec<[u32; BITS]>,
+    point: Vec<u32>,
+    index: u64,
+}
+
+impl Sobol {
+    /// Creates a generator of `d`-dimensional points, with `1 <= d <= SOBOL_MAX_DIM`.
+    pub fn new(d: usize) -> Sobol {
+        assert!(d >= 1 && d <= SOBOL_MAX_DIM,
+                "Sobol sequences are supported for 1 to {} dimensions", SOBOL_MAX_DIM);
+        let mut directions = Vec::with_capacity(d);
+        // The first dimension is the van der Corput sequence in base 2
+        let mut v = [0; BITS];
+        for k in 0..BITS {
+            v[k] = 1 << (BITS - 1 - k);
+        }
+        directions.push(v);
+        for &(s, a, m) in JOE_KUO[..d - 1].iter() {
+            let mut v = [0; BITS];
+            for k in 0..s {
+                v[k] = m[k] << (BITS - 1 - k);
+            }
+            for k in s..BITS {
+                v[k] = v[k - s] ^ (v[k - s] >> s);
+                for i in 1..s {
+                    if (a >> (s - 1 - i)) & 1 == 1 {
+                        v[k] ^= v[k - i];
+                    }
+                }
+            }
+            directions.push(v);
+        }
+        Sobol{directions: directions, point: vec![0; d], index: 0}
+    }
+
+    /// Generates the next `n` points as a tensor of shape `[n, d]` in `[0, 1)^d`.
+    pub fn sample(&mut self, n: usize) -> Tensor<f64> {
+        let d = self.point.len();
+        assert!(self.index + n as u64 <= 1 << BITS, "At most 2^32 points can be generated");
+        let mut t = Tensor::zeros(&[n, d]);
+        for row in t.slice_mut().chunks_mut(d) {
+            for (x, &p) in row.iter_mut().zip(self.point.iter()) {
+                *x = p as f64 / (1u64 << BITS) as f64;
+            }
+            // Flip the direction number of the lowest zero bit of the index
+            let c = (!self.index).trailing_zeros() as usize;
+            if c < BITS {
+                for (p, v) in self.point.iter_mut().zip(self.directions.iter()) {
+                    *p ^= v[c];
+                }
+            }
+            self.index += 1;
+        }
+        t
+    }
+}
+
+/// Generates the first `n` points of the `d`-dimensional Sobol sequence as a tensor of shape
+/// `[n, d]` (see `Sobol`).
+pub fn sobol(n: usize, d: usize) -> Tensor<f64> {
+    Sobol::new(d).sample(n)
+}
@@ -0,0 +1,311 @@
+/// A source of uniformly distributed 64-bit integers that drives a `RandomState`.
+///
+/// The output of each implementation for a given seed is fixed, and will not change between
+/// versions of Numeric.
+pub trait BitGenerator: Clone {
+    /// Creates a generator from a 64-bit seed.
+    fn from_seed(seed: u64) -> Self;
+
+    /// Returns the full state of the generator as 64-bit words. The first word identifies the
+    /// type of generator.
+    fn state(&self) -> Vec<u64>;
+
+    /// Restores a generator from the words returned by `state`, or returns `None` if they do
+    /// not describe a valid state of this type of generator.
+    fn from_state(state: &[u64]) -> Option<Self>;
+
+    /// Returns the next 64 random bits.
+    fn next_u64(&mut self) -> u64;
+
+    /// Advances the generator as if a very large number of values had been drawn, so that the
+    /// streams before and after the jump do not overlap in practice.
+    fn jump(&mut self);
+}
+
+/// Implements `Serialize` and `Deserialize` through `state` and `from_state`, so that
+/// deserialization is validated the same way as `RandomState::set_state`.
+#[cfg(feature = "serde-serialize")]
+macro_rules! serde_via_state {
+    ($t:ident) => (
+        impl ::serde::Serialize for $t {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                    where S: ::serde::Serializer {
+                ::serde::Serialize::serialize(&self.state(), serializer)
+            }
+        }
+
+        impl<'de> ::serde::Deserialize<'de> for $t {
+            fn deserialize<D>(deserializer: D) -> Result<$t, D::Error>
+                    where D: ::serde::Deserializer<'de> {
+                let state: Vec<u64> = try!(::serde::Deserialize::deserialize(deserializer));
+                $t::from_state(&state).ok_or_else(|| {
+                    ::serde::de::Error::custom(concat!("invalid state for ", stringify!($t)))
+                })
+            }
+        }
+    )
+}
+
+/// The SplitMix64 generator, used to expand a 64-bit seed into a full state.
+fn splitmix64(x: &mut u64) -> u64 {
+    *x = x.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *x;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+fn splitmix128(x: &mut u64) -> u128 {
+    let hi = splitmix64(x) as u128;
+    (hi << 64) | splitmix64(x) as u128
+}
+
+const PCG_MULTIPLIER: u128 = 0x2360ed051fc65da44385df649fccf645;
+
+/// The PCG XSL RR 128/64 generator of O'Neill, as used by NumPy's `PCG64`. It has a period of
+/// 2^128 and 2^127 selectable streams. This is the default generator of `RandomState`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pcg64 {
+    state: u128,
+    inc: u128,
+}
+
+impl Pcg64 {
+    /// Creates a generator with the given initial state and stream, seeded the same way as
+    /// `pcg64_srandom_r` in the reference implementation.
+    pub fn new(state: u128, stream: u128) -> Pcg64 {
+        let mut g = Pcg64{state: 0, inc: (stream << 1) | 1};
+        g.step();
+        g.state = g.state.wrapping_add(state);
+        g.step();
+        g
+    }
+
+    /// Advances the generator by `delta` steps in `O(log delta)` time.
+    pub fn advance(&mut self, delta: u128) {
+        let (mut acc_mult, mut acc_plus) = (1u128, 0u128);
+        let (mut cur_mult, mut cur_plus) = (PCG_MULTIPLIER, self.inc);
+        let mut delta = delta;
+        while delta > 0 {
+            if delta & 1 == 1 {
+                acc_mult = acc_mult.wrapping_mul(cur_mult);
+                acc_plus = acc_plus.wrapping_mul(cur_mult).wrapping_add(cur_plus);
+            }
+            cur_plus = cur_mult.wrapping_add(1).wrapping_mul(cur_plus);
+            cur_mult = cur_mult.wrapping_mul(cur_mult);
+            delta >>= 1;
+        }
+        self.state = acc_mult.wrapping_mul(self.state).wrapping_add(acc_plus);
+    }
+
+    fn step(&mut self) {
+        self.state = self.state.wrapping_mul(PCG_MULTIPLIER).wrapping_add(self.inc);
+    }
+}
+
+impl BitGenerator for Pcg64 {
+    fn from_seed(seed: u64) -> Pcg64 {
+        let mut x = seed;
+        let state = splitmix128(&mut x);
+        Pcg64::new(state, splitmix128(&mut x))
+    }
+
+    fn state(&self) -> Vec<u64> {
+        vec![1, (self.state >> 64) as u64, self.state as u64, (self.inc >> 64) as u64,
+             self.inc as u64]
+    }
+
+    fn from_state(state: &[u64]) -> Option<Pcg64> {
+        if state.len() != 5 || state[0] != 1 || state[4] & 1 == 0 {
+            return None;
+        }
+        Some(Pcg64{state: (state[1] as u128) << 64 | state[2] as u128,
+                   inc: (state[3] as u128) << 64 | state[4] as u128})
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.step();
+        let rot = (self.state >> 122) as u32;
+        (((self.state >> 64) as u64) ^ (self.state as u64)).rotate_right(rot)
+    }
+
+    /// Advances the state by about 0.618 * 2^128 steps (the golden ratio makes the starting
+    /// points of repeated jumps spread out evenly over the period).
+    fn jump(&mut self) {
+        self.advance(0x9e3779b97f4a7c15f39cc0605cedc835);
+    }
+}
+
+/// The xoshiro256++ generator of Blackman and Vigna, with a period of 2^256 - 1.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Xoshiro256PlusPlus {
+    s: [u64; 4],
+}
+
+impl Xoshiro256PlusPlus {
+    /// Creates a generator from its raw state, which must not be all zeros.
+    pub fn new(s: [u64; 4]) -> Xoshiro256PlusPlus {
+        assert!(s.iter().any(|&x| x != 0), "The state must not be all zeros");
+        Xoshiro256PlusPlus{s: s}
+    }
+}
+
+impl BitGenerator for Xoshiro256PlusPlus {
+    fn from_seed(seed: u64) -> Xoshiro256PlusPlus {
+        let mut x = seed;
+        let mut s = [0; 4];
+        for v in s.iter_mut() {
+            *v = splitmix64(&mut x);
+        }
+        Xoshiro256PlusPlus::new(s)
+    }
+
+    fn state(&self) -> Vec<u64> {
+        let mut state = vec![2];
+        state.extend_from_slice(&self.s);
+        state
+    }
+
+    fn from_state(state: &[u64]) -> Option<Xoshiro256PlusPlus> {
+        if state.len() != 5 || state[0] != 2 || state[1..].iter().all(|&x| x == 0) {
+            return None;
+        }
+        Some(Xoshiro256PlusPlus{s: [state[1], state[2], state[3], state[4]]})
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let s = &mut self.s;
+        let result = s[0].wrapping_add(s[3]).rotate_left(23).wrapping_add(s[0]);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// Advances the state by 2^128 steps.
+    fn jump(&mut self) {
+        const JUMP: [u64; 4] = [0x180ec6d33cfd0aba, 0xd5a61266f0c9392c,
+                                0xa9582618e03fc9aa, 0x39abdc4529b1661c];
+        let mut s = [0; 4];
+        for &j in JUMP.iter() {
+            for b in 0..64 {
+                if j & (1 << b) != 0 {
+                    for (x, y) in s.iter_mut().zip(self.s.iter()) {
+                        *x ^= *y;
+                    }
+                }
+                self.next_u64();
+            }
+        }
+        self.s = s;
+    }
+}
+
+const PHILOX_M0: u64 = 0xd2e7470ee14c6c93;
+const PHILOX_M1: u64 = 0xca5a826395121157;
+const PHILOX_W0: u64 = 0x9e3779b97f4a7c15;
+const PHILOX_W1: u64 = 0xbb67ae8584caa73b;
+
+/// The counter-based Philox4x64-10 generator of Salmon et al. Each 256-bit counter is
+/// encrypted with a 128-bit key into four outputs, so any position of the stream can be
+/// reached directly.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Philox4x64 {
+    key: [u64; 2],
+    counter: [u64; 4],
+    buffer: [u64; 4],
+    index: usize,
+}
+
+impl Philox4x64 {
+    /// Creates a generator with the given key, starting at the given counter.
+    pub fn new(key: [u64; 2], counter: [u64; 4]) -> Philox4x64 {
+        let mut g = Philox4x64{key: key, counter: counter, buffer: [0; 4], index: 0};
+        g.buffer = g.block();
+        g
+    }
+
+    /// Encrypts the current counter.
+    fn block(&self) -> [u64; 4] {
+        let mut x = self.counter;
+        let mut key = self.key;
+        for round in 0..10 {
+            if round > 0 {
+                key[0] = key[0].wrapping_add(PHILOX_W0);
+                key[1] = key[1].wrapping_add(PHILOX_W1);
+            }
+            let p0 = PHILOX_M0 as u128 * x[0] as u128;
+            let p1 = PHILOX_M1 as u128 * x[2] as u128;
+            x = [(p1 >> 64) as u64 ^ x[1] ^ key[0], p1 as u64,
+                 (p0 >> 64) as u64 ^ x[3] ^ key[1], p0 as u64];
+        }
+        x
+    }
+
+    /// Adds `delta` to the counter, starting at word `word`.
+    fn increment(&mut self, word: usize, delta: u64) {
+        let (v, mut carry) = self.counter[word].overflowing_add(delta);
+        self.counter[word] = v;
+        for c in self.counter[word + 1..].iter_mut() {
+            if !carry {
+                break;
+            }
+            let (v, o) = c.overflowing_add(1);
+            *c = v;
+            carry = o;
+        }
+    }
+}
+
+impl BitGenerator for Philox4x64 {
+    fn from_seed(seed: u64) -> Philox4x64 {
+        let mut x = seed;
+        let key = [splitmix64(&mut x), splitmix64(&mut x)];
+        Philox4x64::new(key, [0; 4])
+    }
+
+    /// The buffered outputs are not stored, since they are recomputed from the counter.
+    fn state(&self) -> Vec<u64> {
+        let mut state = vec![3];
+        state.extend_from_slice(&self.key);
+        state.extend_from_slice(&self.counter);
+        state.push(self.index as u64);
+        state
+    }
+
+    fn from_state(state: &[u64]) -> Option<Philox4x64> {
+        if state.len() != 8 || state[0] != 3 || state[7] > 4 {
+            return None;
+        }
+        let mut g = Philox4x64::new([state[1], state[2]], [state[3], state[4], state[5], state[6]]);
+        g.index = state[7] as usize;
+        Some(g)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        if self.index == 4 {
+            self.increment(0, 1);
+            self.buffer = self.block();
+            self.index = 0;
+        }
+        self.index += 1;
+        self.buffer[self.index - 1]
+    }
+
+    /// Advances the counter by 2^128 (that is, by 2^130 outputs).
+    fn jump(&mut self) {
+        self.increment(2, 1);
+        self.buffer = self.block();
+    }
+}
+
+#[cfg(feature = "serde-serialize")]
+serde_via_state!(Pcg64);
+#[cfg(feature = "serde-serialize")]
+serde_via_state!(Xoshiro256PlusPlus);
+#[cfg(feature = "serde-serialize")]
+serde_via_state!(Philox4x64);
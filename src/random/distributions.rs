@@ -0,0 +1,241 @@
+use num::traits::Float;
+use std::f64;
+
+use tensor::Tensor;
+use traits::NumericTrait;
+use super::{RandomState, BitGenerator, SampleInt};
+
+impl<G: BitGenerator> RandomState<G> {
+    /// Generates a tensor of samples from the exponential distribution with unit rate.
+    pub fn standard_exponential<T>(&mut self, shape: &[usize]) -> Tensor<T>
+            where T: NumericTrait + Float {
+        let mut t = Tensor::zeros(shape);
+        self.fill_standard_exponential(&mut t);
+        t
+    }
+
+    /// Fills `out` with samples from the exponential distribution with unit rate.
+    pub fn fill_standard_exponential<T>(&mut self, out: &mut Tensor<T>)
+            where T: NumericTrait + Float {
+        self.fill(out, |rs| rs.next_exponential())
+    }
+
+    /// Generates a tensor of samples from the gamma distribution with shape `k` and scale
+    /// `theta`.
+    pub fn gamma<T>(&mut self, k: f64, theta: f64, shape: &[usize]) -> Tensor<T>
+            where T: NumericTrait + Float {
+        let mut t = Tensor::zeros(shape);
+        self.fill_gamma(&mut t, k, theta);
+        t
+    }
+
+    /// Fills `out` with samples from the gamma distribution with shape `k` and scale
+    /// `theta`.
+    pub fn fill_gamma<T>(&mut self, out: &mut Tensor<T>, k: f64, theta: f64)
+            where T: NumericTrait + Float {
+        assert!(k > 0.0 && theta > 0.0, "Shape and scale must be positive");
+        self.fill(out, |rs| theta * rs.next_gamma(k))
+    }
+
+    /// Generates a tensor of samples from the beta distribution with parameters `a` and `b`.
+    pub fn beta<T>(&mut self, a: f64, b: f64, shape: &[usize]) -> Tensor<T>
+            where T: NumericTrait + Float {
+        let mut t = Tensor::zeros(shape);
+        self.fill_beta(&mut t, a, b);
+        t
+    }
+
+    /// Fills `out` with samples from the beta distribution with parameters `a` and `b`.
+    pub fn fill_beta<T>(&mut self, out: &mut Tensor<T>, a: f64, b: f64)
+            where T: NumericTrait + Float {
+        assert!(a > 0.0 && b > 0.0, "Parameters must be positive");
+        self.fill(out, |rs| {
+            let x = rs.next_gamma(a);
+            let y = rs.next_gamma(b);
+            x / (x + y)
+        })
+    }
+
+    /// Generates a tensor of samples from the chi-square distribution with `df` degrees of
+    /// freedom.
+    pub fn chi_square<T>(&mut self, df: f64, shape: &[usize]) -> Tensor<T>
+            where T: NumericTrait + Float {
+        let mut t = Tensor::zeros(shape);
+        self.fill_chi_square(&mut t, df);
+        t
+    }
+
+    /// Fills `out` with samples from the chi-square distribution with `df` degrees of
+    /// freedom.
+    pub fn fill_chi_square<T>(&mut self, out: &mut Tensor<T>, df: f64)
+            where T: NumericTrait + Float {
+        assert!(df > 0.0, "Degrees of freedom must be positive");
+        self.fill(out, |rs| 2.0 * rs.next_gamma(0.5 * df))
+    }
+
+    /// Generates a tensor of samples from Student's t distribution with `df` degrees of freedom.
+    pub fn student_t<T>(&mut self, df: f64, shape: &[usize]) -> Tensor<T>
+            where T: NumericTrait + Float {
+        let mut t = Tensor::zeros(shape);
+        self.fill_student_t(&mut t, df);
+        t
+    }
+
+    /// Fills `out` with samples from Student's t distribution with `df` degrees of freedom.
+    pub fn fill_student_t<T>(&mut self, out: &mut Tensor<T>, df: f64)
+            where T: NumericTrait + Float {
+        assert!(df > 0.0, "Degrees of freedom must be positive");
+        self.fill(out, |rs| {
+            let z = rs.next_normal();
+            z / (2.0 * rs.next_gamma(0.5 * df) / df).sqrt()
+        })
+    }
+
+    /// Generates a tensor of samples from the log-normal distribution, whose logarithm is
+    /// normally distributed with the given mean and standard deviation.
+    pub fn lognormal<T>(&mut self, mean: f64, sigma: f64, shape: &[usize]) -> Tensor<T>
+            where T: NumericTrait + Float {
+        let mut t = Tensor::zeros(shape);
+        self.fill_lognormal(&mut t, mean, sigma);
+        t
+    }
+
+    /// Fills `out` with samples from the log-normal distribution, whose logarithm is
+    /// normally distributed with the given mean and standard deviation.
+    pub fn fill_lognormal<T>(&mut self, out: &mut Tensor<T>, mean: f64, sigma: f64)
+            where T: NumericTrait + Float {
+        assert!(sigma >= 0.0, "Standard deviation must be non-negative");
+        self.fill(out, |rs| (mean + sigma * rs.next_normal()).exp())
+    }
+
+    /// Generates a tensor of samples from the Poisson distribution with mean `lam`.
+    pub fn poisson<T>(&mut self, lam: f64, shape: &[usize]) -> Tensor<T>
+            where T: NumericTrait {
+        let mut t = Tensor::zeros(shape);
+        self.fill_poisson(&mut t, lam);
+        t
+    }
+
+    /// Fills `out` with samples from the Poisson distribution with mean `lam`.
+    pub fn fill_poisson<T>(&mut self, out: &mut Tensor<T>, lam: f64)
+            where T: NumericTrait {
+        assert!(lam >= 0.0 && lam.is_finite(), "Mean must be non-negative");
+        self.fill(out, |rs| rs.next_poisson(lam))
+    }
+
+    /// Generates a tensor of samples from the binomial distribution, counting the successes in
+    /// `n` trials with success probability `p`.
+    pub fn binomial<T>(&mut self, n: usize, p: f64, shape: &[usize]) -> Tensor<T>
+            where T: NumericTrait {
+        let mut t = Tensor::zeros(shape);
+        self.fill_binomial(&mut t, n, p);
+        t
+    }
+
+    /// Fills `out` with samples from the binomial distribution, counting the successes in
+    /// `n` trials with success probability `p`.
+    pub fn fill_binomial<T>(&mut self, out: &mut Tensor<T>, n: usize, p: f64)
+            where T: NumericTrait {
+        assert!(p >= 0.0 && p <= 1.0, "Probability must be in [0, 1]");
+        self.fill(out, |rs| rs.next_binomial(n as f64, p))
+    }
+
+    /// Generates a tensor of samples from the geometric distribution, the number of trials up
+    /// to and including the first success, with success probability `p`.
+    pub fn geometric<T>(&mut self, p: f64, shape: &[usize]) -> Tensor<T>
+            where T: NumericTrait {
+        let mut t = Tensor::zeros(shape);
+        self.fill_geometric(&mut t, p);
+        t
+    }
+
+    /// Fills `out` with samples from the geometric distribution, the number of trials up
+    /// to and including the first success, with success probability `p`.
+    pub fn fill_geometric<T>(&mut self, out: &mut Tensor<T>, p: f64)
+            where T: NumericTrait {
+        assert!(p > 0.0 && p <= 1.0, "Probability must be in (0, 1]");
+        self.fill(out, |rs| {
+            if p == 1.0 { 1.0 } else { (rs.next_exponential() / -(-p).ln_1p()).ceil().max(1.0) }
+        })
+    }
+
+    /// Generates a tensor of ones (with probability `p`) and zeros.
+    pub fn bernoulli<T>(&mut self, p: f64, shape: &[usize]) -> Tensor<T>
+            where T: NumericTrait {
+        let mut t = Tensor::zeros(shape);
+        self.fill_bernoulli(&mut t, p);
+        t
+    }
+
+    /// Fills `out` with ones (with probability `p`) and zeros.
+    pub fn fill_bernoulli<T>(&mut self, out: &mut Tensor<T>, p: f64)
+            where T: NumericTrait {
+        assert!(p >= 0.0 && p <= 1.0, "Probability must be in [0, 1]");
+        self.fill(out, |rs| if rs.next_f64() < p { 1.0 } else { 0.0 })
+    }
+
+    /// Generates a tensor of integers drawn uniformly from `[low, high)`.
+    pub fn randint<T>(&mut self, low: T, high: T, shape: &[usize]) -> Tensor<T>
+            where T: SampleInt {
+        let mut t = Tensor::zeros(shape);
+        self.fill_randint(&mut t, low, high);
+        t
+    }
+
+    /// Fills `out` with integers drawn uniformly from `[low, high)`.
+    pub fn fill_randint<T>(&mut self, out: &mut Tensor<T>, low: T, high: T)
+            where T: SampleInt {
+        assert!(low < high, "`low` must be less than `high`");
+        self.fill_uniform(out, low, high)
+    }
+
+    /// Generates a tensor of samples from the Laplace (double exponential) distribution.
+    pub fn laplace<T>(&mut self, loc: f64, scale: f64, shape: &[usize]) -> Tensor<T>
+            where T: NumericTrait + Float {
+        let mut t = Tensor::zeros(shape);
+        self.fill_laplace(&mut t, loc, scale);
+        t
+    }
+
+    /// Fills `out` with samples from the Laplace (double exponential) distribution.
+    pub fn fill_laplace<T>(&mut self, out: &mut Tensor<T>, loc: f64, scale: f64)
+            where T: NumericTrait + Float {
+        assert!(scale > 0.0, "Scale must be positive");
+        self.fill(out, |rs| {
+            let u = rs.next_open01() - 0.5;
+            loc - scale * u.signum() * (1.0 - 2.0 * u.abs()).ln()
+        })
+    }
+
+    /// Generates a tensor of samples from the Cauchy distribution.
+    pub fn cauchy<T>(&mut self, loc: f64, scale: f64, shape: &[usize]) -> Tensor<T>
+            where T: NumericTrait + Float {
+        let mut t = Tensor::zeros(shape);
+        self.fill_cauchy(&mut t, loc, scale);
+        t
+    }
+
+    /// Fills `out` with samples from the Cauchy distribution.
+    pub fn fill_cauchy<T>(&mut self, out: &mut Tensor<T>, loc: f64, scale: f64)
+            where T: NumericTrait + Float {
+        assert!(scale > 0.0, "Scale must be positive");
+        self.fill(out, |rs| loc + scale * (f64::consts::PI * (rs.next_open01() - 0.5)).tan())
+    }
+
+    /// Generates a tensor of samples from the Weibull distribution with shape `a` and unit
+    /// scale.
+    pub fn weibull<T>(&mut self, a: f64, shape: &[usize]) -> Tensor<T>
+            where T: NumericTrait + Float {
+        let mut t = Tensor::zeros(shape);
+        self.fill_weibull(&mut t, a);
+        t
+    }
+
+    /// Fills `out` with samples from the Weibull distribution with shape `a` and unit
+    /// scale.
+    pub fn fill_weibull<T>(&mut self, out: &mut Tensor<T>, a: f64)
+            where T: NumericTrait + Float {
+        assert!(a > 0.0, "Shape must be positive");
+        self.fill(out, |rs| rs.next_exponential().powf(1.0 / a))
+    }
+}
@@ -11,94 +11,404 @@
 //!
 //! let t = rs.uniform(0.0, 1.0, &[3, 3]);
 //! println!("{}", t);
-//! //  0.820987  0.93044 0.507159
-//! //  0.603939  0.31157 0.383515
-//! //  0.702227 0.346673 0.737954
+//! //  0.7812905187199857  0.1809218601375071  0.7805691944270181
+//! // 0.14594102000998865  0.9781072295548923  0.3814300541992903
+//! // 0.09939128266349051  0.4895659709087484  0.6986292072969005
 //! // [Tensor<f64> of shape 3x3]
 //! ```
-use rand::{Rng, SeedableRng, StdRng};
-use rand::distributions::range::SampleRange;
+//!
+//! The random bits come from a `BitGenerator`, which is `Pcg64` by default. `Xoshiro256PlusPlus`
+//! and the counter-based `Philox4x64` can be used instead, and independent streams for
+//! parallel work are created with `jump` or `spawn`:
+//!
+//! ```
+//! use numeric::random::{RandomState, Philox4x64};
+//!
+//! let mut rs = RandomState::<Philox4x64>::from_seed(1234);
+//! let mut workers = rs.spawn(4);
+//! let t = workers[0].standard_exponential::<f64>(&[100]);
+//! ```
+//!
+//! The samples drawn for a given seed and generator do not change between versions of
+//! Numeric, except where noted in the changelog.
 use num::traits::Float;
 use std::f64;
 
-use tensor::{Tensor, AxisIndex};
+use tensor::Tensor;
 use traits::NumericTrait;
-use math;
+use special::scalar::lgamma;
+
+mod bitgen;
+mod distributions;
+mod multivariate;
+mod sampling;
+mod resample;
+mod ziggurat;
 
-pub struct RandomState {
-    rng: StdRng,
+pub use self::bitgen::{BitGenerator, Pcg64, Xoshiro256PlusPlus, Philox4x64};
+pub use self::resample::{BootstrapResult, JackknifeResult, jackknife};
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-serialize", serde(transparent))]
+pub struct RandomState<G: BitGenerator = Pcg64> {
+    rng: G,
 }
 
 impl RandomState {
-    /// Creates a new `RandomState` object with the given seed. The object needs to be captured
-    /// as mutable in order to draw samples from it (since its internal state changes).
+    /// Creates a new `RandomState` object with the given seed, using the default `Pcg64`
+    /// generator. The object needs to be captured as mutable in order to draw samples from it
+    /// (since its internal state changes).
     pub fn new(seed: usize) -> RandomState {
-        let ss: &[_] = &[seed];
-        RandomState{rng: SeedableRng::from_seed(ss)}
+        RandomState::from_seed(seed as u64)
+    }
+}
+
+/// Element types that `RandomState::uniform` can draw.
+pub trait SampleUniform: NumericTrait {
+    /// Draws a value uniformly from `[low, high)`.
+    fn sample_uniform<G: BitGenerator>(rng: &mut G, low: Self, high: Self) -> Self;
+}
+
+/// The integer types among those of `SampleUniform`, which `RandomState::randint` can draw.
+pub trait SampleInt: SampleUniform {}
+
+macro_rules! sample_uniform_int {
+    ($t:ty, $u:ty) => (
+        impl SampleUniform for $t {
+            fn sample_uniform<G: BitGenerator>(rng: &mut G, low: $t, high: $t) -> $t {
+                let range = high.wrapping_sub(low) as $u as u64;
+                low.wrapping_add(bounded(rng, range) as $t)
+            }
+        }
+
+        impl SampleInt for $t {}
+    )
+}
+
+sample_uniform_int!(i8, u8);
+sample_uniform_int!(i16, u16);
+sample_uniform_int!(i32, u32);
+sample_uniform_int!(i64, u64);
+sample_uniform_int!(isize, usize);
+sample_uniform_int!(u8, u8);
+sample_uniform_int!(u16, u16);
+sample_uniform_int!(u32, u32);
+sample_uniform_int!(u64, u64);
+sample_uniform_int!(usize, usize);
+
+impl SampleUniform for f32 {
+    fn sample_uniform<G: BitGenerator>(rng: &mut G, low: f32, high: f32) -> f32 {
+        let u = (rng.next_u64() >> 40) as f32 * (1.0 / 16777216.0);
+        low + (high - low) * u
+    }
+}
+
+impl SampleUniform for f64 {
+    fn sample_uniform<G: BitGenerator>(rng: &mut G, low: f64, high: f64) -> f64 {
+        low + (high - low) * unit_f64(rng)
+    }
+}
+
+/// Copies the slices of `a` along `axis` in the order given by `indices`.
+fn take_along<T: Copy>(a: &Tensor<T>, axis: usize, indices: &[usize]) -> Tensor<T> {
+    let a = a.canonize();
+    let len = a.dim(axis);
+    let outer: usize = a.shape()[..axis].iter().product();
+    let inner: usize = a.shape()[axis + 1..].iter().product();
+    let data = a.slice();
+    let mut v = Vec::with_capacity(outer * indices.len() * inner);
+    for o in 0..outer {
+        for &i in indices {
+            let start = (o * len + i) * inner;
+            v.extend_from_slice(&data[start..start + inner]);
+        }
+    }
+    let mut shape: Vec<isize> = a.shape().iter().map(|&d| d as isize).collect();
+    shape[axis] = indices.len() as isize;
+    Tensor::new(v).reshape(&shape)
+}
+
+/// Draws a sample from the uniform distribution on `[0, 1)` with 53 random bits.
+fn unit_f64<G: BitGenerator>(rng: &mut G) -> f64 {
+    (rng.next_u64() >> 11) as f64 * (1.0 / 9007199254740992.0)
+}
+
+/// Draws a sample from the uniform distribution on `(0, 1)`.
+fn open01<G: BitGenerator>(rng: &mut G) -> f64 {
+    loop {
+        let u = unit_f64(rng);
+        if u > 0.0 {
+            return u;
+        }
+    }
+}
+
+/// Draws an integer uniformly from `0..n` without bias (Lemire's method).
+fn bounded<G: BitGenerator>(rng: &mut G, n: u64) -> u64 {
+    let mut m = rng.next_u64() as u128 * n as u128;
+    if (m as u64) < n {
+        let threshold = n.wrapping_neg() % n;
+        while (m as u64) < threshold {
+            m = rng.next_u64() as u128 * n as u128;
+        }
+    }
+    (m >> 64) as u64
+}
+
+impl<G: BitGenerator> RandomState<G> {
+    /// Creates a new `RandomState` object with the given seed and generator type, e.g.
+    /// `RandomState::<Philox4x64>::from_seed(1234)`.
+    pub fn from_seed(seed: u64) -> RandomState<G> {
+        RandomState{rng: G::from_seed(seed)}
+    }
+
+    /// Creates a new `RandomState` object drawing from the given generator.
+    pub fn from_generator(rng: G) -> RandomState<G> {
+        RandomState{rng: rng}
+    }
+
+    /// Returns the state of the underlying generator, so that it can be restored later with
+    /// `set_state`.
+    pub fn get_state(&self) -> Tensor<u64> {
+        Tensor::new(self.rng.state())
+    }
+
+    /// Restores a state returned by `get_state`. The state must come from the same type of
+    /// generator.
+    pub fn set_state(&mut self, state: &Tensor<u64>) {
+        assert!(state.ndim() == 1, "The state must be a vector (1D)");
+        let state: Vec<u64> = state.iter().collect();
+        self.rng = G::from_state(&state).expect("The state does not match the generator");
+    }
+
+    /// Advances the underlying generator far ahead (see `BitGenerator::jump`).
+    pub fn jump(&mut self) {
+        self.rng.jump();
+    }
+
+    /// Creates `n` independent random states for parallel work. Child `i` starts `i` jumps
+    /// ahead of the current state, and this state is then advanced past all children.
+    pub fn spawn(&mut self, n: usize) -> Vec<RandomState<G>> {
+        let mut children = Vec::with_capacity(n);
+        for _ in 0..n {
+            children.push(self.clone());
+            self.jump();
+        }
+        children
     }
 
     /// Generates a tensor by independently drawing samples from a uniform distribution in the 
     /// range [`low`, `high`). This is appropriate for integer types as well.
     pub fn uniform<T>(&mut self, low: T, high: T, shape: &[usize]) -> Tensor<T>
-            where T: NumericTrait + SampleRange {
+            where T: SampleUniform {
         let mut t = Tensor::zeros(shape);
-        {
-            let n = t.size();
-            let mut data = t.slice_mut();
-            for i in 0..n {
-                data[i] = self.rng.gen_range::<T>(low, high);
-            }
-        }
+        self.fill_uniform(&mut t, low, high);
         t
     }
 
+    /// Fills `out` with samples from a uniform distribution in the range [`low`, `high`).
+    pub fn fill_uniform<T>(&mut self, out: &mut Tensor<T>, low: T, high: T)
+            where T: SampleUniform {
+        assert!(low < high, "`low` must be less than `high`");
+        out.canonize_inplace();
+        for v in out.slice_mut().iter_mut() {
+            *v = T::sample_uniform(&mut self.rng, low, high);
+        }
+    }
+
     /// Generates a tensor by independently drawing samples from a standard normal.
-    pub fn normal<T>(&mut self, shape: &[usize]) -> Tensor<T>
-            where T: NumericTrait + SampleRange + Float {
-        let u1 = self.uniform(T::zero(), T::one(), shape);
-        let u2 = self.uniform(T::zero(), T::one(), shape);
+    pub fn standard_normal<T>(&mut self, shape: &[usize]) -> Tensor<T>
+            where T: NumericTrait + Float {
+        let mut t = Tensor::zeros(shape);
+        self.fill_standard_normal(&mut t);
+        t
+    }
+
+    /// Fills `out` with samples from a standard normal.
+    pub fn fill_standard_normal<T>(&mut self, out: &mut Tensor<T>)
+            where T: NumericTrait + Float {
+        self.fill(out, |rs| rs.next_normal());
+    }
 
-        let minustwo = Tensor::fscalar(-2.0);
-        let twopi = Tensor::fscalar(2.0 * f64::consts::PI);
+    /// Generates a tensor by independently drawing samples from a normal distribution with the
+    /// given mean and standard deviation.
+    pub fn normal<T>(&mut self, mean: T, std: T, shape: &[usize]) -> Tensor<T>
+            where T: NumericTrait + Float {
+        let mut t = Tensor::zeros(shape);
+        self.fill_normal(&mut t, mean, std);
+        t
+    }
 
-        math::sqrt(math::ln(u1) * &minustwo) * &math::cos(u2 * &twopi)
+    /// Fills `out` with samples from a normal distribution with the given mean and standard
+    /// deviation.
+    pub fn fill_normal<T>(&mut self, out: &mut Tensor<T>, mean: T, std: T)
+            where T: NumericTrait + Float {
+        assert!(std >= T::zero(), "Standard deviation must be non-negative");
+        let (mean, std) = (mean.to_f64().unwrap(), std.to_f64().unwrap());
+        self.fill(out, |rs| mean + std * rs.next_normal());
     }
 
     /// Shuffle tensor in-place along its first axis. This uses the modern version of the
     /// Fisher-Yates algorithm.
     pub fn shuffle<T>(&mut self, a: &mut Tensor<T>) -> ()
             where T: Copy {
-        if a.ndim() == 1 && a.size() > 0 {
-            a.canonize_inplace();
-            let n = a.dim(0);
-            {
-                let mut data = a.mem_slice_mut();
-                for i in (1..n).rev() {
-                    let j = self.rng.gen_range::<usize>(0, i + 1);
-                    data.swap(i, j);
-                }
+        if a.ndim() > 0 {
+            self.shuffle_axis(a, 0);
+        }
+    }
+
+    /// Draws a sample from the uniform distribution on `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        unit_f64(&mut self.rng)
+    }
+
+    /// Draws an index uniformly from `0..n`.
+    fn next_index(&mut self, n: usize) -> usize {
+        bounded(&mut self.rng, n as u64) as usize
+    }
+
+    /// Fisher-Yates shuffle of `0..n`.
+    fn permutation_vec(&mut self, n: usize) -> Vec<usize> {
+        let mut perm: Vec<usize> = (0..n).collect();
+        for i in (1..n).rev() {
+            let j = self.next_index(i + 1);
+            perm.swap(i, j);
+        }
+        perm
+    }
+
+    /// Draws a sample from the uniform distribution on `(0, 1)`.
+    fn next_open01(&mut self) -> f64 {
+        open01(&mut self.rng)
+    }
+
+    /// Draws a sample from the standard normal distribution (Ziggurat method).
+    fn next_normal(&mut self) -> f64 {
+        ziggurat::normal(&mut self.rng)
+    }
+
+    /// Draws a sample from the standard exponential distribution (Ziggurat method).
+    fn next_exponential(&mut self) -> f64 {
+        ziggurat::exponential(&mut self.rng)
+    }
+
+    /// Draws a sample from the gamma distribution with shape `k` and unit scale (Marsaglia and
+    /// Tsang's method).
+    fn next_gamma(&mut self, k: f64) -> f64 {
+        if k < 1.0 {
+            let u = self.next_open01();
+            return self.next_gamma(k + 1.0) * u.powf(1.0 / k);
+        }
+        let d = k - 1.0 / 3.0;
+        let c = 1.0 / (9.0 * d).sqrt();
+        loop {
+            let x = self.next_normal();
+            let v = 1.0 + c * x;
+            if v <= 0.0 {
+                continue;
             }
-        } else if a.ndim() >= 2 && a.size() > 0 {
-            a.canonize_inplace();
-            let mut row_shape: Vec<usize> = Vec::with_capacity(a.ndim() - 1);
-            for i in 1..a.ndim() {
-                row_shape.push(a.shape()[i]);
+            let v = v * v * v;
+            let u = self.next_open01();
+            if u < 1.0 - 0.0331 * x * x * x * x || u.ln() < 0.5 * x * x + d * (1.0 - v + v.ln()) {
+                return d * v;
             }
-            let n = a.dim(0);
-            let mut row1: Tensor<T> = Tensor::empty(&row_shape[..]);
-            let mut row2: Tensor<T> = Tensor::empty(&row_shape[..]);
-            for i in (1..n).rev() {
-                let j: usize = self.rng.gen_range::<usize>(0, i + 1);
-                // TODO: Can be made faster.
-                // Rust requires us to have two buffers instead of just one, but
-                // we can probably make it even faster by shuffling indices first
-                // and then move memory around. A specialized swap rows function
-                // could also make things faster.
-                row1.set(&a.index(&[AxisIndex::Index(i as isize)]));
-                row2.set(&a.index(&[AxisIndex::Index(j as isize)]));
-                a.index_set(&[AxisIndex::Index(i as isize)], &row2);
-                a.index_set(&[AxisIndex::Index(j as isize)], &row1);
+        }
+    }
+
+    /// Draws a Poisson sample by multiplying uniforms for small means, and otherwise with the
+    /// transformed rejection method (PTRS) of Hörmann.
+    fn next_poisson(&mut self, lam: f64) -> f64 {
+        if lam < 10.0 {
+            let limit = (-lam).exp();
+            let mut k = 0.0;
+            let mut prod = self.next_f64();
+            while prod > limit {
+                k += 1.0;
+                prod *= self.next_f64();
+            }
+            return k;
+        }
+        let slam = lam.sqrt();
+        let loglam = lam.ln();
+        let b = 0.931 + 2.53 * slam;
+        let a = -0.059 + 0.02483 * b;
+        let invalpha = 1.1239 + 1.1328 / (b - 3.4);
+        let vr = 0.9277 - 3.6224 / (b - 2.0);
+        loop {
+            let u = self.next_f64() - 0.5;
+            let v = self.next_open01();
+            let us = 0.5 - u.abs();
+            let k = ((2.0 * a / us + b) * u + lam + 0.43).floor();
+            if us >= 0.07 && v <= vr {
+                return k;
             }
+            if k < 0.0 || (us < 0.013 && v > us) {
+                continue;
+            }
+            if v.ln() + invalpha.ln() - (a / (us * us) + b).ln() <=
+               -lam + k * loglam - lgamma(k + 1.0) {
+                return k;
+            }
+        }
+    }
+
+    /// Draws a binomial sample by inversion for small means, and otherwise with the transformed
+    /// rejection method (BTRS) of Hörmann.
+    fn next_binomial(&mut self, n: f64, p: f64) -> f64 {
+        if p > 0.5 {
+            return n - self.next_binomial(n, 1.0 - p);
+        }
+        if n == 0.0 || p == 0.0 {
+            return 0.0;
+        }
+        let q = 1.0 - p;
+        if n * p < 10.0 {
+            let s = p / q;
+            let a = (n + 1.0) * s;
+            let mut r = q.powf(n);
+            let mut u = self.next_f64();
+            let mut x = 0.0;
+            while u > r && x < n {
+                u -= r;
+                x += 1.0;
+                r *= a / x - s;
+            }
+            return x;
+        }
+        let spq = (n * p * q).sqrt();
+        let b = 1.15 + 2.53 * spq;
+        let a = -0.0873 + 0.0248 * b + 0.01 * p;
+        let c = n * p + 0.5;
+        let vr = 0.92 - 4.2 / b;
+        let alpha = (2.83 + 5.1 / b) * spq;
+        let lpq = (p / q).ln();
+        let m = ((n + 1.0) * p).floor();
+        let h = lgamma(m + 1.0) + lgamma(n - m + 1.0);
+        loop {
+            let u = self.next_f64() - 0.5;
+            let v = self.next_open01();
+            let us = 0.5 - u.abs();
+            let k = ((2.0 * a / us + b) * u + c).floor();
+            if k < 0.0 || k > n {
+                continue;
+            }
+            if us >= 0.07 && v <= vr {
+                return k;
+            }
+            let v = (v * alpha / (a / (us * us) + b)).ln();
+            if v <= h - lgamma(k + 1.0) - lgamma(n - k + 1.0) + (k - m) * lpq {
+                return k;
+            }
+        }
+    }
+
+    /// Overwrites each element of `out` with a sample drawn with `f`.
+    fn fill<T, F>(&mut self, out: &mut Tensor<T>, mut f: F)
+            where T: NumericTrait, F: FnMut(&mut RandomState<G>) -> f64 {
+        out.canonize_inplace();
+        for v in out.slice_mut().iter_mut() {
+            *v = T::from(f(self)).unwrap();
         }
     }
 }
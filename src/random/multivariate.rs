@@ -0,0 +1,178 @@
+use tensor::Tensor;
+use traits::NumericTrait;
+use linalg::{LinalgError, diag};
+use super::{RandomState, BitGenerator};
+
+impl<G: BitGenerator> RandomState<G> {
+    /// Generates `n` samples from the multivariate normal distribution with the given mean
+    /// (a vector of length `d`) and covariance (a `d x d` matrix), returned as a tensor of shape
+    /// `[n, d]`. The covariance is factorized with a Cholesky decomposition, or with an SVD if it
+    /// is singular.
+    ///
+    /// Returns `Err(LinalgError::NotPositiveDefinite)` if the covariance is not symmetric
+    /// positive semi-definite.
+    pub fn multivariate_normal(&mut self, mean: &Tensor<f64>, cov: &Tensor<f64>, n: usize)
+            -> Result<Tensor<f64>, LinalgError> {
+        let mut x = Tensor::zeros(&[n, mean.size()]);
+        try!(self.fill_multivariate_normal(&mut x, mean, cov));
+        Ok(x)
+    }
+
+    /// Fills `out`, a matrix of shape `[n, d]`, with samples from the multivariate normal
+    /// distribution (see `multivariate_normal`).
+    pub fn fill_multivariate_normal(&mut self, out: &mut Tensor<f64>, mean: &Tensor<f64>,
+                                    cov: &Tensor<f64>) -> Result<(), LinalgError> {
+        assert!(mean.ndim() == 1, "`mean` must be a vector (1D)");
+        let d = mean.size();
+        assert!(out.ndim() == 2 && out.dim(1) == d, "`out` must be a matrix of shape [n, d]");
+        let n = out.dim(0);
+        assert!(cov.ndim() == 2 && cov.dim(0) == d && cov.dim(1) == d,
+                "`cov` must be a square matrix matching `mean`");
+
+        let tol = 1e-8 * (1.0 + cov.iter().fold(0.0, |m: f64, x| m.max(x.abs())));
+        if (0..d).any(|i| (0..i).any(|j| (cov[(i, j)] - cov[(j, i)]).abs() > tol)) {
+            return Err(LinalgError::NotPositiveDefinite);
+        }
+
+        // A factor with cov = A A^T
+        let a = match cov.cholesky(true) {
+            Ok(l) => l,
+            Err(_) => {
+                let (u, s, vt) = cov.svd(false);
+                // For a symmetric positive semi-definite matrix, U S V^T = V S V^T
+                let sym = vt.transpose().dot(&diag(&s).dot(&vt));
+                if sym.iter().zip(cov.iter()).any(|(x, y)| (x - y).abs() > tol) {
+                    return Err(LinalgError::NotPositiveDefinite);
+                }
+                let mut a = u.canonize();
+                for i in 0..d {
+                    for j in 0..d {
+                        a[(i, j)] *= s[(j,)].sqrt();
+                    }
+                }
+                a
+            },
+        };
+        self.fill(out, |rs| rs.next_normal());
+        let mut x = out.dot(&a.transpose());
+        for i in 0..n {
+            for j in 0..d {
+                x[(i, j)] += mean[(j,)];
+            }
+        }
+        *out = x;
+        Ok(())
+    }
+
+    /// Generates `n` samples from the Dirichlet distribution with concentration parameters
+    /// `alpha` (a vector of length `k`), returned as a tensor of shape `[n, k]` whose rows sum
+    /// to one.
+    pub fn dirichlet(&mut self, alpha: &Tensor<f64>, n: usize) -> Tensor<f64> {
+        let mut x = Tensor::zeros(&[n, alpha.size()]);
+        self.fill_dirichlet(&mut x, alpha);
+        x
+    }
+
+    /// Fills `out`, a matrix of shape `[n, k]`, with samples from the Dirichlet distribution
+    /// with concentration parameters `alpha`.
+    pub fn fill_dirichlet(&mut self, out: &mut Tensor<f64>, alpha: &Tensor<f64>) {
+        assert!(alpha.ndim() == 1, "`alpha` must be a vector (1D)");
+        assert!(alpha.iter().all(|a| a > 0.0), "Concentration parameters must be positive");
+        let alpha: Vec<f64> = alpha.iter().collect();
+        let k = alpha.len();
+        assert!(out.ndim() == 2 && out.dim(1) == k, "`out` must be a matrix of shape [n, k]");
+        out.canonize_inplace();
+        for row in out.slice_mut().chunks_mut(k.max(1)) {
+            let mut sum = 0.0;
+            for (v, &a) in row.iter_mut().zip(alpha.iter()) {
+                *v = self.next_gamma(a);
+                sum += *v;
+            }
+            for v in row.iter_mut() {
+                *v /= sum;
+            }
+        }
+    }
+
+    /// Generates samples from the multinomial distribution, counting the outcomes of `n`
+    /// trials with probabilities `pvals` (a vector of length `k`). The result has shape
+    /// `shape + [k]`. The last probability is taken as the remainder, so that the probabilities
+    /// sum to one.
+    pub fn multinomial<T>(&mut self, n: usize, pvals: &Tensor<f64>, shape: &[usize]) -> Tensor<T>
+            where T: NumericTrait {
+        let mut out_shape = shape.to_vec();
+        out_shape.push(pvals.size());
+        let mut x = Tensor::zeros(&out_shape);
+        self.fill_multinomial(&mut x, n, pvals);
+        x
+    }
+
+    /// Fills `out`, whose last axis has the length `k` of `pvals`, with samples from the
+    /// multinomial distribution (see `multinomial`).
+    pub fn fill_multinomial<T>(&mut self, out: &mut Tensor<T>, n: usize, pvals: &Tensor<f64>)
+            where T: NumericTrait {
+        assert!(pvals.ndim() == 1 && pvals.size() > 0, "`pvals` must be a non-empty vector (1D)");
+        assert!(pvals.iter().all(|p| p >= 0.0 && p <= 1.0), "Probabilities must be in [0, 1]");
+        let pvals: Vec<f64> = pvals.iter().collect();
+        let k = pvals.len();
+        assert!(pvals[..k - 1].iter().sum::<f64>() <= 1.0 + 1e-12,
+                "Probabilities must not sum to more than one");
+        assert!(out.ndim() > 0 && out.dim(out.ndim() - 1) == k,
+                "The last axis of `out` must match `pvals`");
+        out.canonize_inplace();
+        for row in out.slice_mut().chunks_mut(k) {
+            // Draw each count conditioned on the previous ones
+            let mut remaining = n as f64;
+            let mut mass = 1.0;
+            for j in 0..k - 1 {
+                let count = if remaining > 0.0 && mass > 0.0 {
+                    self.next_binomial(remaining, (pvals[j] / mass).min(1.0).max(0.0))
+                } else {
+                    0.0
+                };
+                row[j] = T::from(count).unwrap();
+                remaining -= count;
+                mass -= pvals[j];
+            }
+            row[k - 1] = T::from(remaining).unwrap();
+        }
+    }
+
+    /// Generates a tensor of category indices in `0..k`, drawn with the probabilities `probs`
+    /// (a vector of length `k`, normalized to sum to one).
+    pub fn categorical<T>(&mut self, probs: &Tensor<f64>, shape: &[usize]) -> Tensor<T>
+            where T: NumericTrait {
+        let mut t = Tensor::zeros(shape);
+        self.fill_categorical(&mut t, probs);
+        t
+    }
+
+    /// Fills `out` with category indices in `0..k`, drawn with the probabilities `probs`
+    /// (a vector of length `k`, normalized to sum to one).
+    pub fn fill_categorical<T>(&mut self, out: &mut Tensor<T>, probs: &Tensor<f64>)
+            where T: NumericTrait {
+        assert!(probs.ndim() == 1 && probs.size() > 0, "`probs` must be a non-empty vector (1D)");
+        assert!(probs.iter().all(|p| p >= 0.0), "Probabilities must be non-negative");
+        let mut cdf: Vec<f64> = probs.iter().scan(0.0, |s, p| { *s += p; Some(*s) }).collect();
+        let total = cdf[cdf.len() - 1];
+        assert!(total > 0.0 && total.is_finite(), "Probabilities must have a positive sum");
+        for c in cdf.iter_mut() {
+            *c /= total;
+        }
+        let k = cdf.len();
+        self.fill(out, |rs| {
+            let u = rs.next_f64();
+            // The first index with u < cdf[i], skipping categories with zero probability
+            let (mut lo, mut hi) = (0, k - 1);
+            while lo < hi {
+                let mid = (lo + hi) / 2;
+                if u < cdf[mid] {
+                    hi = mid;
+                } else {
+                    lo = mid + 1;
+                }
+            }
+            lo as f64
+        })
+    }
+}
@@ -0,0 +1,170 @@
+use tensor::Tensor;
+use super::{RandomState, BitGenerator, take_along};
+
+/// The result of `RandomState::bootstrap`.
+pub struct BootstrapResult {
+    /// The statistic computed on the original data.
+    pub estimate: f64,
+    /// The statistic computed on each resample.
+    pub distribution: Tensor<f64>,
+    /// The standard deviation of the bootstrap distribution.
+    pub standard_error: f64,
+}
+
+impl BootstrapResult {
+    /// Returns the percentile confidence interval `(low, high)` at the given level (e.g. `0.95`),
+    /// interpolating linearly between the sorted bootstrap statistics.
+    pub fn confidence_interval(&self, level: f64) -> (f64, f64) {
+        assert!(level > 0.0 && level < 1.0, "The confidence level must be in (0, 1)");
+        let mut sorted: Vec<f64> = self.distribution.iter().collect();
+        assert!(sorted.len() > 0, "The bootstrap distribution is empty");
+        sorted.sort_by(|a, b| a.partial_cmp(b).expect("The statistic returned NaN"));
+        let alpha = 0.5 * (1.0 - level);
+        (quantile(&sorted, alpha), quantile(&sorted, 1.0 - alpha))
+    }
+}
+
+/// The result of `jackknife`.
+pub struct JackknifeResult {
+    /// The statistic computed on the original data.
+    pub estimate: f64,
+    /// The statistic computed with each slice left out in turn.
+    pub values: Tensor<f64>,
+    /// The jackknife estimate of the bias of the statistic.
+    pub bias: f64,
+    /// The jackknife estimate of the standard error of the statistic.
+    pub standard_error: f64,
+}
+
+/// The `p`-quantile of sorted values, interpolating linearly.
+fn quantile(sorted: &[f64], p: f64) -> f64 {
+    let pos = p * (sorted.len() - 1) as f64;
+    let i = pos.floor() as usize;
+    if i + 1 >= sorted.len() {
+        sorted[sorted.len() - 1]
+    } else {
+        sorted[i] + (pos - i as f64) * (sorted[i + 1] - sorted[i])
+    }
+}
+
+fn mean_std(values: &Tensor<f64>) -> (f64, f64) {
+    let n = values.size() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let var = values.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / n;
+    (mean, var.sqrt())
+}
+
+/// Estimates the bias and standard error of `statistic` by recomputing it with each slice of
+/// `a` along `axis` left out in turn.
+pub fn jackknife<T, F>(a: &Tensor<T>, axis: usize, mut statistic: F) -> JackknifeResult
+        where T: Copy, F: FnMut(&Tensor<T>) -> f64 {
+    assert!(axis < a.ndim(), "Axis out of range");
+    let n = a.dim(axis);
+    assert!(n >= 2, "At least two slices are needed along the axis");
+    let estimate = statistic(a);
+    let values = Tensor::new((0..n).map(|i| {
+        let keep: Vec<usize> = (0..n).filter(|&j| j != i).collect();
+        statistic(&take_along(a, axis, &keep))
+    }).collect());
+    let (mean, std) = mean_std(&values);
+    let nf = n as f64;
+    JackknifeResult {
+        estimate: estimate,
+        values: values,
+        bias: (nf - 1.0) * (mean - estimate),
+        standard_error: (nf - 1.0).sqrt() * std,
+    }
+}
+
+impl<G: BitGenerator> RandomState<G> {
+    /// Computes the bootstrap distribution of `statistic`, by evaluating it on `n_resamples`
+    /// resamples of `a` drawn with replacement along `axis`. Confidence intervals are given by
+    /// `BootstrapResult::confidence_interval`.
+    ///
+    /// ```
+    /// use numeric::Tensor;
+    /// use numeric::random::RandomState;
+    ///
+    /// let mut rs = RandomState::new(1234);
+    /// let t: Tensor<f64> = rs.normal(5.0, 1.0, &[100]);
+    /// let mean = |x: &Tensor<f64>| x.iter().sum::<f64>() / x.size() as f64;
+    /// let res = rs.bootstrap(&t, 1000, 0, mean);
+    /// let (low, high) = res.confidence_interval(0.95);
+    /// assert!(low < res.estimate && res.estimate < high);
+    /// ```
+    pub fn bootstrap<T, F>(&mut self, a: &Tensor<T>, n_resamples: usize, axis: usize,
+                           mut statistic: F) -> BootstrapResult
+            where T: Copy, F: FnMut(&Tensor<T>) -> f64 {
+        assert!(axis < a.ndim(), "Axis out of range");
+        let n = a.dim(axis);
+        assert!(n > 0, "Cannot resample an empty axis");
+        let estimate = statistic(a);
+        let mut indices = vec![0; n];
+        let distribution = Tensor::new((0..n_resamples).map(|_| {
+            for i in indices.iter_mut() {
+                *i = self.next_index(n);
+            }
+            statistic(&take_along(a, axis, &indices))
+        }).collect());
+        let (_, std) = mean_std(&distribution);
+        BootstrapResult {
+            estimate: estimate,
+            distribution: distribution,
+            standard_error: std,
+        }
+    }
+
+    /// Randomly splits the indices `0..n` into `k` folds of (almost) equal size, and returns a
+    /// `(train, test)` pair of sorted index vectors for each fold.
+    pub fn kfold(&mut self, n: usize, k: usize) -> Vec<(Tensor<usize>, Tensor<usize>)> {
+        assert!(k >= 2 && k <= n, "The number of folds must be in [2, n]");
+        let perm = self.permutation_vec(n);
+        let mut folds = vec![Vec::new(); k];
+        for (i, &j) in perm.iter().enumerate() {
+            folds[i % k].push(j);
+        }
+        split_folds(n, folds)
+    }
+
+    /// Like `kfold`, but each fold has (almost) the same proportion of each label as the whole
+    /// data set. `labels` is a vector with the label of each of the `n` samples.
+    pub fn stratified_kfold<T>(&mut self, labels: &Tensor<T>, k: usize)
+            -> Vec<(Tensor<usize>, Tensor<usize>)>
+            where T: Copy + PartialEq {
+        assert!(labels.ndim() == 1, "`labels` must be a vector (1D)");
+        let n = labels.size();
+        assert!(k >= 2 && k <= n, "The number of folds must be in [2, n]");
+        let labels: Vec<T> = labels.iter().collect();
+        let mut classes: Vec<T> = Vec::new();
+        for &l in labels.iter() {
+            if !classes.contains(&l) {
+                classes.push(l);
+            }
+        }
+        // Deal the shuffled members of each class to the folds in turn, continuing from one
+        // class to the next so that the fold sizes stay balanced
+        let mut folds = vec![Vec::new(); k];
+        let mut f = 0;
+        for &c in classes.iter() {
+            let members: Vec<usize> = (0..n).filter(|&i| labels[i] == c).collect();
+            for j in self.permutation_vec(members.len()) {
+                folds[f].push(members[j]);
+                f = (f + 1) % k;
+            }
+        }
+        split_folds(n, folds)
+    }
+}
+
+/// Turns the test indices of each fold into sorted `(train, test)` pairs.
+fn split_folds(n: usize, folds: Vec<Vec<usize>>) -> Vec<(Tensor<usize>, Tensor<usize>)> {
+    folds.into_iter().map(|mut test| {
+        test.sort();
+        let mut in_test = vec![false; n];
+        for &i in test.iter() {
+            in_test[i] = true;
+        }
+        let train: Vec<usize> = (0..n).filter(|&i| !in_test[i]).collect();
+        (Tensor::new(train), Tensor::new(test))
+    }).collect()
+}
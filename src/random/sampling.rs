@@ -0,0 +1,145 @@
+use std::f64;
+
+use tensor::Tensor;
+use super::{RandomState, BitGenerator, take_along};
+
+/// Walker's alias table, for constant-time draws from a discrete distribution.
+struct AliasTable {
+    prob: Vec<f64>,
+    alias: Vec<usize>,
+}
+
+impl AliasTable {
+    /// Builds the table with Vose's method from non-negative weights with a positive sum.
+    fn new(weights: &[f64]) -> AliasTable {
+        let n = weights.len();
+        let total: f64 = weights.iter().sum();
+        let mut p: Vec<f64> = weights.iter().map(|w| w * n as f64 / total).collect();
+        let mut prob = vec![1.0; n];
+        let mut alias: Vec<usize> = (0..n).collect();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) = (0..n).partition(|&i| p[i] < 1.0);
+        while !small.is_empty() && !large.is_empty() {
+            let s = small.pop().unwrap();
+            let l = large.pop().unwrap();
+            prob[s] = p[s];
+            alias[s] = l;
+            p[l] += p[s] - 1.0;
+            if p[l] < 1.0 {
+                small.push(l);
+            } else {
+                large.push(l);
+            }
+        }
+        // Whatever is left has probability one up to rounding errors
+        AliasTable{prob: prob, alias: alias}
+    }
+
+    fn draw<G: BitGenerator>(&self, rs: &mut RandomState<G>) -> usize {
+        let i = rs.next_index(self.prob.len());
+        if rs.next_f64() < self.prob[i] { i } else { self.alias[i] }
+    }
+}
+
+impl<G: BitGenerator> RandomState<G> {
+    /// Generates a random permutation of `0..n`.
+    pub fn permutation(&mut self, n: usize) -> Tensor<usize> {
+        Tensor::new(self.permutation_vec(n))
+    }
+
+    /// Returns a copy of `a` with its slices along `axis` randomly permuted.
+    pub fn permutation_of<T>(&mut self, a: &Tensor<T>, axis: usize) -> Tensor<T>
+            where T: Copy {
+        assert!(axis < a.ndim(), "Axis out of range");
+        let perm = self.permutation_vec(a.dim(axis));
+        take_along(a, axis, &perm)
+    }
+
+    /// Shuffle tensor in-place along the given axis. This uses the modern version of the
+    /// Fisher-Yates algorithm on the indices, and then moves each slice once.
+    pub fn shuffle_axis<T>(&mut self, a: &mut Tensor<T>, axis: usize) -> ()
+            where T: Copy {
+        assert!(axis < a.ndim(), "Axis out of range");
+        let perm = self.permutation_vec(a.dim(axis));
+        *a = take_along(a, axis, &perm);
+    }
+
+    /// Generates a tensor of the given shape by drawing elements from the vector `a`, with or
+    /// without replacement. If `weights` is given (a vector of non-negative numbers of the
+    /// same length as `a`), elements are drawn with probability proportional to their weight.
+    ///
+    /// Weighted draws with replacement use Walker's alias method. Weighted draws without
+    /// replacement follow the sequential model (as if drawing one element at a time and
+    /// removing it), using the exponential keys of Efraimidis and Spirakis.
+    pub fn choice<T>(&mut self, a: &Tensor<T>, size: &[usize], replace: bool,
+                     weights: Option<&Tensor<f64>>) -> Tensor<T>
+            where T: Copy {
+        assert!(a.ndim() == 1, "`a` must be a vector (1D)");
+        let n = a.size();
+        let k: usize = size.iter().product();
+        let size: Vec<isize> = size.iter().map(|&d| d as isize).collect();
+        if k == 0 {
+            return Tensor::new(vec![]).reshape(&size);
+        }
+        assert!(n > 0, "Cannot draw from an empty tensor");
+        assert!(replace || k <= n, "Cannot draw more elements than available without replacement");
+
+        let indices: Vec<usize> = match weights {
+            None if replace => (0..k).map(|_| self.next_index(n)).collect(),
+            None => {
+                // A partial Fisher-Yates shuffle
+                let mut idx: Vec<usize> = (0..n).collect();
+                for i in 0..k {
+                    let j = i + self.next_index(n - i);
+                    idx.swap(i, j);
+                }
+                idx.truncate(k);
+                idx
+            },
+            Some(w) => {
+                assert!(w.ndim() == 1 && w.size() == n, "`weights` must be a vector matching `a`");
+                let w: Vec<f64> = w.iter().collect();
+                assert!(w.iter().all(|&x| x >= 0.0 && x.is_finite()),
+                        "Weights must be non-negative and finite");
+                assert!(w.iter().any(|&x| x > 0.0), "Weights must have a positive sum");
+                if replace {
+                    let table = AliasTable::new(&w);
+                    (0..k).map(|_| table.draw(self)).collect()
+                } else {
+                    assert!(w.iter().filter(|&&x| x > 0.0).count() >= k,
+                            "Fewer non-zero weights than elements to draw");
+                    // The largest keys u^(1/w), compared through their logarithm
+                    let keys: Vec<f64> = w.iter().map(|&x| {
+                        let u = self.next_open01();
+                        if x > 0.0 { u.ln() / x } else { f64::NEG_INFINITY }
+                    }).collect();
+                    let mut idx: Vec<usize> = (0..n).collect();
+                    idx.sort_by(|&i, &j| keys[j].partial_cmp(&keys[i]).unwrap());
+                    idx.truncate(k);
+                    idx
+                }
+            },
+        };
+        let data = a.canonize();
+        let data = data.slice();
+        Tensor::new(indices.iter().map(|&i| data[i]).collect()).reshape(&size)
+    }
+
+    /// Draws `k` items uniformly without replacement from an iterator of unknown length
+    /// (reservoir sampling). If the iterator has fewer than `k` items, all of them are
+    /// returned.
+    pub fn reservoir_sample<I>(&mut self, iter: I, k: usize) -> Vec<I::Item>
+            where I: IntoIterator {
+        let mut reservoir = Vec::with_capacity(k);
+        for (i, item) in iter.into_iter().enumerate() {
+            if i < k {
+                reservoir.push(item);
+            } else {
+                let j = self.next_index(i + 1);
+                if j < k {
+                    reservoir[j] = item;
+                }
+            }
+        }
+        reservoir
+    }
+}
@@ -0,0 +1,332 @@
+//! The Ziggurat method of Marsaglia and Tsang for the normal and exponential distributions.
+//!
+//! The density is covered by 256 layers of equal area: a base strip (with the tail beyond
+//! `R`) and 255 rectangles. A sample is drawn from a random layer and, most of the time, is
+//! accepted after a single comparison.
+
+use super::{BitGenerator, unit_f64, open01};
+
+const LAYERS: usize = 256;
+
+// The tables hold the right edges `x` of the layers, with `x[0] = V / f(R)`, `x[1] = R` and
+// `x[256] = 0`, and the density `f` at each edge. They were computed with 60-digit arithmetic
+// and rounded to the nearest `f64`, so that the samples do not depend on the precision of the
+// platform's `exp` and `ln`.
+
+// The normal tables, with `R = 3.654152885361008796`, `V = 0.00492867323399` and
+// `f(x) = exp(-x^2 / 2)`
+static NORMAL_X: [f64; LAYERS + 1] = [
+    3.910757959537092, 3.654152885361009, 3.4492782985609645, 3.320244733839166, 3.224575052047029,
+    3.14788928951715, 3.083526132001233, 3.0278377917686354, 2.978603279880845, 2.9343668672078547,
+    2.894121053612348, 2.8571387308721325, 2.822877396825325, 2.790921174000786, 2.760944005278823,
+    2.732685359042827, 2.705933656121858, 2.680514643284522, 2.6562830375755024,
+    2.6331163936303246, 2.6109105184875485, 2.5895759867069956, 2.569035452680537,
+    2.5492215503234608, 2.530075232158517, 2.5115444416253427, 2.4935830412696807,
+    2.4761499396691433, 2.4592083743333113, 2.442725318198957, 2.4266709849357264,
+    2.411018413899686, 2.395743119780481, 2.3808227951706264, 2.366237056715819,
+    2.3519672273776604, 2.337996148795032, 2.3243080188696235, 2.3108882505998505,
+    2.2977233489013305, 2.284800802722947, 2.2721089902268248, 2.2596370951722187,
+    2.2473750329458086, 2.235313384928329, 2.2234433400909066, 2.2117566428825457,
+    2.2002455466096493, 2.1889027716247225, 2.177721467738643, 2.1666951803526473,
+    2.1558178198750646, 2.145083634046205, 2.1344871828443215, 2.1240233156878165,
+    2.113687150684935, 2.1034740557131477, 2.093379631137051, 2.0833996939965527,
+    2.0735302635169797, 2.0637675478099573, 2.054107931648866, 2.044547965215734, 2.03508435372781,
+    2.0257139478620343, 2.0164337349043726, 2.0072408305586857, 1.9981324713565654,
+    1.9891060076155724, 1.9801588968985995, 1.9712886979317705, 1.9624930649424628,
+    1.9537697423827352, 1.945116560006755, 1.9365314282737598, 1.9280123340507191,
+    1.91955733659123, 1.9111645637692833, 1.9028322085484475, 1.8945585256687112,
+    1.8863418285347775, 1.8781804862909786, 1.8700729210692375, 1.862017605397633,
+    1.8540130597581486, 1.84605785028312, 1.838150586580729, 1.830289919680667, 1.8224745400917837,
+    1.814703175964168, 1.8069745913486939, 1.7992875845475806, 1.7916409865500105,
+    1.7840336595472768, 1.7764644955223454, 1.7689324149090784, 1.761436365316707,
+    1.7539753203154553, 1.7465482782794932, 1.7391542612836695, 1.7317923140507077,
+    1.7244615029457762, 1.717160915015541, 1.709889657069006, 1.702646854797614,
+    1.6954316519322385, 1.6882432094348585, 1.6810807047228231, 1.6739433309237604,
+    1.6668302961592867, 1.6597408228557895, 1.6526741470806485, 1.6456295179023606,
+    1.6386061967731114, 1.6316034569324225, 1.6246205828305689, 1.6176568695705347,
+    1.6107116223673341, 1.6037841560235833, 1.5968737944202616, 1.5899798700216488,
+    1.5831017233934717, 1.5762387027333329, 1.5693901634125342, 1.5625554675284394,
+    1.5557339834665547, 1.5489250854715353, 1.5421281532263473, 1.5353425714388427,
+    1.528567729435024, 1.5218030207582924, 1.5150478427739917, 1.5083015962785713,
+    1.5015636851127059, 1.494833515777718, 1.4881104970546537, 1.4813940396253753,
+    1.474683555695025, 1.4679784586152305, 1.4612781625074076, 1.454582081885523,
+    1.4478896312776697, 1.441200224845798, 1.4345132760029464, 1.4278281970272901,
+    1.4211443986723227, 1.4144612897724644, 1.407778276843371, 1.4010947636762021,
+    1.3944101509250708, 1.3877238356868842, 1.3810352110727415, 1.37434366577003,
+    1.3676485835943175, 1.3609493430301014, 1.35424531675943, 1.3475358711773586,
+    1.3408203658931515, 1.3340981532160832, 1.3273685776246245, 1.3206309752177299,
+    1.3138846731468685, 1.3071289890273534, 1.3003632303274333, 1.2935866937335172,
+    1.286798664489786, 1.2799984157103326, 1.273185207661843, 1.2663582870146877,
+    1.2595168860601436, 1.252660221891297, 1.245787495544997, 1.2388978911020267,
+    1.2319905747424442, 1.2250646937528074, 1.2181193754817259, 1.2111537262399106,
+    1.2041668301405593, 1.1971577478755853, 1.190125515422801, 1.18306914267876,
+    1.1759876120114892, 1.1688798767268331, 1.1617448594415736, 1.1545814503558511,
+    1.147388505416733, 1.140164844363995, 1.132909248648336, 1.1256204592112935, 1.118297174115062,
+    1.1109380460092486, 1.1035416794202673, 1.0961066278476026, 1.0886313906495135,
+    1.0811144096988887, 1.0735540657878713, 1.065948674757506, 1.058296483326006,
+    1.0505956645862067, 1.04284431313937, 1.0350404398286048, 1.0271819660307508,
+    1.0192667174605288, 1.011292417434978, 1.0032566795395907, 0.9951569996299424,
+    0.9869907470938456, 0.9787551552889371, 0.9704473110588638, 0.9620641432176045,
+    0.9536024098755718, 0.9450586844625702, 0.9364293402808961, 0.927710533396234,
+    0.9188981836437341, 0.909987953490768, 0.9009752244551736, 0.8918550707267915,
+    0.8826222295789093, 0.8732710680824938, 0.863795545546826, 0.8541891710015598,
+    0.8444449549024229, 0.8345553540795179, 0.8245122087452879, 0.8143066701280635,
+    0.803929116982664, 0.7933690588331518, 0.7826150232995878, 0.7716544242167384,
+    0.7604734064220822, 0.7490566620095804, 0.7373872114258375, 0.7254461409013022,
+    0.7132122851820214, 0.7006618410975831, 0.6877678927862563, 0.674499822827435,
+    0.6608225742342045, 0.6466957148843874, 0.6320722363750231, 0.6168969899962339,
+    0.6011046177439386, 0.5846167660937205, 0.5673382570404714, 0.5491517023130249,
+    0.5299097206464933, 0.5094233295859314, 0.4874439661217523, 0.46363433677176097,
+    0.4375184021866601, 0.4083891345879979, 0.37512133285046234, 0.3357375191804553,
+    0.2861745917472551, 0.2152418959132654, 0.0,
+];
+
+static NORMAL_F: [f64; LAYERS + 1] = [
+    0.000477467764586652, 0.0012602859304985975, 0.002609072746106362, 0.0040379725933718715,
+    0.005522403299264755, 0.007050875471392109, 0.008616582769422912, 0.0102149714397311,
+    0.0118427578579431, 0.013497450601780796, 0.015177088307982065, 0.016880083152595836,
+    0.018605121275783343, 0.020351096230109344, 0.022117062707379908, 0.023902203305873237,
+    0.02570580400863265, 0.027527235669693315, 0.02936593975823011, 0.031221417192023686,
+    0.033093219458688684, 0.034980941461833046, 0.03688421568869112, 0.0388027074046569,
+    0.04073611065607874, 0.042684144916619336, 0.044646552251446515, 0.04662309490208967,
+    0.04861355321603513, 0.05061772386112175, 0.05263541827697361, 0.05466646132507786,
+    0.056710690106399425, 0.05876795292113793, 0.06083810834975175, 0.06292102443797778,
+    0.06501657797147035, 0.06712465382802392, 0.06924514439725017, 0.07137794905914183,
+    0.07352297371424082, 0.07568013035919481, 0.07784933670237201, 0.08003051581494733,
+    0.0822235958134955, 0.08442850957065445, 0.08664519445086755, 0.08887359206859394,
+    0.09111364806670041, 0.09336531191302634, 0.09562853671335306, 0.09790327903921535,
+    0.10018949876917177, 0.10248715894230599, 0.10479622562286683, 0.10711666777507266,
+    0.10944845714720981, 0.1117915681642454, 0.11414597782825504, 0.11651166562603685,
+    0.11888861344334545, 0.12127680548523516, 0.12367622820205106, 0.12608687022065,
+    0.12850872228047336, 0.13094177717412792, 0.13338602969216254, 0.13584147657175705,
+    0.138308116449064, 0.14078594981496803, 0.14327497897404687, 0.14577520800653765,
+    0.1482866427331284, 0.15080929068240986, 0.15334316106083742, 0.15588826472506426,
+    0.1584446141565199, 0.16101222343811727, 0.1635911082329826, 0.1661812857651097,
+    0.16878277480185, 0.17139559563815535, 0.17401977008249914, 0.17665532144440646,
+    0.17930227452353026, 0.18196065560021638, 0.18463049242750437, 0.18731181422451676,
+    0.19000465167119293, 0.19270903690432864, 0.1954250035148854, 0.19815258654653795,
+    0.20089182249543117, 0.20364274931112133, 0.20640540639867916, 0.20917983462193548,
+    0.21196607630785277, 0.2147641752520084, 0.21757417672517823, 0.22039612748101145,
+    0.22323007576478943, 0.22607607132326474, 0.22893416541557743, 0.23180441082524855,
+    0.2346868618732527, 0.23758157443217368, 0.24048860594144916, 0.24340801542371202,
+    0.24633986350223877, 0.2492842124195167, 0.25224112605694377, 0.2552106699556771,
+    0.25819291133864797, 0.2611879191337636, 0.2641957639983174, 0.26721651834463167,
+    0.2702502563669598, 0.27329705406967564, 0.2763569892967811, 0.27943014176276515,
+    0.28251659308484933, 0.28561642681665805, 0.28872972848335393, 0.2918565856182811,
+    0.2949970878011627, 0.29815132669790145, 0.30131939610203423, 0.30450139197789644,
+    0.307697412505554, 0.310907558127564, 0.3141319315976305, 0.3173706380312227,
+    0.3206237849582305, 0.32389148237773235, 0.32717384281495887, 0.3304709813805373,
+    0.33378301583210873, 0.33711006663841303, 0.3404522570459456, 0.3438097131482915,
+    0.34718256395825153, 0.35057094148288126, 0.35397498080156936, 0.3573948201472906,
+    0.36083060099117586, 0.36428246813054976, 0.3677505697805964, 0.37123505766982157,
+    0.37473608713949164, 0.37825381724723833, 0.3817884108750316, 0.38534003484173424,
+    0.3889088600204649, 0.39249506146101104, 0.3960988185175474, 0.399720314981932,
+    0.4033597392228692, 0.40701728433124823, 0.41069314827198344, 0.4143875340427069,
+    0.41810064983968476, 0.4218327092313535, 0.42558393133990086, 0.42935454103134185,
+    0.43314476911457434, 0.43695485254992955, 0.4407850346677702, 0.4446355653977281,
+    0.4485067015092144, 0.4523987068638829, 0.45631185268077407, 0.460246417814924,
+    0.4642026890502793, 0.46818096140782267, 0.4721815384698837, 0.4762047327216842,
+    0.48025086591125016, 0.48432026942891204, 0.4884132847077125, 0.4925302636461491,
+    0.49667156905479676, 0.5008375751284826, 0.5050286679458292, 0.5092452459981365,
+    0.5134877207497434, 0.5177565172322012, 0.5220520746747954, 0.5263748471741873,
+    0.5307253044061945, 0.5351039323830201, 0.5395112342595453, 0.5439477311926505,
+    0.5484139632579217, 0.5529104904285204, 0.5574378936214867, 0.5619967758172782,
+    0.5665877632589521, 0.5712115067380753, 0.5758686829752109, 0.5805599961036837,
+    0.5852861792663006, 0.5900479963357922, 0.5948462437709915, 0.599681752622168,
+    0.6045553907005499, 0.6094680649288958, 0.6144207238920772, 0.6194143606090396,
+    0.6244500155502747, 0.6295287799281287, 0.6346517992909606, 0.6398202774564395,
+    0.6450354808242524, 0.650298743114295, 0.6556114705832252, 0.6609751477802419,
+    0.6663913439123812, 0.6718617199007669, 0.6773880362225135, 0.6829721616487918,
+    0.6886160830085275, 0.694321916130033, 0.7000919181404905, 0.7059285013367979,
+    0.711834248882359, 0.717811932634902, 0.7238645334728822, 0.729995264565803,
+    0.7362075981312672, 0.7425052963446368, 0.7488924472237273, 0.7553735065117552,
+    0.7619533468415471, 0.7686373158033355, 0.775431304986139, 0.7823418326598627,
+    0.7893761435711993, 0.7965423304282554, 0.8038494831763903, 0.8113078743182208,
+    0.8189291916094156, 0.8267268339520951, 0.8347162929929313, 0.842915653118442,
+    0.8513462584651245, 0.8600336212030095, 0.869008688043794, 0.8783096558161477,
+    0.8879846607634008, 0.898095921906305, 0.9087264400605639, 0.9199915050483614,
+    0.9320600759689914, 0.9451989534530794, 0.9598790918124174, 0.9771017012827331, 1.0,
+];
+
+// The exponential tables, with `R = 7.69711747013104972`, `V = 0.0039496598225815571993` and
+// `f(x) = exp(-x)`
+static EXPONENTIAL_X: [f64; LAYERS + 1] = [
+    8.69711747013105, 7.69711747013105, 6.941033629377213, 6.47837849383257, 6.144164665772473,
+    5.8821443157954, 5.666410167454034, 5.4828906275260625, 5.323090505754399, 5.181487281301501,
+    5.054288489981305, 4.938777085901251, 4.832939741025113, 4.735242996601741, 4.644491885420085,
+    4.559737061707351, 4.480211746528422, 4.405287693473573, 4.334443680317273, 4.267242480277366,
+    4.203313713735184, 4.1423408656640515, 4.084051310408298, 4.028208544647937,
+    3.9746060666737884, 3.9230625001354897, 3.873417670399509, 3.8255294185223367,
+    3.779270992411668, 3.7345288940397974, 3.691201090237419, 3.6491955157608538,
+    3.6084288131289095, 3.5688252656483375, 3.530315889129344, 3.49283765477406,
+    3.4563328211327606, 3.4207483572511204, 3.386035442460302, 3.35214903090011, 3.319047470970749,
+    3.286692171599069, 3.2550473085704503, 3.2240795652862646, 3.1937579032122407,
+    3.1640533580259733, 3.134938858084441, 3.1063890623398245, 3.0783802152540907,
+    3.0508900166154556, 3.0238975044556766, 2.9973829495161306, 2.9713277599210897,
+    2.9457143948950457, 2.920526286512741, 2.895747768600142, 2.8713640120155364,
+    2.847360965635189, 2.8237253024500353, 2.8004443702507382, 2.777506146439757,
+    2.7548991965623455, 2.732612636194701, 2.710636095867929, 2.688959688741804, 2.667573980773267,
+    2.6464699631518096, 2.6256390267977885, 2.6050729387408356, 2.5847638202141408,
+    2.5647041263169053, 2.54488662711187, 2.525304390037828, 2.505950763528594, 2.48681936174021,
+    2.467904050297365, 2.4491989329782498, 2.43069833926442, 2.4123968126888706,
+    2.3942890999214583, 2.376370140536141, 2.3586350574093373, 2.341079147703035,
+    2.3236978743901964, 2.30648685828358, 2.2894418705322694, 2.272558825553155, 2.255833774367219,
+    2.2392628983129086, 2.2228425031110364, 2.2065690132576634, 2.19043896672322,
+    2.1744490099377747, 2.1585958930438855, 2.1428764653998416, 2.127287671317368,
+    2.1118265460190417, 2.0964902118017146, 2.0812758743932247, 2.0661808194905755,
+    2.051202409468585, 2.0363380802487696, 2.021585338318926, 2.006941757894518,
+    1.9924049782135764, 1.9779727009573602, 1.963642687789548, 1.9494127580071845,
+    1.9352807862970511, 1.9212447005915276, 1.907302480018387, 1.8934521529393078,
+    1.879691795072211, 1.8660195276928275, 1.852433515911175, 1.8389319670188795,
+    1.8255131289035191, 1.8121752885263902, 1.7989167704602904, 1.7857359354841253,
+    1.772631179231305, 1.7596009308890743, 1.746643651946074, 1.7337578349855711,
+    1.720942002521935, 1.7081947058780576, 1.6955145241015377, 1.6829000629175537,
+    1.670349953716452, 1.6578628525741725, 1.6454374393037234, 1.6330724165359911,
+    1.6207665088282577, 1.6085184617988582, 1.5963270412864832, 1.5841910325326887,
+    1.5721092393862295, 1.5600804835278879, 1.5481036037145133, 1.5361774550410319,
+    1.524300908219226, 1.5124728488721169, 1.5006921768428165, 1.4889578055167456,
+    1.4772686611561334, 1.4656236822457451, 1.4540218188487932, 1.4424620319720123,
+    1.4309432929388795, 1.4194645827699828, 1.4080248915695353, 1.3966232179170417,
+    1.3852585682631218, 1.3739299563284901, 1.3626364025050866, 1.351376933258335,
+    1.3401505805295046, 1.3289563811371163, 1.3177933761763245, 1.306660610415174,
+    1.2955571316866008, 1.2844819902750126, 1.2734342382962411, 1.2624129290696153,
+    1.2514171164808525, 1.2404458543344066, 1.229498195693849, 1.2185731922087903,
+    1.2076698934267613, 1.196787346088403, 1.1859245934042024, 1.1750806743109117,
+    1.1642546227056791, 1.1534454666557747, 1.1426522275816728, 1.1318739194110787,
+    1.1211095477013306, 1.1103581087274115, 1.0996185885325978, 1.0888899619385473,
+    1.0781711915113728, 1.067461226479968, 1.0567590016025519, 1.0460634359770447,
+    1.035373431790529, 1.0246878730026179, 1.0140056239570971, 1.0033255279156974,
+    0.9926464055072765, 0.9819670530850632, 0.971286240983904, 0.9606027116686671,
+    0.9499151777640766, 0.939222319955263, 0.9285227847472112, 0.917815182070045,
+    0.907098082715691, 0.8963700155898907, 0.8856294647617523, 0.8748748662910258,
+    0.8641046048110053, 0.8533170098423741, 0.8425103518103693, 0.8316828377342739,
+    0.8208326065544125, 0.8099577240574191, 0.7990561773554878, 0.7881258688694932,
+    0.7771646097591305, 0.7661701127354354, 0.7551399841819829, 0.7440717155005088,
+    0.7329626735843661, 0.7218100903087569, 0.7106110509096557, 0.6993624811032326,
+    0.6880611327737486, 0.6767035680295234, 0.6652861413926786, 0.6538049798476656,
+    0.642255960424537, 0.6306346849334911, 0.6189364513948767, 0.6071562216203009,
+    0.5952885842915036, 0.5833277127487703, 0.571267316532589, 0.5591005855115413,
+    0.5468201251633111, 0.5344178812371662, 0.5218850515921356, 0.509211982443655,
+    0.4963880455186716, 0.48340149165346225, 0.47023927508216945, 0.45688684093142073,
+    0.44332786607355296, 0.4295439402254113, 0.41551416960035703, 0.4012146788962784,
+    0.38661797794112024, 0.3716921453299179, 0.3563997602583945, 0.34069648106484984,
+    0.3245291170169101, 0.3078329546749329, 0.29052795549123117, 0.2725131854784655,
+    0.25365836338591286, 0.23379048305967556, 0.21267151063096748, 0.1899586896224328,
+    0.16512762256418836, 0.13730498094001384, 0.10483850756582022, 0.06385216381500354, 0.0,
+];
+
+static EXPONENTIAL_F: [f64; LAYERS + 1] = [
+    0.0001670666923079639, 0.00045413435384149677, 0.0009672692823271745, 0.0015362997803015724,
+    0.0021459677437189063, 0.002788798793574076, 0.003460264777836904, 0.004157295120833795,
+    0.004877655983542392, 0.005619642207205483, 0.006381905937319179, 0.007163353183634984,
+    0.00796307743801704, 0.008780314985808975, 0.00961441364250221, 0.010464810181029979,
+    0.011331013597834597, 0.012212592426255381, 0.013109164931254991, 0.014020391403181938,
+    0.014945968011691148, 0.015885621839973163, 0.016839106826039948, 0.01780620041091136,
+    0.01878670074469603, 0.019780424338009743, 0.020787204072578117, 0.02180688750428358,
+    0.02283933540638524, 0.02388442051155817, 0.024942026419731783, 0.026012046645134217,
+    0.0270943837809558, 0.028188948763978636, 0.029295660224637393, 0.030414443910466604,
+    0.03154523217289361, 0.032687963508959535, 0.03384258215087433, 0.03500903769739741,
+    0.03618728478193142, 0.03737728277295936, 0.03857899550307486, 0.039792391023374125,
+    0.04101744138041482, 0.042254122413316234, 0.04350241356888818, 0.04476229773294328,
+    0.04603376107617517, 0.04731679291318155, 0.0486113855733795, 0.04991753428270637,
+    0.05123523705512628, 0.05256449459307169, 0.05390531019604609, 0.05525768967669704,
+    0.05662164128374288, 0.05799717563120066, 0.059384305633420266, 0.06078304644547963,
+    0.062193415408540995, 0.06361543199980733, 0.06504911778675375, 0.06649449638533977,
+    0.0679515934219366, 0.06942043649872875, 0.07090105516237183, 0.07239348087570874,
+    0.07389774699236475, 0.07541388873405841, 0.0769419431704805, 0.07848194920160642,
+    0.0800339475423199, 0.08159798070923742, 0.08317409300963238, 0.08476233053236812,
+    0.08636274114075691, 0.08797537446727022, 0.08960028191003286, 0.09123751663104016,
+    0.09288713355604354, 0.09454918937605586, 0.0962237425504328, 0.0979108533114922,
+    0.09961058367063713, 0.10132299742595363, 0.10304816017125772, 0.10478613930657017,
+    0.10653700405000166, 0.1083008254510338, 0.11007767640518538, 0.1118676316700563,
+    0.11367076788274431, 0.11548716357863353, 0.11731689921155557, 0.11916005717532768,
+    0.12101672182667483, 0.12288697950954514, 0.12477091858083096, 0.12666862943751067,
+    0.12858020454522817, 0.13050573846833077, 0.13244532790138752, 0.13439907170221363,
+    0.13636707092642886, 0.1383494288635802, 0.14034625107486245, 0.1423576454324722,
+    0.14438372216063478, 0.14642459387834494, 0.1484803756438668, 0.1505511850010399,
+    0.15263714202744286, 0.15473836938446808, 0.15685499236936523, 0.1589871389693142,
+    0.16113493991759203, 0.16329852875190182, 0.165478041874936, 0.1676736186172502,
+    0.16988540130252766, 0.17211353531532006, 0.1743581691713535, 0.17661945459049488,
+    0.1788975465724783, 0.1811926034754963, 0.18350478709776746, 0.1858342627621971,
+    0.1881811994042543, 0.1905457696631954, 0.19292814997677132, 0.19532852067956322,
+    0.19774706610509887, 0.20018397469191127, 0.20263943909370902, 0.2051136562938377,
+    0.20760682772422204, 0.21011915938898826, 0.21265086199297828, 0.21520215107537868,
+    0.21777324714870053, 0.2203643758433595, 0.22297576805812017, 0.22560766011668407,
+    0.2282602939307167, 0.2309339171696274, 0.23362878343743335, 0.23634515245705964,
+    0.23908329026244918, 0.24184346939887721, 0.2446259691318921, 0.24743107566532763,
+    0.2502590823688623, 0.25311029001562946, 0.2559850070304154, 0.25888354974901623,
+    0.2618062426893629, 0.2647534188350622, 0.2677254199320448, 0.27072259679906,
+    0.27374530965280297, 0.27679392844851736, 0.27986883323697287, 0.28297041453878075,
+    0.2860990737370768, 0.28925522348967775, 0.2924392881618926, 0.2956517042812612,
+    0.2988929210155818, 0.3021634006756935, 0.30546361924459026, 0.3087940669345602,
+    0.31215524877417955, 0.31554768522712895, 0.31897191284495724, 0.3224284849560891,
+    0.3259179723935562, 0.3294409642641363, 0.332998068761809, 0.33658991402867755,
+    0.34021714906678, 0.3438804447045024, 0.347580494621637, 0.35131801643748334,
+    0.35509375286678746, 0.3589084729487498, 0.3627629733548178, 0.36665807978151416,
+    0.370594648435146, 0.37457356761590216, 0.3785957594095808, 0.38266218149600983,
+    0.38677382908413765, 0.3909317369847971, 0.39513698183329016, 0.3993906844752311,
+    0.4036940125305303, 0.4080481831520324, 0.4124544659971612, 0.4169141864330029,
+    0.4214287289976166, 0.42599954114303434, 0.43062813728845883, 0.4353161032156366,
+    0.4400651008423539, 0.4448768734145485, 0.449753251162755, 0.45469615747461545,
+    0.4597076156421377, 0.4647897562504262, 0.46994482528396, 0.4751751930373774,
+    0.4804833639304542, 0.4858719873418849, 0.49134386959403253, 0.49690198724154955,
+    0.5025495018413477, 0.5082897764106429, 0.5141263938147486, 0.5200631773682336,
+    0.5261042139836197, 0.5322538802630432, 0.5385168720028619, 0.5448982376724396,
+    0.5514034165406413, 0.5580382822625874, 0.5648091929124002, 0.5717230486648258,
+    0.578787358602845, 0.586010318477268, 0.5934009016917334, 0.6009689663652322,
+    0.608725382079622, 0.6166821809152077, 0.6248527387036659, 0.6332519942143661,
+    0.6418967164272661, 0.650805833414571, 0.6600008410789997, 0.6695063167319247,
+    0.6793505722647654, 0.689566496117078, 0.7001926550827882, 0.711274760805076,
+    0.722867659593572, 0.7350380924314235, 0.7478686219851951, 0.7614633888498963,
+    0.7759568520401156, 0.7915276369724956, 0.8084216515230084, 0.8269932966430503,
+    0.8477855006239896, 0.8717043323812036, 0.9004699299257464, 0.9381436808621746, 1.0,
+];
+
+/// Draws a sample from the standard normal distribution.
+pub fn normal<G: BitGenerator>(rng: &mut G) -> f64 {
+    let (tx, tf) = (&NORMAL_X, &NORMAL_F);
+    loop {
+        let bits = rng.next_u64();
+        let i = (bits & 0xff) as usize;
+        // A uniform number in (-1, 1) from the bits not used for the layer
+        let u = 2.0 * ((bits >> 11) as f64 * (1.0 / 9007199254740992.0)) - 1.0;
+        let x = u * tx[i];
+        if x.abs() < tx[i + 1] {
+            return x;
+        }
+        if i == 0 {
+            // Marsaglia's method for the tail beyond R
+            let r = tx[1];
+            loop {
+                let xx = -open01(rng).ln() / r;
+                let yy = -open01(rng).ln();
+                if 2.0 * yy >= xx * xx {
+                    return if u < 0.0 { -r - xx } else { r + xx };
+                }
+            }
+        }
+        if tf[i + 1] + (tf[i] - tf[i + 1]) * unit_f64(rng) < (-0.5 * x * x).exp() {
+            return x;
+        }
+    }
+}
+
+/// Draws a sample from the standard exponential distribution.
+pub fn exponential<G: BitGenerator>(rng: &mut G) -> f64 {
+    let (tx, tf) = (&EXPONENTIAL_X, &EXPONENTIAL_F);
+    loop {
+        let bits = rng.next_u64();
+        let i = (bits & 0xff) as usize;
+        let x = ((bits >> 11) as f64 * (1.0 / 9007199254740992.0)) * tx[i];
+        if x < tx[i + 1] {
+            return x;
+        }
+        if i == 0 {
+            // The tail is itself exponential, shifted by R
+            return tx[1] - open01(rng).ln();
+        }
+        if tf[i + 1] + (tf[i] - tf[i + 1]) * unit_f64(rng) < (-x).exp() {
+            return x;
+        }
+    }
+}
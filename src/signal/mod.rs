@@ -0,0 +1,208 @@
+//! Convolution and correlation.
+//!
+//! ```
+//! use numeric::Tensor;
+//! use numeric::signal::{convolve, ConvMode};
+//!
+//! let a = Tensor::new(vec![1.0, 2.0, 3.0]);
+//! let v = Tensor::new(vec![0.0, 1.0, 0.5]);
+//! assert!(convolve(&a, &v, ConvMode::Full) == Tensor::new(vec![0.0, 1.0, 2.5, 4.0, 1.5]));
+//! assert!(convolve(&a, &v, ConvMode::Same) == Tensor::new(vec![1.0, 2.5, 4.0]));
+//! ```
+
+use std::cmp::{min, max};
+use tensor::{Tensor, StridedSlice};
+use traits::NumericTrait;
+use num::complex::Complex64;
+use num::traits::{Float, cast};
+
+/// Determines the size of the output of `convolve`, `correlate` and `fftconvolve`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ConvMode {
+    /// The full convolution, of length `n + m - 1`.
+    Full,
+    /// The central part, of length `max(n, m)`.
+    Same,
+    /// Only the part where the inputs overlap completely, of length `max(n, m) - min(n, m) + 1`.
+    Valid,
+}
+
+impl ConvMode {
+    /// Returns the offset into the full output and the length of the result.
+    fn range(self, n: usize, m: usize) -> (usize, usize) {
+        let (lo, hi) = (min(n, m), max(n, m));
+        match self {
+            ConvMode::Full => (0, n + m - 1),
+            ConvMode::Same => ((lo - 1) / 2, hi),
+            ConvMode::Valid => (lo - 1, hi - lo + 1),
+        }
+    }
+}
+
+/// Determines how the input of `convolve_nd` is extended beyond its boundaries.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Boundary {
+    /// Pads with zeros.
+    Zero,
+    /// Mirrors the input around its edge, so that `[a b c]` becomes `... b a | a b c | c b ...`.
+    Reflect,
+    /// Wraps around periodically.
+    Wrap,
+    /// Repeats the edge value.
+    Nearest,
+}
+
+impl Boundary {
+    /// Maps a (possibly out-of-bounds) index into an axis of length `n`. Returns `None` if the
+    /// value is zero.
+    fn index(self, x: isize, n: usize) -> Option<usize> {
+        let n = n as isize;
+        if x >= 0 && x < n {
+            return Some(x as usize);
+        }
+        match self {
+            Boundary::Zero => None,
+            Boundary::Wrap => Some(((x % n + n) % n) as usize),
+            Boundary::Nearest => Some(if x < 0 { 0 } else { n as usize - 1 }),
+            Boundary::Reflect => {
+                let y = (x % (2 * n) + 2 * n) % (2 * n);
+                Some(if y < n { y as usize } else { (2 * n - 1 - y) as usize })
+            },
+        }
+    }
+}
+
+/// Computes the full 1-D convolution (or correlation) and returns the part given by `mode`.
+fn convolve_1d<T: NumericTrait>(a: &Tensor<T>, v: &Tensor<T>, mode: ConvMode, flip: bool)
+        -> Tensor<T> {
+    assert!(a.ndim() == 1 && v.ndim() == 1, "Inputs must be vectors (1D)");
+    assert!(a.size() > 0 && v.size() > 0, "Inputs cannot be empty");
+    let (n, m) = (a.size(), v.size());
+    let a = a.canonize();
+    let v = v.canonize();
+    let (a, v) = (a.slice(), v.slice());
+    let (offset, len) = mode.range(n, m);
+
+    let mut y = Tensor::zeros(&[len]);
+    {
+        let data = y.slice_mut();
+        for i in 0..len {
+            // Full output index k = i + offset, with y[k] = sum_j a[k - j] v[j]
+            let k = i + offset;
+            let mut s = T::zero();
+            let j0 = if k + 1 > n { k + 1 - n } else { 0 };
+            for j in j0..min(k + 1, m) {
+                let vj = if flip { v[m - 1 - j] } else { v[j] };
+                s = s + a[k - j] * vj;
+            }
+            data[i] = s;
+        }
+    }
+    y
+}
+
+/// Computes the discrete 1-D convolution of `a` and `v`, `y[k] = sum_j a[k - j] v[j]`.
+pub fn convolve<T: NumericTrait>(a: &Tensor<T>, v: &Tensor<T>, mode: ConvMode) -> Tensor<T> {
+    convolve_1d(a, v, mode, false)
+}
+
+/// Computes the discrete 1-D cross-correlation of `a` and `v`, which is the convolution of `a`
+/// with `v` reversed. In `Full` mode, `y[k] = sum_j a[k + j - (m - 1)] v[j]`, where `m` is the
+/// length of `v`.
+pub fn correlate<T: NumericTrait>(a: &Tensor<T>, v: &Tensor<T>, mode: ConvMode) -> Tensor<T> {
+    convolve_1d(a, v, mode, true)
+}
+
+/// Converts a flat index into a multi-index of a tensor with the given shape.
+fn unravel(mut p: usize, shape: &[usize], out: &mut [usize]) {
+    for d in (0..shape.len()).rev() {
+        out[d] = p % shape[d];
+        p /= shape[d];
+    }
+}
+
+/// Computes the N-D convolution of `a` and `v` using FFTs, which is much faster than `convolve`
+/// for large kernels. The inputs must have the same number of dimensions, and `mode` is applied
+/// to each axis as for `convolve`.
+pub fn fftconvolve<T>(a: &Tensor<T>, v: &Tensor<T>, mode: ConvMode) -> Tensor<T>
+        where T: NumericTrait + Float {
+    assert!(a.ndim() == v.ndim(), "Inputs must have the same number of dimensions");
+    assert!(a.size() > 0 && v.size() > 0, "Inputs cannot be empty");
+    let d = a.ndim();
+    let full: Vec<usize> = (0..d).map(|i| a.dim(i) + v.dim(i) - 1).collect();
+
+    // Zero-pad both inputs to the full size
+    let pad = |t: &Tensor<T>| {
+        let mut y: Tensor<Complex64> = Tensor::zeros(&full);
+        {
+            let data = y.slice_mut();
+            let mut idx = vec![0; d];
+            for (p, x) in t.iter().enumerate() {
+                unravel(p, t.shape(), &mut idx);
+                let q = idx.iter().zip(full.iter()).fold(0, |acc, (&i, &n)| acc * n + i);
+                data[q] = Complex64::new(cast(x).unwrap(), 0.0);
+            }
+        }
+        y
+    };
+    let fa = pad(a).fftn(::fft::FftNorm::Backward);
+    let fv = pad(v).fftn(::fft::FftNorm::Backward);
+    let y = (&fa * &fv).ifftn(::fft::FftNorm::Backward).real();
+
+    let sel: Vec<_> = (0..d).map(|i| {
+        let (offset, len) = mode.range(a.dim(i), v.dim(i));
+        StridedSlice(Some(offset as isize), Some((offset + len) as isize), 1)
+    }).collect();
+    let y = y.index(&sel);
+    let mut out = Tensor::zeros(y.shape());
+    {
+        let data = out.slice_mut();
+        for (i, x) in y.iter().enumerate() {
+            data[i] = cast(x).unwrap();
+        }
+    }
+    out
+}
+
+/// Computes the N-D convolution of `input` with `kernel`, returning a tensor of the same shape
+/// as `input`. Values outside of `input` are determined by `boundary`.
+///
+/// The kernel is centered such that `y[i] = sum_j kernel[j] input[i + (k - 1) / 2 - j]` along
+/// each axis, where `k` is the kernel length. For 1-D inputs with `Boundary::Zero`, this is the
+/// same as `convolve` in `Same` mode (as long as the kernel is not longer than the input).
+pub fn convolve_nd<T: NumericTrait>(input: &Tensor<T>, kernel: &Tensor<T>, boundary: Boundary)
+        -> Tensor<T> {
+    assert!(input.ndim() == kernel.ndim(),
+            "Input and kernel must have the same number of dimensions");
+    let d = input.ndim();
+    let shape = input.shape().clone();
+    let kshape = kernel.shape().clone();
+    let src = input.canonize();
+    let src = src.slice();
+    let kernel = kernel.canonize();
+
+    let mut y = Tensor::zeros(&shape);
+    {
+        let data = y.slice_mut();
+        let mut idx = vec![0; d];
+        let mut kidx = vec![0; d];
+        for p in 0..data.len() {
+            unravel(p, &shape, &mut idx);
+            let mut s = T::zero();
+            'kernel: for (q, &kv) in kernel.slice().iter().enumerate() {
+                unravel(q, &kshape, &mut kidx);
+                let mut r = 0;
+                for a in 0..d {
+                    let x = idx[a] as isize + ((kshape[a] as isize - 1) / 2) - kidx[a] as isize;
+                    match boundary.index(x, shape[a]) {
+                        Some(i) => r = r * shape[a] + i,
+                        None => continue 'kernel,
+                    }
+                }
+                s = s + kv * src[r];
+            }
+            data[p] = s;
+        }
+    }
+    y
+}
@@ -0,0 +1,100 @@
+use tensor::Tensor;
+use traits::NumericTrait;
+use sparse::{Compressed, CsrMatrix, CscMatrix};
+
+/// A sparse matrix in coordinate format, stored as `(row, col, value)` triplets. Duplicate
+/// entries are allowed and are summed when converting to another format.
+#[derive(Clone)]
+pub struct CooMatrix<T> {
+    shape: [usize; 2],
+    rows: Vec<usize>,
+    cols: Vec<usize>,
+    data: Vec<T>,
+}
+
+impl<T: NumericTrait> CooMatrix<T> {
+    /// Creates an empty matrix of the given shape.
+    pub fn new(shape: &[usize]) -> CooMatrix<T> {
+        CooMatrix::from_triplets(shape, Vec::new(), Vec::new(), Vec::new())
+    }
+
+    /// Creates a matrix from row indices, column indices and values.
+    pub fn from_triplets(shape: &[usize], rows: Vec<usize>, cols: Vec<usize>, data: Vec<T>)
+            -> CooMatrix<T> {
+        assert!(shape.len() == 2, "Sparse matrix must be 2D");
+        assert!(rows.len() == cols.len() && rows.len() == data.len(),
+                "Indices and data must have the same length");
+        assert!(rows.iter().all(|&i| i < shape[0]) && cols.iter().all(|&j| j < shape[1]),
+                "Index out of bounds");
+        CooMatrix { shape: [shape[0], shape[1]], rows: rows, cols: cols, data: data }
+    }
+
+    /// Creates a matrix from the non-zero elements of a dense matrix.
+    pub fn from_tensor(t: &Tensor<T>) -> CooMatrix<T> {
+        CsrMatrix::from_tensor(t).to_coo()
+    }
+
+    /// Adds the entry `v` at `(i, j)`. If there already is an entry there, they will be summed.
+    pub fn push(&mut self, i: usize, j: usize, v: T) {
+        assert!(i < self.shape[0] && j < self.shape[1], "Index out of bounds");
+        self.rows.push(i);
+        self.cols.push(j);
+        self.data.push(v);
+    }
+
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// Returns the number of stored entries (including duplicates).
+    pub fn nnz(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn row_indices(&self) -> &[usize] {
+        &self.rows
+    }
+
+    pub fn col_indices(&self) -> &[usize] {
+        &self.cols
+    }
+
+    pub fn data(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn transpose(&self) -> CooMatrix<T> {
+        CooMatrix { shape: [self.shape[1], self.shape[0]], rows: self.cols.clone(),
+                    cols: self.rows.clone(), data: self.data.clone() }
+    }
+
+    /// Multiplies all entries by `s`.
+    pub fn scale(&self, s: T) -> CooMatrix<T> {
+        CooMatrix { shape: self.shape, rows: self.rows.clone(), cols: self.cols.clone(),
+                    data: self.data.iter().map(|&v| v * s).collect() }
+    }
+
+    pub fn to_csr(&self) -> CsrMatrix<T> {
+        let c = Compressed::from_triplets(self.shape[0], self.shape[1], &self.rows, &self.cols,
+                                          &self.data);
+        CsrMatrix::from_compressed(c)
+    }
+
+    pub fn to_csc(&self) -> CscMatrix<T> {
+        let c = Compressed::from_triplets(self.shape[1], self.shape[0], &self.cols, &self.rows,
+                                          &self.data);
+        CscMatrix::from_compressed(c)
+    }
+
+    pub fn to_tensor(&self) -> Tensor<T> {
+        let mut t = Tensor::zeros(&self.shape);
+        {
+            let out = t.slice_mut();
+            for k in 0..self.data.len() {
+                let p = self.rows[k] * self.shape[1] + self.cols[k];
+                out[p] = out[p] + self.data[k];
+            }
+        }
+        t
+    }
+}
@@ -0,0 +1,110 @@
+use std::ops::{Add, Sub};
+use tensor::Tensor;
+use traits::NumericTrait;
+use sparse::{Compressed, CooMatrix, CsrMatrix, CscMatrix};
+
+// The compressed storage of a CSC matrix is the CSR storage of its transpose, which is why the
+// major and minor axes are swapped compared to `CsrMatrix`.
+impl<T: NumericTrait> CscMatrix<T> {
+    /// Creates a matrix from its raw arrays. Indices within a column may be unsorted and contain
+    /// duplicates, which are summed.
+    pub fn new(shape: &[usize], indptr: Vec<usize>, indices: Vec<usize>, data: Vec<T>)
+            -> CscMatrix<T> {
+        assert!(shape.len() == 2, "Sparse matrix must be 2D");
+        CscMatrix::from_compressed(Compressed::from_raw(shape[1], shape[0], indptr, indices, data))
+    }
+
+    /// Creates a matrix from the non-zero elements of a dense matrix.
+    pub fn from_tensor(t: &Tensor<T>) -> CscMatrix<T> {
+        CscMatrix::from_compressed(Compressed::from_dense(t, false))
+    }
+
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// Returns the number of stored entries.
+    pub fn nnz(&self) -> usize {
+        self.c.data.len()
+    }
+
+    pub fn indptr(&self) -> &[usize] {
+        &self.c.indptr
+    }
+
+    pub fn indices(&self) -> &[usize] {
+        &self.c.indices
+    }
+
+    pub fn data(&self) -> &[T] {
+        &self.c.data
+    }
+
+    /// Returns the element at `(i, j)`.
+    pub fn get(&self, i: usize, j: usize) -> T {
+        self.c.get(j, i)
+    }
+
+    pub fn to_tensor(&self) -> Tensor<T> {
+        self.c.to_dense().transpose().canonize()
+    }
+
+    pub fn to_coo(&self) -> CooMatrix<T> {
+        CooMatrix::from_triplets(self.shape(), self.c.indices.clone(), self.c.major_indices(),
+                                 self.c.data.clone())
+    }
+
+    pub fn to_csr(&self) -> CsrMatrix<T> {
+        CsrMatrix::from_compressed(self.c.transpose())
+    }
+
+    pub fn transpose(&self) -> CscMatrix<T> {
+        CscMatrix::from_compressed(self.c.transpose())
+    }
+
+    /// Multiplies all entries by `s`.
+    pub fn scale(&self, s: T) -> CscMatrix<T> {
+        CscMatrix::from_compressed(self.c.map(|v| v * s))
+    }
+
+    /// Multiplies with a dense vector or matrix, returning a dense result.
+    pub fn dot(&self, b: &Tensor<T>) -> Tensor<T> {
+        self.c.dot_dense(b, false)
+    }
+
+    /// Multiplies with another sparse matrix.
+    pub fn dot_sparse(&self, b: &CscMatrix<T>) -> CscMatrix<T> {
+        // (AB)^T = B^T A^T
+        CscMatrix::from_compressed(b.c.matmul(&self.c))
+    }
+
+    /// Returns the rows `start..end`.
+    pub fn slice_rows(&self, start: usize, end: usize) -> CscMatrix<T> {
+        CscMatrix::from_compressed(self.c.slice_minor(start, end))
+    }
+
+    /// Returns the columns `start..end`.
+    pub fn slice_cols(&self, start: usize, end: usize) -> CscMatrix<T> {
+        CscMatrix::from_compressed(self.c.slice_major(start, end))
+    }
+
+    /// Sums along `axis`, returning a dense vector.
+    pub fn sum_axis(&self, axis: usize) -> Tensor<T> {
+        assert!(axis < 2, "Reduced axis must exist");
+        if axis == 0 { self.c.sum_minor() } else { self.c.sum_major() }
+    }
+}
+
+impl<'a, 'b, T: NumericTrait> Add<&'b CscMatrix<T>> for &'a CscMatrix<T> {
+    type Output = CscMatrix<T>;
+    fn add(self, rhs: &'b CscMatrix<T>) -> CscMatrix<T> {
+        CscMatrix::from_compressed(self.c.combine(&rhs.c, |a, b| a + b))
+    }
+}
+
+impl<'a, 'b, T: NumericTrait> Sub<&'b CscMatrix<T>> for &'a CscMatrix<T> {
+    type Output = CscMatrix<T>;
+    fn sub(self, rhs: &'b CscMatrix<T>) -> CscMatrix<T> {
+        CscMatrix::from_compressed(self.c.combine(&rhs.c, |a, b| a - b))
+    }
+}
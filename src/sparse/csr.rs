@@ -0,0 +1,107 @@
+use std::ops::{Add, Sub};
+use tensor::Tensor;
+use traits::NumericTrait;
+use sparse::{Compressed, CooMatrix, CsrMatrix, CscMatrix};
+
+impl<T: NumericTrait> CsrMatrix<T> {
+    /// Creates a matrix from its raw arrays. Indices within a row may be unsorted and contain
+    /// duplicates, which are summed.
+    pub fn new(shape: &[usize], indptr: Vec<usize>, indices: Vec<usize>, data: Vec<T>)
+            -> CsrMatrix<T> {
+        assert!(shape.len() == 2, "Sparse matrix must be 2D");
+        CsrMatrix::from_compressed(Compressed::from_raw(shape[0], shape[1], indptr, indices, data))
+    }
+
+    /// Creates a matrix from the non-zero elements of a dense matrix.
+    pub fn from_tensor(t: &Tensor<T>) -> CsrMatrix<T> {
+        CsrMatrix::from_compressed(Compressed::from_dense(t, true))
+    }
+
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// Returns the number of stored entries.
+    pub fn nnz(&self) -> usize {
+        self.c.data.len()
+    }
+
+    pub fn indptr(&self) -> &[usize] {
+        &self.c.indptr
+    }
+
+    pub fn indices(&self) -> &[usize] {
+        &self.c.indices
+    }
+
+    pub fn data(&self) -> &[T] {
+        &self.c.data
+    }
+
+    /// Returns the element at `(i, j)`.
+    pub fn get(&self, i: usize, j: usize) -> T {
+        self.c.get(i, j)
+    }
+
+    pub fn to_tensor(&self) -> Tensor<T> {
+        self.c.to_dense()
+    }
+
+    pub fn to_coo(&self) -> CooMatrix<T> {
+        CooMatrix::from_triplets(self.shape(), self.c.major_indices(), self.c.indices.clone(),
+                                 self.c.data.clone())
+    }
+
+    pub fn to_csc(&self) -> CscMatrix<T> {
+        CscMatrix::from_compressed(self.c.transpose())
+    }
+
+    pub fn transpose(&self) -> CsrMatrix<T> {
+        CsrMatrix::from_compressed(self.c.transpose())
+    }
+
+    /// Multiplies all entries by `s`.
+    pub fn scale(&self, s: T) -> CsrMatrix<T> {
+        CsrMatrix::from_compressed(self.c.map(|v| v * s))
+    }
+
+    /// Multiplies with a dense vector or matrix, returning a dense result.
+    pub fn dot(&self, b: &Tensor<T>) -> Tensor<T> {
+        self.c.dot_dense(b, true)
+    }
+
+    /// Multiplies with another sparse matrix.
+    pub fn dot_sparse(&self, b: &CsrMatrix<T>) -> CsrMatrix<T> {
+        CsrMatrix::from_compressed(self.c.matmul(&b.c))
+    }
+
+    /// Returns the rows `start..end`.
+    pub fn slice_rows(&self, start: usize, end: usize) -> CsrMatrix<T> {
+        CsrMatrix::from_compressed(self.c.slice_major(start, end))
+    }
+
+    /// Returns the columns `start..end`.
+    pub fn slice_cols(&self, start: usize, end: usize) -> CsrMatrix<T> {
+        CsrMatrix::from_compressed(self.c.slice_minor(start, end))
+    }
+
+    /// Sums along `axis`, returning a dense vector.
+    pub fn sum_axis(&self, axis: usize) -> Tensor<T> {
+        assert!(axis < 2, "Reduced axis must exist");
+        if axis == 0 { self.c.sum_major() } else { self.c.sum_minor() }
+    }
+}
+
+impl<'a, 'b, T: NumericTrait> Add<&'b CsrMatrix<T>> for &'a CsrMatrix<T> {
+    type Output = CsrMatrix<T>;
+    fn add(self, rhs: &'b CsrMatrix<T>) -> CsrMatrix<T> {
+        CsrMatrix::from_compressed(self.c.combine(&rhs.c, |a, b| a + b))
+    }
+}
+
+impl<'a, 'b, T: NumericTrait> Sub<&'b CsrMatrix<T>> for &'a CsrMatrix<T> {
+    type Output = CsrMatrix<T>;
+    fn sub(self, rhs: &'b CsrMatrix<T>) -> CsrMatrix<T> {
+        CsrMatrix::from_compressed(self.c.combine(&rhs.c, |a, b| a - b))
+    }
+}
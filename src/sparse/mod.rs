@@ -0,0 +1,363 @@
+//! Sparse matrices.
+//!
+//! Three formats are provided:
+//!
+//! * `CooMatrix` (coordinate format) is a list of `(row, col, value)` triplets. It is the easiest
+//!   to assemble, since entries can be pushed in any order and duplicates are summed.
+//! * `CsrMatrix` (compressed sparse row) is efficient for row slicing and matrix-vector products.
+//! * `CscMatrix` (compressed sparse column) is efficient for column slicing.
+//!
+//! Compressed matrices always store their indices sorted and without duplicates.
+//!
+//! ```
+//! use numeric::Tensor;
+//! use numeric::sparse::CooMatrix;
+//!
+//! let mut coo = CooMatrix::new(&[3, 3]);
+//! for i in 0..3 {
+//!     coo.push(i, i, 2.0);
+//! }
+//! coo.push(0, 1, -1.0);
+//!
+//! let a = coo.to_csr();
+//! let y = a.dot(&Tensor::ones(&[3]));
+//! assert!(y == Tensor::new(vec![1.0, 2.0, 2.0]));
+//! ```
+
+use tensor::Tensor;
+use traits::NumericTrait;
+
+mod coo;
+mod csr;
+mod csc;
+
+pub use self::coo::CooMatrix;
+
+/// A sparse matrix in compressed sparse row format. The column indices and values of row `i`
+/// are stored at `indptr[i]..indptr[i + 1]` in `indices` and `data`.
+#[derive(Clone, PartialEq)]
+pub struct CsrMatrix<T> {
+    shape: [usize; 2],
+    c: Compressed<T>,
+}
+
+/// A sparse matrix in compressed sparse column format. The row indices and values of column `j`
+/// are stored at `indptr[j]..indptr[j + 1]` in `indices` and `data`.
+#[derive(Clone, PartialEq)]
+pub struct CscMatrix<T> {
+    shape: [usize; 2],
+    c: Compressed<T>,
+}
+
+impl<T> CsrMatrix<T> {
+    fn from_compressed(c: Compressed<T>) -> CsrMatrix<T> {
+        CsrMatrix { shape: [c.major, c.minor], c: c }
+    }
+}
+
+impl<T> CscMatrix<T> {
+    fn from_compressed(c: Compressed<T>) -> CscMatrix<T> {
+        CscMatrix { shape: [c.minor, c.major], c: c }
+    }
+}
+
+/// Compressed storage shared by `CsrMatrix` and `CscMatrix`. The major axis is the compressed
+/// one (rows for CSR, columns for CSC), so that the entries of major index `i` are found at
+/// `indptr[i]..indptr[i + 1]`.
+#[derive(Clone, PartialEq)]
+struct Compressed<T> {
+    major: usize,
+    minor: usize,
+    indptr: Vec<usize>,
+    indices: Vec<usize>,
+    data: Vec<T>,
+}
+
+impl<T: NumericTrait> Compressed<T> {
+    /// Builds compressed storage from triplets, sorting the indices and summing duplicates.
+    fn from_triplets(major: usize, minor: usize, maj: &[usize], min: &[usize], data: &[T])
+            -> Compressed<T> {
+        assert!(maj.len() == min.len() && maj.len() == data.len(),
+                "Indices and data must have the same length");
+
+        let mut counts = vec![0; major + 1];
+        for &i in maj {
+            assert!(i < major, "Index out of bounds");
+            counts[i + 1] += 1;
+        }
+        for i in 0..major {
+            counts[i + 1] += counts[i];
+        }
+
+        // Bucket the entries by major index
+        let mut order = vec![0; data.len()];
+        let mut next = counts.clone();
+        for (k, &i) in maj.iter().enumerate() {
+            order[next[i]] = k;
+            next[i] += 1;
+        }
+
+        let mut indptr = Vec::with_capacity(major + 1);
+        let mut indices = Vec::with_capacity(data.len());
+        let mut values = Vec::with_capacity(data.len());
+        indptr.push(0);
+        for i in 0..major {
+            let bucket = &mut order[counts[i]..counts[i + 1]];
+            bucket.sort_by_key(|&k| min[k]);
+            for &k in bucket.iter() {
+                assert!(min[k] < minor, "Index out of bounds");
+                if indices.len() > indptr[i] && indices[indices.len() - 1] == min[k] {
+                    let last = values.len() - 1;
+                    values[last] = values[last] + data[k];
+                } else {
+                    indices.push(min[k]);
+                    values.push(data[k]);
+                }
+            }
+            indptr.push(indices.len());
+        }
+        Compressed { major: major, minor: minor, indptr: indptr, indices: indices, data: values }
+    }
+
+    /// Builds compressed storage from raw arrays, which are validated and canonicalized.
+    fn from_raw(major: usize, minor: usize, indptr: Vec<usize>, indices: Vec<usize>,
+                data: Vec<T>) -> Compressed<T> {
+        assert!(indptr.len() == major + 1, "`indptr` must have one more element than the \
+                                            compressed dimension");
+        assert!(indices.len() == data.len(), "`indices` and `data` must have the same length");
+        assert!(indptr[0] == 0 && indptr[major] == data.len(), "`indptr` does not match `data`");
+
+        let mut maj = Vec::with_capacity(data.len());
+        for i in 0..major {
+            assert!(indptr[i] <= indptr[i + 1], "`indptr` must be non-decreasing");
+            for _ in indptr[i]..indptr[i + 1] {
+                maj.push(i);
+            }
+        }
+        Compressed::from_triplets(major, minor, &maj, &indices, &data)
+    }
+
+    /// Builds compressed storage from a dense matrix. If `by_rows` is false, the matrix is
+    /// compressed along its columns.
+    fn from_dense(t: &Tensor<T>, by_rows: bool) -> Compressed<T> {
+        assert!(t.ndim() == 2, "Tensor must be a matrix (2D)");
+        let t = if by_rows { t.canonize() } else { t.transpose().canonize() };
+        let (major, minor) = (t.dim(0), t.dim(1));
+        let mut indptr = Vec::with_capacity(major + 1);
+        let mut indices = Vec::new();
+        let mut data = Vec::new();
+        indptr.push(0);
+        for (k, &v) in t.slice().iter().enumerate() {
+            if v != T::zero() {
+                indices.push(k % minor);
+                data.push(v);
+            }
+            if (k + 1) % minor == 0 {
+                indptr.push(data.len());
+            }
+        }
+        // Rows are only pushed when visited, which skips them for empty minor dimensions
+        while indptr.len() < major + 1 {
+            indptr.push(data.len());
+        }
+        Compressed { major: major, minor: minor, indptr: indptr, indices: indices, data: data }
+    }
+
+    /// Returns the dense matrix, of shape `[major, minor]`.
+    fn to_dense(&self) -> Tensor<T> {
+        let mut t = Tensor::zeros(&[self.major, self.minor]);
+        {
+            let data = t.slice_mut();
+            for i in 0..self.major {
+                for p in self.indptr[i]..self.indptr[i + 1] {
+                    data[i * self.minor + self.indices[p]] = self.data[p];
+                }
+            }
+        }
+        t
+    }
+
+    /// Expands the major indices, returning one per stored entry.
+    fn major_indices(&self) -> Vec<usize> {
+        let mut maj = Vec::with_capacity(self.data.len());
+        for i in 0..self.major {
+            for _ in self.indptr[i]..self.indptr[i + 1] {
+                maj.push(i);
+            }
+        }
+        maj
+    }
+
+    /// Compresses along the other axis. For CSR storage of `A`, this gives CSR storage of `A^T`.
+    fn transpose(&self) -> Compressed<T> {
+        Compressed::from_triplets(self.minor, self.major, &self.indices, &self.major_indices(),
+                                  &self.data)
+    }
+
+    fn get(&self, i: usize, j: usize) -> T {
+        assert!(i < self.major && j < self.minor, "Index out of bounds");
+        let row = &self.indices[self.indptr[i]..self.indptr[i + 1]];
+        match row.binary_search(&j) {
+            Ok(p) => self.data[self.indptr[i] + p],
+            Err(_) => T::zero(),
+        }
+    }
+
+    fn map<F: Fn(T) -> T>(&self, f: F) -> Compressed<T> {
+        Compressed { major: self.major, minor: self.minor, indptr: self.indptr.clone(),
+                     indices: self.indices.clone(),
+                     data: self.data.iter().map(|&v| f(v)).collect() }
+    }
+
+    /// Combines two matrices of the same shape elementwise, treating missing entries as zero.
+    fn combine<F: Fn(T, T) -> T>(&self, other: &Compressed<T>, f: F) -> Compressed<T> {
+        assert!(self.major == other.major && self.minor == other.minor,
+                "Sparse matrices must have the same shape");
+        let mut indptr = Vec::with_capacity(self.major + 1);
+        let mut indices = Vec::with_capacity(self.data.len() + other.data.len());
+        let mut data = Vec::with_capacity(self.data.len() + other.data.len());
+        indptr.push(0);
+        for i in 0..self.major {
+            let (mut p, pend) = (self.indptr[i], self.indptr[i + 1]);
+            let (mut q, qend) = (other.indptr[i], other.indptr[i + 1]);
+            while p < pend || q < qend {
+                if q == qend || (p < pend && self.indices[p] < other.indices[q]) {
+                    indices.push(self.indices[p]);
+                    data.push(f(self.data[p], T::zero()));
+                    p += 1;
+                } else if p == pend || other.indices[q] < self.indices[p] {
+                    indices.push(other.indices[q]);
+                    data.push(f(T::zero(), other.data[q]));
+                    q += 1;
+                } else {
+                    indices.push(self.indices[p]);
+                    data.push(f(self.data[p], other.data[q]));
+                    p += 1;
+                    q += 1;
+                }
+            }
+            indptr.push(indices.len());
+        }
+        Compressed { major: self.major, minor: self.minor, indptr: indptr, indices: indices,
+                     data: data }
+    }
+
+    /// Sums the entries of each major index.
+    fn sum_minor(&self) -> Tensor<T> {
+        let mut s = Tensor::zeros(&[self.major]);
+        {
+            let out = s.slice_mut();
+            for i in 0..self.major {
+                for p in self.indptr[i]..self.indptr[i + 1] {
+                    out[i] = out[i] + self.data[p];
+                }
+            }
+        }
+        s
+    }
+
+    /// Sums the entries of each minor index.
+    fn sum_major(&self) -> Tensor<T> {
+        let mut s = Tensor::zeros(&[self.minor]);
+        {
+            let out = s.slice_mut();
+            for (&j, &v) in self.indices.iter().zip(self.data.iter()) {
+                out[j] = out[j] + v;
+            }
+        }
+        s
+    }
+
+    fn slice_major(&self, start: usize, end: usize) -> Compressed<T> {
+        assert!(start <= end && end <= self.major, "Slice out of bounds");
+        let (p0, p1) = (self.indptr[start], self.indptr[end]);
+        Compressed { major: end - start, minor: self.minor,
+                     indptr: self.indptr[start..end + 1].iter().map(|&p| p - p0).collect(),
+                     indices: self.indices[p0..p1].to_vec(), data: self.data[p0..p1].to_vec() }
+    }
+
+    fn slice_minor(&self, start: usize, end: usize) -> Compressed<T> {
+        assert!(start <= end && end <= self.minor, "Slice out of bounds");
+        let mut indptr = Vec::with_capacity(self.major + 1);
+        let mut indices = Vec::new();
+        let mut data = Vec::new();
+        indptr.push(0);
+        for i in 0..self.major {
+            for p in self.indptr[i]..self.indptr[i + 1] {
+                let j = self.indices[p];
+                if j >= start && j < end {
+                    indices.push(j - start);
+                    data.push(self.data[p]);
+                }
+            }
+            indptr.push(indices.len());
+        }
+        Compressed { major: self.major, minor: end - start, indptr: indptr, indices: indices,
+                     data: data }
+    }
+
+    /// Computes the product of two matrices in CSR storage (Gustavson's algorithm).
+    fn matmul(&self, other: &Compressed<T>) -> Compressed<T> {
+        assert!(self.minor == other.major, "Sparse matrices must have matching inner dimensions");
+        let n = other.minor;
+        let mut acc = vec![T::zero(); n];
+        let mut marker = vec![usize::max_value(); n];
+        let mut indptr = Vec::with_capacity(self.major + 1);
+        let mut indices = Vec::new();
+        let mut data = Vec::new();
+        indptr.push(0);
+        for i in 0..self.major {
+            let start = indices.len();
+            for p in self.indptr[i]..self.indptr[i + 1] {
+                let (k, a) = (self.indices[p], self.data[p]);
+                for q in other.indptr[k]..other.indptr[k + 1] {
+                    let j = other.indices[q];
+                    if marker[j] != i {
+                        marker[j] = i;
+                        acc[j] = T::zero();
+                        indices.push(j);
+                    }
+                    acc[j] = acc[j] + a * other.data[q];
+                }
+            }
+            indices[start..].sort();
+            for &j in &indices[start..] {
+                data.push(acc[j]);
+            }
+            indptr.push(indices.len());
+        }
+        Compressed { major: self.major, minor: n, indptr: indptr, indices: indices, data: data }
+    }
+
+    /// Multiplies with a dense vector or matrix `b`. If `by_rows` is true, the storage is treated
+    /// as `[major, minor]` (CSR), otherwise as `[minor, major]` (CSC).
+    fn dot_dense(&self, b: &Tensor<T>, by_rows: bool) -> Tensor<T> {
+        assert!(b.ndim() == 1 || b.ndim() == 2, "`b` must be a vector or a matrix");
+        let (rows, cols) = if by_rows {
+            (self.major, self.minor)
+        } else {
+            (self.minor, self.major)
+        };
+        assert!(b.dim(0) == cols, "Sparse matrix and `b` must have matching dimensions");
+
+        let k = if b.ndim() == 1 { 1 } else { b.dim(1) };
+        let b = b.canonize();
+        let x = b.slice();
+        let mut shape = b.shape().clone();
+        shape[0] = rows;
+        let mut y = Tensor::zeros(&shape);
+        {
+            let out = y.slice_mut();
+            for i in 0..self.major {
+                for p in self.indptr[i]..self.indptr[i + 1] {
+                    let (r, c) = if by_rows { (i, self.indices[p]) } else { (self.indices[p], i) };
+                    let v = self.data[p];
+                    for l in 0..k {
+                        out[r * k + l] = out[r * k + l] + v * x[c * k + l];
+                    }
+                }
+            }
+        }
+        y
+    }
+}
@@ -0,0 +1,85 @@
+//! Special functions, applied elementwise. The computations are carried out in `f64`, so `f32`
+//! tensors get fully accurate results.
+//!
+//! ```
+//! use numeric::Tensor;
+//! use numeric::special;
+//!
+//! let x = Tensor::new(vec![0.5_f64, 1.0, 5.0]);
+//! let y = special::gamma(x);
+//! assert!((y[(2,)] - 24.0).abs() < 1e-12);
+//! ```
+//!
+//! The scalar versions are available in `special::scalar`.
+
+use num::traits::{Float, cast};
+use tensor::Tensor;
+use traits::NumericTrait;
+
+pub mod scalar;
+
+macro_rules! add_impl {
+    ($($f:ident)*) => ($(
+        pub fn $f<T: NumericTrait + Float>(x: Tensor<T>) -> Tensor<T> {
+            let mut y = x;
+            y.canonize_inplace();
+            {
+                let n = y.size();
+                let mut data = y.slice_mut();
+                for i in 0..n {
+                    data[i] = cast(scalar::$f(cast(data[i]).unwrap())).unwrap();
+                }
+            }
+            y
+        }
+    )*)
+}
+
+add_impl! { gamma lgamma digamma erf erfc erfinv j0 j1 y0 y1 i0 k0 expit logit sinc }
+
+macro_rules! add_binary_impl {
+    ($($f:ident)*) => ($(
+        pub fn $f<T: NumericTrait + Float>(a: &Tensor<T>, x: &Tensor<T>) -> Tensor<T> {
+            assert!(a.shape() == x.shape(), "Shapes must match");
+            let mut z = Tensor::empty(&x.shape());
+            {
+                let mut data = z.slice_mut();
+                for (i, (v1, v2)) in a.iter().zip(x.iter()).enumerate() {
+                    data[i] = cast(scalar::$f(cast(v1).unwrap(), cast(v2).unwrap())).unwrap();
+                }
+            }
+            z
+        }
+    )*)
+}
+
+add_binary_impl! { beta gammainc gammaincc xlogy }
+
+/// The regularized incomplete beta function `I_x(a, b)`.
+pub fn betainc<T: NumericTrait + Float>(a: &Tensor<T>, b: &Tensor<T>, x: &Tensor<T>)
+        -> Tensor<T> {
+    assert!(a.shape() == x.shape() && b.shape() == x.shape(), "Shapes must match");
+    let mut z = Tensor::empty(&x.shape());
+    {
+        let mut data = z.slice_mut();
+        for (i, ((v1, v2), v3)) in a.iter().zip(b.iter()).zip(x.iter()).enumerate() {
+            data[i] = cast(scalar::betainc(cast(v1).unwrap(), cast(v2).unwrap(),
+                                           cast(v3).unwrap())).unwrap();
+        }
+    }
+    z
+}
+
+/// The Bessel function of the first kind of integer order `n`.
+pub fn jn<T: NumericTrait + Float>(n: i32, x: Tensor<T>) -> Tensor<T> {
+    let mut y = x;
+    y.canonize_inplace();
+    {
+        let len = y.size();
+        let mut data = y.slice_mut();
+        for i in 0..len {
+            data[i] = cast(scalar::jn(n, cast(data[i]).unwrap())).unwrap();
+        }
+    }
+    y
+}
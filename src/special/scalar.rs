@@ -0,0 +1,554 @@
+//! Scalar versions of the special functions, operating on `f64`.
+
+use std::f64;
+use std::f64::consts::{PI, FRAC_2_SQRT_PI};
+
+/// The Euler-Mascheroni constant.
+const EULER: f64 = 0.57721566490153286061;
+
+/// ln(sqrt(2 pi))
+const LN_SQRT_2PI: f64 = 0.91893853320467274178;
+
+/// Coefficients of the Lanczos approximation with g = 7.
+const LANCZOS_G: f64 = 7.0;
+const LANCZOS: [f64; 9] = [0.99999999999980993, 676.5203681218851, -1259.1392167224028,
+                           771.32342877765313, -176.61502916214059, 12.507343278686905,
+                           -0.13857109526572012, 9.9843695780195716e-6, 1.5056327351493116e-7];
+
+/// Relative tolerance and iteration limit of the series and continued fractions.
+const EPS: f64 = 1e-16;
+const MAX_ITER: usize = 1000;
+
+/// Evaluates the Lanczos series at `x - 1`, for `x >= 0.5`.
+fn lanczos_sum(x: f64) -> f64 {
+    let x = x - 1.0;
+    let mut s = LANCZOS[0];
+    for (k, c) in LANCZOS.iter().enumerate().skip(1) {
+        s += c / (x + k as f64);
+    }
+    s
+}
+
+/// The gamma function.
+pub fn gamma(x: f64) -> f64 {
+    if x.is_nan() || (x <= 0.0 && x == x.floor()) {
+        return f64::NAN;
+    }
+    if x < 0.5 {
+        // Reflection formula
+        return PI / ((PI * x).sin() * gamma(1.0 - x));
+    }
+    if x > 171.7 {
+        return f64::INFINITY;
+    }
+    let t = x - 0.5 + LANCZOS_G;
+    // Split the power in two so that it does not overflow before it is multiplied by exp(-t)
+    let p = t.powf(0.5 * (x - 0.5));
+    (2.0 * PI).sqrt() * p * (-t).exp() * p * lanczos_sum(x)
+}
+
+/// The natural logarithm of the absolute value of the gamma function.
+pub fn lgamma(x: f64) -> f64 {
+    if x.is_nan() {
+        return f64::NAN;
+    }
+    if x <= 0.0 && x == x.floor() {
+        return f64::INFINITY;
+    }
+    if x < 0.5 {
+        return (PI / (PI * x).sin().abs()).ln() - lgamma(1.0 - x);
+    }
+    if x == 1.0 || x == 2.0 {
+        return 0.0;
+    }
+    let t = x - 0.5 + LANCZOS_G;
+    LN_SQRT_2PI + (x - 0.5) * t.ln() - t + lanczos_sum(x).ln()
+}
+
+/// The digamma function, the logarithmic derivative of the gamma function.
+pub fn digamma(x: f64) -> f64 {
+    if x.is_nan() || (x <= 0.0 && x == x.floor()) {
+        return f64::NAN;
+    }
+    if x < 0.0 {
+        return digamma(1.0 - x) - PI / (PI * x).tan();
+    }
+    // Use the recurrence psi(x) = psi(x + 1) - 1/x until the asymptotic series is accurate
+    let mut x = x;
+    let mut s = 0.0;
+    while x < 10.0 {
+        s -= 1.0 / x;
+        x += 1.0;
+    }
+    let z = 1.0 / (x * x);
+    let series = z * (1.0 / 12.0 - z * (1.0 / 120.0 - z * (1.0 / 252.0 - z * (1.0 / 240.0 -
+                 z * (1.0 / 132.0 - z * (691.0 / 32760.0 - z / 12.0))))));
+    s + x.ln() - 0.5 / x - series
+}
+
+/// The beta function, `gamma(a) gamma(b) / gamma(a + b)`.
+pub fn beta(a: f64, b: f64) -> f64 {
+    if a > 0.0 && b > 0.0 && a + b > 100.0 {
+        (lgamma(a) + lgamma(b) - lgamma(a + b)).exp()
+    } else {
+        gamma(a) * gamma(b) / gamma(a + b)
+    }
+}
+
+/// Evaluates the power series of the regularized lower incomplete gamma function.
+fn gammainc_series(a: f64, x: f64) -> f64 {
+    let mut term = 1.0 / a;
+    let mut sum = term;
+    let mut n = a;
+    for _ in 0..MAX_ITER {
+        n += 1.0;
+        term *= x / n;
+        sum += term;
+        if term.abs() < sum.abs() * EPS {
+            break;
+        }
+    }
+    sum * (-x + a * x.ln() - lgamma(a)).exp()
+}
+
+/// Evaluates the continued fraction of the regularized upper incomplete gamma function, using
+/// the modified Lentz method.
+fn gammaincc_fraction(a: f64, x: f64) -> f64 {
+    let tiny = 1e-300;
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / tiny;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for i in 1..MAX_ITER {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.0;
+        d = an * d + b;
+        if d.abs() < tiny {
+            d = tiny;
+        }
+        c = b + an / c;
+        if c.abs() < tiny {
+            c = tiny;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < EPS {
+            break;
+        }
+    }
+    h * (-x + a * x.ln() - lgamma(a)).exp()
+}
+
+/// The regularized lower incomplete gamma function `P(a, x)`, for `a > 0` and `x >= 0`.
+pub fn gammainc(a: f64, x: f64) -> f64 {
+    if !(a > 0.0) || !(x >= 0.0) {
+        return f64::NAN;
+    }
+    if x == 0.0 {
+        0.0
+    } else if x < a + 1.0 {
+        gammainc_series(a, x)
+    } else {
+        1.0 - gammaincc_fraction(a, x)
+    }
+}
+
+/// The regularized upper incomplete gamma function `Q(a, x) = 1 - P(a, x)`.
+pub fn gammaincc(a: f64, x: f64) -> f64 {
+    if !(a > 0.0) || !(x >= 0.0) {
+        return f64::NAN;
+    }
+    if x == f64::INFINITY {
+        0.0
+    } else if x < a + 1.0 {
+        1.0 - gammainc_series(a, x)
+    } else {
+        gammaincc_fraction(a, x)
+    }
+}
+
+/// Evaluates the continued fraction of the incomplete beta function (modified Lentz).
+fn betainc_fraction(a: f64, b: f64, x: f64) -> f64 {
+    let tiny = 1e-300;
+    let (qab, qap, qam) = (a + b, a + 1.0, a - 1.0);
+    let mut c = 1.0;
+    let mut d = 1.0 - qab * x / qap;
+    if d.abs() < tiny {
+        d = tiny;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+    for m in 1..MAX_ITER {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+        // Even step
+        let aa = m * (b - m) * x / ((qam + m2) * (a + m2));
+        d = 1.0 + aa * d;
+        if d.abs() < tiny {
+            d = tiny;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < tiny {
+            c = tiny;
+        }
+        d = 1.0 / d;
+        h *= d * c;
+        // Odd step
+        let aa = -(a + m) * (qab + m) * x / ((a + m2) * (qap + m2));
+        d = 1.0 + aa * d;
+        if d.abs() < tiny {
+            d = tiny;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < tiny {
+            c = tiny;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < EPS {
+            break;
+        }
+    }
+    h
+}
+
+/// The regularized incomplete beta function `I_x(a, b)`, for `a, b > 0` and `0 <= x <= 1`.
+pub fn betainc(a: f64, b: f64, x: f64) -> f64 {
+    if !(a > 0.0) || !(b > 0.0) || !(x >= 0.0 && x <= 1.0) {
+        return f64::NAN;
+    }
+    if x == 0.0 || x == 1.0 {
+        return x;
+    }
+    let front = (lgamma(a + b) - lgamma(a) - lgamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    // The continued fraction converges quickly below this point; use symmetry above it
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * betainc_fraction(a, b, x) / a
+    } else {
+        1.0 - front * betainc_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+/// The error function.
+pub fn erf(x: f64) -> f64 {
+    if x.is_nan() {
+        f64::NAN
+    } else if x.abs() < 2.0 {
+        // Series erf(x) = 2/sqrt(pi) exp(-x^2) sum_n 2^n x^(2n+1) / (1 3 ... (2n+1)), which has
+        // no cancellation
+        let x2 = x * x;
+        let mut term = x;
+        let mut sum = x;
+        let mut n = 0.0;
+        for _ in 0..MAX_ITER {
+            n += 1.0;
+            term *= 2.0 * x2 / (2.0 * n + 1.0);
+            sum += term;
+            if term.abs() < sum.abs() * EPS {
+                break;
+            }
+        }
+        FRAC_2_SQRT_PI * (-x2).exp() * sum
+    } else if x > 0.0 {
+        1.0 - erfc(x)
+    } else {
+        erfc(-x) - 1.0
+    }
+}
+
+/// The complementary error function `1 - erf(x)`, accurate also for large `x`.
+pub fn erfc(x: f64) -> f64 {
+    if x.is_nan() {
+        f64::NAN
+    } else if x < 0.5 {
+        1.0 - erf(x)
+    } else if x > 27.3 {
+        0.0
+    } else {
+        gammaincc(0.5, x * x)
+    }
+}
+
+/// The inverse error function, defined on `[-1, 1]`.
+pub fn erfinv(y: f64) -> f64 {
+    if !(y >= -1.0 && y <= 1.0) {
+        return f64::NAN;
+    }
+    if y == 1.0 {
+        return f64::INFINITY;
+    } else if y == -1.0 {
+        return f64::NEG_INFINITY;
+    } else if y == 0.0 {
+        return 0.0;
+    }
+    // Initial approximation by Winitzki, refined by Halley's method
+    let a = 0.147;
+    let l = (1.0 - y * y).ln();
+    let t = 2.0 / (PI * a) + 0.5 * l;
+    let mut x = ((t * t - l / a).sqrt() - t).sqrt();
+    if y < 0.0 {
+        x = -x;
+    }
+    for _ in 0..3 {
+        let f = erf(x) - y;
+        let df = FRAC_2_SQRT_PI * (-x * x).exp();
+        x -= f / (df + x * f);
+    }
+    x
+}
+
+/// Returns the Hankel asymptotic expansion `(P, Q)` of the Bessel functions of order `nu`.
+fn hankel_pq(nu: f64, x: f64) -> (f64, f64) {
+    let mu = 4.0 * nu * nu;
+    let (mut p, mut q) = (1.0, 0.0);
+    let mut term = 1.0;
+    let mut last = f64::INFINITY;
+    for k in 1..60 {
+        let kf = k as f64;
+        term *= (mu - (2.0 * kf - 1.0).powi(2)) / (kf * 8.0 * x);
+        if term.abs() > last || term.abs() < EPS {
+            break;
+        }
+        last = term.abs();
+        // Terms alternate between Q and P, with signs + - - + + - - ...
+        match k % 4 {
+            1 => q += term,
+            2 => p -= term,
+            3 => q -= term,
+            _ => p += term,
+        }
+    }
+    (p, q)
+}
+
+/// Evaluates `J_nu(x)` and `Y_nu(x)` for `nu` 0 or 1 and large `x`.
+fn bessel_asymptotic(nu: f64, x: f64) -> (f64, f64) {
+    let (p, q) = hankel_pq(nu, x);
+    let chi = x - (0.5 * nu + 0.25) * PI;
+    let (s, c) = chi.sin_cos();
+    let scale = (2.0 / (PI * x)).sqrt();
+    (scale * (p * c - q * s), scale * (p * s + q * c))
+}
+
+/// Above this argument, the asymptotic expansion is used for `J_0`, `J_1`, `Y_0` and `Y_1`.
+const BESSEL_ASYMPTOTIC: f64 = 25.0;
+
+/// Computes `J_0(x), ..., J_n(x)` for `x > 0` with Miller's backward recurrence, normalized with
+/// `J_0 + 2 J_2 + 2 J_4 + ... = 1`. At least `n + 1` values are returned.
+fn bessel_j_miller(n: usize, x: f64) -> Vec<f64> {
+    let top = ::std::cmp::max(n, x as usize) + 20 + (40.0 * (n as f64).max(x)).sqrt() as usize;
+    let top = top + top % 2;
+    let mut j = vec![0.0; top + 2];
+    j[top] = 1e-300;
+    let mut norm = 0.0;
+    for k in (1..top + 1).rev() {
+        j[k - 1] = 2.0 * k as f64 / x * j[k] - j[k + 1];
+        // Rescale to avoid overflow
+        if j[k - 1].abs() > 1e250 {
+            for v in j[k - 1..].iter_mut() {
+                *v *= 1e-250;
+            }
+            norm *= 1e-250;
+        }
+        if (k - 1) % 2 == 0 && k > 1 {
+            norm += 2.0 * j[k - 1];
+        }
+    }
+    norm += j[0];
+    j.truncate(::std::cmp::max(n + 1, 3));
+    for v in j.iter_mut() {
+        *v /= norm;
+    }
+    j
+}
+
+/// The Bessel function of the first kind of order 0.
+pub fn j0(x: f64) -> f64 {
+    let x = x.abs();
+    if x.is_nan() {
+        f64::NAN
+    } else if x == 0.0 {
+        1.0
+    } else if x > BESSEL_ASYMPTOTIC {
+        bessel_asymptotic(0.0, x).0
+    } else {
+        bessel_j_miller(0, x)[0]
+    }
+}
+
+/// The Bessel function of the first kind of order 1.
+pub fn j1(x: f64) -> f64 {
+    let sign = if x < 0.0 { -1.0 } else { 1.0 };
+    let x = x.abs();
+    if x.is_nan() {
+        f64::NAN
+    } else if x == 0.0 {
+        0.0
+    } else if x > BESSEL_ASYMPTOTIC {
+        sign * bessel_asymptotic(1.0, x).0
+    } else {
+        sign * bessel_j_miller(1, x)[1]
+    }
+}
+
+/// The Bessel function of the first kind of integer order `n`.
+pub fn jn(n: i32, x: f64) -> f64 {
+    // J_{-n} = (-1)^n J_n and J_n(-x) = (-1)^n J_n(x)
+    let mut sign = 1.0;
+    if n < 0 && n % 2 != 0 {
+        sign = -sign;
+    }
+    if x < 0.0 && n % 2 != 0 {
+        sign = -sign;
+    }
+    let n = n.abs() as usize;
+    let x = x.abs();
+    if x.is_nan() {
+        return f64::NAN;
+    }
+    let value = if n == 0 {
+        j0(x)
+    } else if n == 1 {
+        j1(x)
+    } else if x == 0.0 {
+        0.0
+    } else if (n as f64) < x {
+        // Forward recurrence is stable when the order is below the argument
+        let (mut a, mut b) = (j0(x), j1(x));
+        for k in 1..n {
+            let c = 2.0 * k as f64 / x * b - a;
+            a = b;
+            b = c;
+        }
+        b
+    } else {
+        bessel_j_miller(n, x)[n]
+    };
+    sign * value
+}
+
+/// The Bessel function of the second kind of order 0, for `x > 0`.
+pub fn y0(x: f64) -> f64 {
+    if x.is_nan() || x < 0.0 {
+        f64::NAN
+    } else if x == 0.0 {
+        f64::NEG_INFINITY
+    } else if x > BESSEL_ASYMPTOTIC {
+        bessel_asymptotic(0.0, x).1
+    } else {
+        // Neumann series Y_0 = 2/pi (ln(x/2) + gamma) J_0 - 4/pi sum_k (-1)^k J_2k / k
+        let j = bessel_j_miller(40 + x as usize, x);
+        let mut s = 0.0;
+        let mut k = 1;
+        while 2 * k < j.len() {
+            let term = j[2 * k] / k as f64;
+            s += if k % 2 == 0 { term } else { -term };
+            k += 1;
+        }
+        2.0 / PI * ((0.5 * x).ln() + EULER) * j[0] - 4.0 / PI * s
+    }
+}
+
+/// The Bessel function of the second kind of order 1, for `x > 0`.
+pub fn y1(x: f64) -> f64 {
+    if x.is_nan() || x < 0.0 {
+        f64::NAN
+    } else if x == 0.0 {
+        f64::NEG_INFINITY
+    } else if x > BESSEL_ASYMPTOTIC {
+        bessel_asymptotic(1.0, x).1
+    } else {
+        // Derivative of the Neumann series of Y_0, using J_n' = (J_{n-1} - J_{n+1}) / 2
+        let j = bessel_j_miller(41 + x as usize, x);
+        let mut s = 0.0;
+        let mut k = 1;
+        while 2 * k + 1 < j.len() {
+            let term = (j[2 * k - 1] - j[2 * k + 1]) / k as f64;
+            s += if k % 2 == 0 { term } else { -term };
+            k += 1;
+        }
+        -2.0 / (PI * x) * j[0] + 2.0 / PI * ((0.5 * x).ln() + EULER) * j[1] + 2.0 / PI * s
+    }
+}
+
+/// The modified Bessel function of the first kind of order 0.
+pub fn i0(x: f64) -> f64 {
+    if x.is_nan() {
+        return f64::NAN;
+    }
+    // The power series has only positive terms, so it is accurate for all x
+    let q = 0.25 * x * x;
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    let mut k = 0.0;
+    for _ in 0..MAX_ITER {
+        k += 1.0;
+        term *= q / (k * k);
+        sum += term;
+        if term < sum * EPS {
+            break;
+        }
+    }
+    sum
+}
+
+/// The modified Bessel function of the second kind of order 0, for `x > 0`.
+pub fn k0(x: f64) -> f64 {
+    if x.is_nan() || x < 0.0 {
+        f64::NAN
+    } else if x == 0.0 {
+        f64::INFINITY
+    } else {
+        // K_0(x) = exp(-x) int_0^inf exp(-x (cosh t - 1)) dt. The integrand decays double
+        // exponentially, so the trapezoidal rule converges exponentially fast in the step size,
+        // as long as the step shrinks like 1/sqrt(x).
+        let h = (0.5 / x.sqrt()).min(0.125);
+        let mut sum = 0.5;
+        let mut k = 1.0;
+        loop {
+            let f = (-x * ((k * h).cosh() - 1.0)).exp();
+            sum += f;
+            if f < sum * EPS {
+                break;
+            }
+            k += 1.0;
+        }
+        h * sum * (-x).exp()
+    }
+}
+
+/// The logistic sigmoid `1 / (1 + exp(-x))`.
+pub fn expit(x: f64) -> f64 {
+    if x >= 0.0 {
+        1.0 / (1.0 + (-x).exp())
+    } else {
+        let e = x.exp();
+        e / (1.0 + e)
+    }
+}
+
+/// The inverse of `expit`, `ln(p / (1 - p))`.
+pub fn logit(p: f64) -> f64 {
+    (p / (1.0 - p)).ln()
+}
+
+/// Computes `x ln(y)`, which is defined to be zero if `x` is zero.
+pub fn xlogy(x: f64, y: f64) -> f64 {
+    if x == 0.0 && !y.is_nan() {
+        0.0
+    } else {
+        x * y.ln()
+    }
+}
+
+/// The normalized sinc function `sin(pi x) / (pi x)`.
+pub fn sinc(x: f64) -> f64 {
+    if x == 0.0 {
+        1.0
+    } else {
+        let y = PI * x;
+        y.sin() / y
+    }
+}
@@ -0,0 +1,98 @@
+use tensor::Tensor;
+use num::complex::Complex;
+use traits::TensorTrait;
+use math;
+
+/// Applies `f` elementwise, returning a new canonical tensor.
+fn map<T: TensorTrait, D: TensorTrait, F: Fn(T) -> D>(t: &Tensor<T>, f: F) -> Tensor<D> {
+    let mut y = Tensor::empty(&t.shape);
+    {
+        let mut data = y.slice_mut();
+        for (i, v) in t.iter().enumerate() {
+            data[i] = f(v);
+        }
+    }
+    y
+}
+
+macro_rules! add_impl {
+    ($t:ty) => (
+        impl Tensor<$t> {
+            /// Converts to a complex tensor with zero imaginary part.
+            pub fn to_complex(&self) -> Tensor<Complex<$t>> {
+                map(self, |v| Complex::new(v, 0.0))
+            }
+        }
+
+        impl Tensor<Complex<$t>> {
+            /// Creates a complex tensor from its real and imaginary parts.
+            pub fn from_parts(re: &Tensor<$t>, im: &Tensor<$t>) -> Tensor<Complex<$t>> {
+                assert!(re.shape() == im.shape(), "Shapes must match");
+                let mut y = Tensor::empty(&re.shape);
+                {
+                    let mut data = y.slice_mut();
+                    for (i, (a, b)) in re.iter().zip(im.iter()).enumerate() {
+                        data[i] = Complex::new(a, b);
+                    }
+                }
+                y
+            }
+
+            /// Creates a complex tensor from magnitudes `r` and phase angles `theta`.
+            pub fn from_polar(r: &Tensor<$t>, theta: &Tensor<$t>) -> Tensor<Complex<$t>> {
+                assert!(r.shape() == theta.shape(), "Shapes must match");
+                let mut y = Tensor::empty(&r.shape);
+                {
+                    let mut data = y.slice_mut();
+                    for (i, (a, b)) in r.iter().zip(theta.iter()).enumerate() {
+                        data[i] = Complex::from_polar(&a, &b);
+                    }
+                }
+                y
+            }
+
+            /// Returns the complex conjugate.
+            pub fn conj(&self) -> Tensor<Complex<$t>> {
+                map(self, |v| v.conj())
+            }
+
+            /// Returns the real part.
+            pub fn real(&self) -> Tensor<$t> {
+                map(self, |v| v.re)
+            }
+
+            /// Returns the imaginary part.
+            pub fn imag(&self) -> Tensor<$t> {
+                map(self, |v| v.im)
+            }
+
+            /// Returns the magnitude (modulus).
+            pub fn abs(&self) -> Tensor<$t> {
+                map(self, |v| v.norm())
+            }
+
+            /// Returns the phase angle in radians, in the range `(-pi, pi]`.
+            pub fn angle(&self) -> Tensor<$t> {
+                map(self, |v| v.arg())
+            }
+
+            /// Computes `e^z` elementwise.
+            pub fn exp(&self) -> Tensor<Complex<$t>> {
+                math::complex::exp(self.clone())
+            }
+
+            /// Computes the principal natural logarithm elementwise.
+            pub fn ln(&self) -> Tensor<Complex<$t>> {
+                math::complex::ln(self.clone())
+            }
+
+            /// Computes the principal square root elementwise.
+            pub fn sqrt(&self) -> Tensor<Complex<$t>> {
+                math::complex::sqrt(self.clone())
+            }
+        }
+    )
+}
+
+add_impl!(f32);
+add_impl!(f64);
@@ -1,8 +1,9 @@
 use tensor::Tensor;
+use num::complex::{Complex32, Complex64};
 use blas;
 
 macro_rules! add_impl {
-    ($t:ty, $gemv:ident, $gemm:ident, $dot:ident) => (
+    ($t:ty, $gemv:ident, $gemm:ident, $dot:path, $zero:expr, $one:expr) => (
         impl Tensor<$t> {
             /// Takes the product of two tensors. If the tensors are both matrices (2D), then a
             /// matrix multiplication is taken. If the tensors are both vectors (1D), the scalar
@@ -16,17 +17,17 @@ macro_rules! add_impl {
                         if cfg!(noblas) {
                             // Naive implementation, BLAS will be much faster
                             for i in 0..self.shape[0] {
-                                let mut v = 0.0;
+                                let mut v = $zero;
                                 for k in 0..self.shape[1] {
-                                    v += self.get2(i, k) * rhs.data[k];
+                                    v = v + self.get2(i, k) * rhs.data[k];
                                 }
                                 data[i] = v;
                             }
                         } else {
                             let t1 = self.canonize();
                             let t2 = rhs.canonize();
-                            blas::$gemv(b'T', t1.shape[1], t1.shape[0], 1.0, &t1.data,
-                                        t1.shape[1], &t2.data, 1, 0.0, data, 1);
+                            blas::$gemv(b'T', t1.shape[1], t1.shape[0], $one, &t1.data,
+                                        t1.shape[1], &t2.data, 1, $zero, data, 1);
                         }
                     }
                     t3
@@ -38,17 +39,17 @@ macro_rules! add_impl {
                         if cfg!(noblas) {
                             // Naive implementation, BLAS will be much faster
                             for i in 0..rhs.shape[1] {
-                                let mut v = 0.0;
+                                let mut v = $zero;
                                 for k in 0..rhs.shape[0] {
-                                    v += self.data[k] * rhs.get2(k, i);
+                                    v = v + self.data[k] * rhs.get2(k, i);
                                 }
                                 data[i] = v;
                             }
                         } else {
                             let t1 = self.canonize();
                             let t2 = rhs.canonize();
-                            blas::$gemv(b'N', t2.shape[1], t2.shape[0], 1.0, &t2.data,
-                                        t2.shape[1], &t1.data, 1, 0.0, data, 1);
+                            blas::$gemv(b'N', t2.shape[1], t2.shape[0], $one, &t2.data,
+                                        t2.shape[1], &t1.data, 1, $zero, data, 1);
                         }
                     }
                     t3
@@ -59,9 +60,9 @@ macro_rules! add_impl {
                         // Naive implementation, BLAS will be much faster
                         for i in 0..self.shape[0] {
                             for j in 0..rhs.shape[1] {
-                                let mut v = 0.0;
+                                let mut v = $zero;
                                 for k in 0..self.shape[1] {
-                                    v += self.get2(i, k) * rhs.get2(k, j);
+                                    v = v + self.get2(i, k) * rhs.get2(k, j);
                                 }
                                 t3.set2(i, j, v);
                             }
@@ -72,23 +73,23 @@ macro_rules! add_impl {
                         let t1 = self.canonize();
                         let t2 = rhs.canonize();
                         let mut data = t3.slice_mut();
-                        blas::$gemm(b'N', b'N', t2.shape[1], t1.shape[0], t2.shape[0], 1.0,
-                                    &t2.data, t2.shape[1], &t1.data, t2.shape[0], 0.0,
+                        blas::$gemm(b'N', b'N', t2.shape[1], t1.shape[0], t2.shape[0], $one,
+                                    &t2.data, t2.shape[1], &t1.data, t2.shape[0], $zero,
                                     data, t2.shape[1]);
                     }
                     t3
                 } else if self.ndim() == 1 && rhs.ndim() == 1 { // scalar product
                     assert_eq!(self.size(), rhs.size());
-                    let mut v = 0.0;
+                    let mut v = $zero;
                     if cfg!(noblas) {
                         // Naive implementation, BLAS will be much faster
                         for (v1, v2) in self.iter().zip(rhs.iter()) {
-                            v += v1 * v2;
+                            v = v + v1 * v2;
                         }
                     } else {
                         let t1 = self.canonize();
                         let t2 = rhs.canonize();
-                        v = blas::$dot(t1.size(), &t1.data, 1, &t2.data, 1);
+                        v = $dot(t1.size(), &t1.data, 1, &t2.data, 1);
                     }
                     Tensor::scalar(v)
                 } else {
@@ -99,5 +100,23 @@ macro_rules! add_impl {
     )
 }
 
-add_impl!(f32, sgemv, sgemm, sdot);
-add_impl!(f64, dgemv, dgemm, ddot);
+// Unconjugated scalar product of complex vectors
+macro_rules! add_dotu {
+    ($name:ident, $t:ty) => (
+        fn $name(n: usize, x: &[$t], incx: usize, y: &[$t], incy: usize) -> $t {
+            let mut v = <$t>::new(0.0, 0.0);
+            for i in 0..n {
+                v = v + x[i * incx] * y[i * incy];
+            }
+            v
+        }
+    )
+}
+
+add_dotu!(cdotu, Complex32);
+add_dotu!(zdotu, Complex64);
+
+add_impl!(f32, sgemv, sgemm, blas::sdot, 0.0, 1.0);
+add_impl!(f64, dgemv, dgemm, blas::ddot, 0.0, 1.0);
+add_impl!(Complex32, cgemv, cgemm, cdotu, Complex32::new(0.0, 0.0), Complex32::new(1.0, 0.0));
+add_impl!(Complex64, zgemv, zgemm, zdotu, Complex64::new(0.0, 0.0), Complex64::new(1.0, 0.0));
@@ -132,6 +132,7 @@ mod indexing;
 mod concat;
 mod convert;
 mod binary;
+mod complex;
 
 use num::traits::{Num, NumCast};
 
@@ -0,0 +1,153 @@
+use numeric::Tensor;
+use numeric::math;
+use num::complex::Complex64;
+use std::f64::consts::PI;
+
+type C = Complex64;
+type T = Tensor<C>;
+
+fn c(re: f64, im: f64) -> C {
+    C::new(re, im)
+}
+
+fn assert_close(a: &T, b: &T) {
+    assert!(a.shape() == b.shape());
+    assert!((a - b).abs().max() < 1e-8);
+}
+
+/// Conjugate transpose
+fn herm(a: &T) -> T {
+    a.transpose().conj()
+}
+
+fn mat() -> T {
+    T::new(vec![c(1.0, 2.0), c(0.5, -1.0), c(0.0, 0.0),
+                c(-2.0, 0.0), c(3.0, 1.0), c(1.0, 1.0),
+                c(0.0, -1.0), c(1.0, 0.0), c(2.0, -2.0)]).reshape(&[3, 3])
+}
+
+#[test]
+fn arithmetic() {
+    let a = T::new(vec![c(1.0, 2.0), c(3.0, -1.0)]);
+    let b = T::new(vec![c(0.0, 1.0), c(2.0, 2.0)]);
+    assert!(&a + &b == T::new(vec![c(1.0, 3.0), c(5.0, 1.0)]));
+    assert!(&a - &b == T::new(vec![c(1.0, 1.0), c(1.0, -3.0)]));
+    assert!(&a * &b == T::new(vec![c(-2.0, 1.0), c(8.0, 4.0)]));
+    assert_close(&(&(&a * &b) / &b), &a);
+    assert!(&a * c(0.0, 1.0) == T::new(vec![c(-2.0, 1.0), c(1.0, 3.0)]));
+    assert!(-&a == T::new(vec![c(-1.0, -2.0), c(-3.0, 1.0)]));
+}
+
+#[test]
+fn parts() {
+    let a = T::new(vec![c(3.0, 4.0), c(-1.0, 0.0), c(0.0, -2.0)]);
+    assert!(a.real() == Tensor::new(vec![3.0, -1.0, 0.0]));
+    assert!(a.imag() == Tensor::new(vec![4.0, 0.0, -2.0]));
+    assert!(a.conj() == T::new(vec![c(3.0, -4.0), c(-1.0, 0.0), c(0.0, 2.0)]));
+    assert!(a.abs() == Tensor::new(vec![5.0, 1.0, 2.0]));
+    assert!(a.angle().iter().zip([0.9272952180016122, PI, -PI / 2.0].iter())
+             .all(|(x, y)| (x - y).abs() < 1e-12));
+
+    assert!(T::from_parts(&a.real(), &a.imag()) == a);
+    assert_close(&T::from_polar(&a.abs(), &a.angle()), &a);
+    assert!(Tensor::new(vec![1.0_f64, 2.0]).to_complex() == T::new(vec![c(1.0, 0.0), c(2.0, 0.0)]));
+}
+
+#[test]
+fn elementwise_functions() {
+    let a = T::new(vec![c(1.0, 2.0), c(-4.0, 0.0), c(0.5, -0.3)]);
+    assert_close(&a.ln().exp(), &a);
+    assert_close(&(&a.sqrt() * &a.sqrt()), &a);
+    assert_close(&T::new(vec![c(-4.0, 0.0)]).sqrt(), &T::new(vec![c(0.0, 2.0)]));
+    assert_close(&T::new(vec![c(0.0, PI)]).exp(), &T::new(vec![c(-1.0, 0.0)]));
+
+    assert_close(&math::complex::exp(math::complex::ln(a.clone())), &a);
+    assert_close(&math::complex::sqrt(a.clone()), &a.sqrt());
+
+    // Non-canonical input
+    let b = T::new(vec![c(1.0, 2.0), c(-4.0, 0.0), c(0.5, -0.3), c(0.0, 1.0)]).reshape(&[2, 2]);
+    assert_close(&math::complex::exp(b.transpose()), &b.exp().transpose());
+}
+
+#[test]
+fn dot() {
+    let a = T::new(vec![c(1.0, 1.0), c(0.0, 2.0),
+                        c(3.0, 0.0), c(1.0, -1.0)]).reshape(&[2, 2]);
+    let x = T::new(vec![c(0.0, 1.0), c(2.0, 0.0)]);
+    assert!(a.dot(&x) == T::new(vec![c(-1.0, 5.0), c(2.0, 1.0)]));
+    assert!(x.dot(&a) == T::new(vec![c(5.0, 1.0), c(0.0, -2.0)]));
+    assert!(x.dot(&x) == Tensor::scalar(c(3.0, 0.0)));
+    assert!(a.dot(&a) == T::new(vec![c(0.0, 8.0), c(0.0, 4.0),
+                                     c(6.0, 0.0), c(0.0, 4.0)]).reshape(&[2, 2]));
+}
+
+#[test]
+fn solve() {
+    let a = mat();
+    let b = T::new(vec![c(1.0, 0.0), c(0.0, 1.0), c(2.0, -1.0)]);
+    let x = a.solve(&b);
+    assert_close(&a.dot(&x), &b);
+
+    let b2 = T::new(vec![c(1.0, 0.0), c(0.0, 0.0),
+                         c(0.0, 1.0), c(1.0, 0.0),
+                         c(2.0, -1.0), c(0.0, 3.0)]).reshape(&[3, 2]);
+    let x2 = a.solve(&b2);
+    assert_close(&a.dot(&x2), &b2);
+}
+
+#[test]
+fn svd() {
+    let a = mat();
+    for &(m, n) in [(3, 3), (3, 2), (2, 3)].iter() {
+        let a = a.index(&[::numeric::StridedSlice(None, Some(m), 1),
+                          ::numeric::StridedSlice(None, Some(n), 1)]).canonize();
+        let k = if m < n { m } else { n };
+
+        let (u, s, vh) = a.svd(false);
+        assert!(u.shape() == &vec![m as usize, k as usize]);
+        assert!(vh.shape() == &vec![k as usize, n as usize]);
+        assert!(s.iter().zip(s.iter().skip(1)).all(|(x, y)| x >= y));
+        let us = &u * &s.to_complex().reshape(&[1, k as isize]);
+        assert_close(&us.dot(&vh), &a);
+        assert_close(&herm(&u).dot(&u), &T::eye(k as usize));
+
+        let (u, _, vh) = a.svd(true);
+        assert_close(&herm(&u).dot(&u), &T::eye(m as usize));
+        assert_close(&vh.dot(&herm(&vh)), &T::eye(n as usize));
+    }
+}
+
+#[test]
+fn svd_rank_deficient() {
+    let v = T::new(vec![c(1.0, 1.0), c(0.0, 2.0), c(-1.0, 0.0)]).reshape(&[3, 1]);
+    let a = v.dot(&herm(&v));
+    let (u, s, vh) = a.svd(true);
+    assert!((s[(0,)] - 7.0).abs() < 1e-8 && s[(1,)].abs() < 1e-8 && s[(2,)].abs() < 1e-8);
+    assert_close(&herm(&u).dot(&u), &T::eye(3));
+    assert_close(&vh.dot(&herm(&vh)), &T::eye(3));
+}
+
+#[test]
+fn eigh() {
+    let a = mat();
+    let h = &a + &herm(&a);
+    let (w, v) = h.eigh().unwrap();
+    assert!(w.iter().zip(w.iter().skip(1)).all(|(x, y)| x <= y));
+    assert_close(&h.dot(&v), &(&v * &w.to_complex().reshape(&[1, 3])));
+    assert_close(&herm(&v).dot(&v), &T::eye(3));
+    assert!(h.eigvalsh().unwrap() == w);
+
+    // Only the lower triangle is referenced
+    let b = T::new(vec![c(2.0, 0.0), c(9.0, 9.0), c(0.0, 1.0), c(2.0, 0.0)]).reshape(&[2, 2]);
+    let w = b.eigvalsh().unwrap();
+    assert!((w[(0,)] - 1.0).abs() < 1e-12 && (w[(1,)] - 3.0).abs() < 1e-12);
+
+    // Rank deficient
+    let u = T::new(vec![c(1.0, 1.0), c(0.0, 2.0), c(-1.0, 0.0)]).reshape(&[3, 1]);
+    let (w, v) = u.dot(&herm(&u)).eigh().unwrap();
+    assert!(w[(0,)].abs() < 1e-8 && w[(1,)].abs() < 1e-8 && (w[(2,)] - 7.0).abs() < 1e-8);
+    assert_close(&herm(&v).dot(&v), &T::eye(3));
+
+    let z: Tensor<::num::complex::Complex32> = Tensor::zeros(&[0, 0]);
+    assert!(z.eigvalsh().unwrap().size() == 0);
+}
@@ -0,0 +1,138 @@
+use numeric::Tensor;
+use numeric::fft::{FftNorm, FftPlan, fftfreq, rfftfreq};
+use num::complex::Complex64;
+use std::f64::consts::PI;
+
+type C = Complex64;
+
+fn signal(n: usize) -> Tensor<C> {
+    Tensor::new((0..n).map(|k| C::new((k as f64 * 0.7).sin() + 0.1 * k as f64,
+                                      (k as f64 * 1.3).cos())).collect())
+}
+
+fn naive_dft(x: &Tensor<C>) -> Tensor<C> {
+    let n = x.size();
+    Tensor::new((0..n).map(|k| {
+        x.iter().enumerate().fold(C::new(0.0, 0.0), |acc, (j, v)| {
+            let theta = -2.0 * PI * (j * k) as f64 / n as f64;
+            acc + v * C::new(theta.cos(), theta.sin())
+        })
+    }).collect())
+}
+
+fn assert_close(a: &Tensor<C>, b: &Tensor<C>) {
+    assert!(a.shape() == b.shape());
+    assert!((a - b).abs().max() < 1e-9 * (1.0 + b.abs().max()));
+}
+
+#[test]
+fn fft_matches_dft() {
+    // Covers powers of two, mixed radices, large primes (Bluestein) and composites thereof
+    for n in (1..41).chain(vec![64, 97, 100, 128, 210, 289].into_iter()) {
+        let x = signal(n);
+        assert_close(&x.fft(0, FftNorm::Backward), &naive_dft(&x));
+    }
+}
+
+#[test]
+fn roundtrip_and_norms() {
+    for &n in [8, 12, 17, 31].iter() {
+        let x = signal(n);
+        for &norm in [FftNorm::Backward, FftNorm::Ortho, FftNorm::Forward].iter() {
+            assert_close(&x.fft(0, norm).ifft(0, norm), &x);
+        }
+        let f = x.fft(0, FftNorm::Backward);
+        assert_close(&x.fft(0, FftNorm::Forward), &(&f * C::new(1.0 / n as f64, 0.0)));
+        assert_close(&x.fft(0, FftNorm::Ortho),
+                     &(&f * C::new(1.0 / (n as f64).sqrt(), 0.0)));
+    }
+}
+
+#[test]
+fn plan() {
+    let p = FftPlan::<f64>::cached(19);
+    assert_eq!(p.len(), 19);
+    let x = signal(19);
+    let mut data = x.slice().to_vec();
+    p.forward(&mut data);
+    assert_close(&Tensor::new(data.clone()), &naive_dft(&x));
+    p.inverse(&mut data);
+    assert_close(&Tensor::new(data), &(&x * C::new(19.0, 0.0)));
+}
+
+#[test]
+fn empty() {
+    let x: Tensor<C> = Tensor::new(vec![]);
+    assert_eq!(x.fft(0, FftNorm::Backward).size(), 0);
+    assert_eq!(x.ifft(0, FftNorm::Ortho).size(), 0);
+    assert_eq!(x.fftn(FftNorm::Forward).size(), 0);
+    let r: Tensor<f64> = Tensor::new(vec![]);
+    assert_eq!(r.rfft(0, FftNorm::Backward).size(), 0);
+    assert_eq!(FftPlan::<f64>::new(0).len(), 0);
+
+    let y = Tensor::<C>::zeros(&[3, 0]);
+    assert_eq!(y.fft2(FftNorm::Backward).shape(), &vec![3, 0]);
+}
+
+#[test]
+fn rfft_irfft() {
+    for &n in [1, 2, 7, 8, 15, 16].iter() {
+        let x: Tensor<f64> = Tensor::new((0..n).map(|k| (k as f64).sqrt() - 1.0).collect());
+        let r = x.rfft(0, FftNorm::Backward);
+        let f = x.to_complex().fft(0, FftNorm::Backward);
+        assert_eq!(r.size(), n / 2 + 1);
+        assert_close(&r, &f.index(&[::numeric::StridedSlice(None, Some((n / 2 + 1) as isize),
+                                                              1)]).canonize());
+        let y = r.irfft(n, 0, FftNorm::Backward);
+        assert!(::numeric::abs(&y - &x).max() < 1e-10);
+    }
+}
+
+#[test]
+fn axes() {
+    let x = signal(12).reshape(&[3, 4]);
+    let f0 = x.fft(0, FftNorm::Backward);
+    let f1 = x.fft(1, FftNorm::Backward);
+    let xt = x.transpose().canonize();
+    for i in 0..4 {
+        let col = xt.index(&[::numeric::Index(i)]).canonize();
+        let expected = naive_dft(&col);
+        for k in 0..3 {
+            assert!((f0[(k, i as usize)] - expected[(k,)]).norm() < 1e-9);
+        }
+    }
+    for i in 0..3 {
+        let row = x.index(&[::numeric::Index(i)]).canonize();
+        let expected = naive_dft(&row);
+        for k in 0..4 {
+            assert!((f1[(i as usize, k)] - expected[(k,)]).norm() < 1e-9);
+        }
+    }
+
+    let f2 = x.fft2(FftNorm::Backward);
+    assert_close(&f2, &f0.fft(1, FftNorm::Backward));
+    assert_close(&f2, &x.fftn(FftNorm::Backward));
+    assert_close(&f2.ifft2(FftNorm::Backward), &x);
+
+    let y = signal(30).reshape(&[2, 3, 5]);
+    assert_close(&y.fftn(FftNorm::Ortho).ifftn(FftNorm::Ortho), &y);
+}
+
+#[test]
+fn shift() {
+    let a: Tensor<i32> = Tensor::range(5);
+    assert!(a.fftshift() == Tensor::new(vec![3, 4, 0, 1, 2]));
+    assert!(a.fftshift().ifftshift() == a);
+
+    let b: Tensor<i32> = Tensor::range(6).reshape(&[2, 3]);
+    assert!(b.fftshift() == Tensor::new(vec![5, 3, 4, 2, 0, 1]).reshape(&[2, 3]));
+    assert!(b.fftshift().ifftshift() == b);
+}
+
+#[test]
+fn frequencies() {
+    assert!(fftfreq(4, 0.5) == Tensor::new(vec![0.0, 0.5, -1.0, -0.5]));
+    assert!(fftfreq(5, 1.0) == Tensor::new(vec![0.0, 0.2, 0.4, -0.4, -0.2]));
+    assert!(rfftfreq(5, 1.0) == Tensor::new(vec![0.0, 0.2, 0.4]));
+    assert!(rfftfreq(4, 0.5) == Tensor::new(vec![0.0, 0.5, 1.0]));
+}
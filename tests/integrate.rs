@@ -0,0 +1,111 @@
+use std::f64::consts::PI;
+use numeric::Tensor;
+use numeric::integrate::{trapz, simpson, cumulative_trapezoid, quad, quad_tol, gauss_legendre,
+                         fixed_quad, Spacing};
+
+fn assert_close(a: &Tensor<f64>, b: &Tensor<f64>, tol: f64) {
+    assert!(a.shape() == b.shape());
+    assert!(a.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() < tol));
+}
+
+#[test]
+fn trapz_axis() {
+    let y = Tensor::new(vec![1.0, 2.0, 3.0]);
+    assert!(trapz(&y, Spacing::Uniform(1.0), 0) == Tensor::scalar(4.0));
+    assert!(trapz(&y, Spacing::Uniform(0.5), 0) == Tensor::scalar(2.0));
+    let x = Tensor::new(vec![4.0, 6.0, 8.0]);
+    assert!(trapz(&y, Spacing::Points(&x), 0) == Tensor::scalar(8.0));
+
+    let m: Tensor<f64> = Tensor::range(6).reshape(&[2, 3]);
+    assert!(trapz(&m, Spacing::Uniform(1.0), 0) == Tensor::new(vec![1.5, 2.5, 3.5]));
+    assert!(trapz(&m, Spacing::Uniform(1.0), 1) == Tensor::new(vec![2.0, 8.0]));
+    let x = Tensor::new(vec![0.0, 1.0, 3.0]);
+    assert!(trapz(&m, Spacing::Points(&x), 1) == Tensor::new(vec![0.5 + 3.0, 3.5 + 9.0]));
+}
+
+#[test]
+fn simpson_exact_for_quadratics() {
+    let f = |x: f64| 3.0 * x * x - x + 2.0;
+    // Exact integral over [0, 2]: 8 - 2 + 4 = 10
+    for &n in [3, 4, 5, 8].iter() {
+        let x: Tensor<f64> = Tensor::linspace(0.0, 2.0, n);
+        let y = Tensor::new(x.iter().map(&f).collect());
+        let r = simpson(&y, Spacing::Uniform(2.0 / (n - 1) as f64), 0);
+        assert_close(&r, &Tensor::scalar(10.0), 1e-12);
+    }
+    // Uneven spacing, with an odd number of intervals
+    let x = Tensor::new(vec![0.0, 0.3, 0.5, 1.2, 2.0]);
+    let y = Tensor::new(x.iter().map(&f).collect());
+    assert_close(&simpson(&y, Spacing::Points(&x), 0), &Tensor::scalar(10.0), 1e-12);
+    let x = Tensor::new(vec![0.0, 0.3, 1.2, 2.0]);
+    let y = Tensor::new(x.iter().map(&f).collect());
+    assert_close(&simpson(&y, Spacing::Points(&x), 0), &Tensor::scalar(10.0), 1e-12);
+
+    let m = Tensor::new(vec![0.0, 1.0, 4.0, 9.0,
+                             1.0, 1.0, 1.0, 1.0]).reshape(&[2, 4]);
+    assert_close(&simpson(&m, Spacing::Uniform(1.0), 1), &Tensor::new(vec![9.0, 3.0]), 1e-12);
+}
+
+#[test]
+fn cumulative() {
+    let y = Tensor::new(vec![1.0, 2.0, 3.0, 4.0]);
+    assert!(cumulative_trapezoid(&y, Spacing::Uniform(1.0), 0, None) ==
+            Tensor::new(vec![1.5, 4.0, 7.5]));
+    assert!(cumulative_trapezoid(&y, Spacing::Uniform(2.0), 0, Some(0.0)) ==
+            Tensor::new(vec![0.0, 3.0, 8.0, 15.0]));
+    let m: Tensor<f64> = Tensor::range(6).reshape(&[3, 2]);
+    assert!(cumulative_trapezoid(&m, Spacing::Uniform(1.0), 0, Some(1.0)) ==
+            Tensor::new(vec![1.0, 1.0, 2.0, 3.0, 5.0, 7.0]).reshape(&[3, 2]));
+}
+
+#[test]
+fn quad_finite() {
+    let r = quad(|x: f64| x.sin(), 0.0, PI);
+    assert!((r.value - 2.0).abs() < 1e-14 && r.converged);
+    assert!(r.error < 1e-10 && r.error >= 0.0);
+
+    // Reversed bounds flip the sign
+    let r = quad(|x: f64| x * x, 1.0, 0.0);
+    assert!((r.value + 1.0 / 3.0).abs() < 1e-15);
+
+    // Integrable singularity, which requires subdivision
+    let r = quad_tol(|x: f64| 1.0 / x.sqrt(), 0.0, 1.0, 1e-10, 1e-10, 200);
+    assert!((r.value - 2.0).abs() < 1e-9 && r.converged && r.evaluations > 15);
+
+    // A peaked integrand, checked against the error estimate
+    let r = quad(|x: f64| 1.0 / (1e-4 + x * x), -1.0, 1.0);
+    let exact = 2.0 / 1e-2 * (1.0 / 1e-2_f64).atan();
+    assert!((r.value - exact).abs() <= r.error.max(1e-10));
+}
+
+#[test]
+fn quad_infinite() {
+    let r = quad(|x: f64| (-x * x).exp(), ::std::f64::NEG_INFINITY, ::std::f64::INFINITY);
+    assert!((r.value - PI.sqrt()).abs() < 1e-10);
+    let r = quad(|x: f64| (-x).exp(), 0.0, ::std::f64::INFINITY);
+    assert!((r.value - 1.0).abs() < 1e-10);
+    let r = quad(|x: f64| 1.0 / (1.0 + x * x), ::std::f64::NEG_INFINITY, 0.0);
+    assert!((r.value - PI / 2.0).abs() < 1e-10);
+}
+
+#[test]
+fn gauss_legendre_rules() {
+    let (x, w) = gauss_legendre(1);
+    assert!(x[(0,)].abs() < 1e-15 && w[(0,)] == 2.0);
+    let (x, w) = gauss_legendre(2);
+    assert_close(&x, &Tensor::new(vec![-1.0 / 3.0_f64.sqrt(), 1.0 / 3.0_f64.sqrt()]), 1e-15);
+    assert_close(&w, &Tensor::new(vec![1.0, 1.0]), 1e-15);
+    let (x, w) = gauss_legendre(3);
+    assert_close(&x, &Tensor::new(vec![-0.6_f64.sqrt(), 0.0, 0.6_f64.sqrt()]), 1e-15);
+    assert_close(&w, &Tensor::new(vec![5.0 / 9.0, 8.0 / 9.0, 5.0 / 9.0]), 1e-15);
+
+    // Exact for polynomials up to degree 2n - 1
+    for n in 1..20 {
+        let (x, w) = gauss_legendre(n);
+        assert!((w.sum() - 2.0).abs() < 1e-13);
+        let d = 2 * n - 2;
+        let s: f64 = x.iter().zip(w.iter()).map(|(xi, wi)| wi * xi.powi(d as i32)).sum();
+        assert!((s - 2.0 / (d + 1) as f64).abs() < 1e-13);
+    }
+    assert!((fixed_quad(|x: f64| x.exp(), 0.0, 1.0, 10) - (1.0_f64.exp() - 1.0)).abs() < 1e-15);
+}
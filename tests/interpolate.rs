@@ -0,0 +1,115 @@
+use numeric::Tensor;
+use numeric::interpolate::{interp, Interp1d, InterpKind, CubicSpline, SplineBoundary,
+                           RegularGridInterpolator};
+
+fn assert_close(a: &Tensor<f64>, b: &Tensor<f64>, tol: f64) {
+    assert!(a.shape() == b.shape());
+    assert!(a.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() < tol));
+}
+
+#[test]
+fn interp_linear() {
+    let xp = Tensor::new(vec![1.0, 2.0, 3.0]);
+    let fp = Tensor::new(vec![3.0, 2.0, 0.0]);
+    let x = Tensor::new(vec![0.0, 1.0, 1.5, 2.72, 3.14]);
+    assert_close(&interp(&x, &xp, &fp, None, None),
+                 &Tensor::new(vec![3.0, 3.0, 2.5, 0.56, 0.0]), 1e-12);
+    assert_close(&interp(&x, &xp, &fp, Some(-99.0), Some(99.0)),
+                 &Tensor::new(vec![-99.0, 3.0, 2.5, 0.56, 99.0]), 1e-12);
+
+    let x2 = Tensor::new(vec![1.5, 2.5, 1.0, 3.0]).reshape(&[2, 2]);
+    assert!(interp(&x2, &xp, &fp, None, None) ==
+            Tensor::new(vec![2.5, 1.0, 3.0, 0.0]).reshape(&[2, 2]));
+}
+
+#[test]
+fn interp1d_kinds() {
+    let x = Tensor::new(vec![0.0, 1.0, 2.0, 4.0]);
+    let y = Tensor::new(vec![0.0, 2.0, 1.0, 5.0]);
+    let q = Tensor::new(vec![0.4, 0.5, 0.6, 3.0, 4.0, -1.0, 5.0]);
+
+    let nearest = Interp1d::new(&x, &y, InterpKind::Nearest).eval(&q);
+    assert!(nearest.iter().take(5).collect::<Vec<f64>>() == vec![0.0, 0.0, 2.0, 1.0, 5.0]);
+    assert!(nearest[(5,)].is_nan() && nearest[(6,)].is_nan());
+
+    let linear = Interp1d::new(&x, &y, InterpKind::Linear).fill_value(-1.0).eval(&q);
+    assert_close(&linear, &Tensor::new(vec![0.8, 1.0, 1.2, 3.0, 5.0, -1.0, -1.0]), 1e-12);
+
+    let cubic = Interp1d::new(&x, &y, InterpKind::Cubic).fill_value(0.0);
+    let spline = CubicSpline::new(&x, &y, SplineBoundary::Natural);
+    assert_close(&cubic.eval(&x), &y, 1e-12);
+    assert!(cubic.eval(&Tensor::new(vec![3.3]))[(0,)] ==
+            spline.eval(&Tensor::new(vec![3.3]))[(0,)]);
+    assert!(cubic.eval(&Tensor::new(vec![4.5]))[(0,)] == 0.0);
+}
+
+#[test]
+fn spline_reproduces_cubic() {
+    // A clamped spline reproduces a cubic exactly when given its end derivatives
+    let f = |x: f64| x * x * x - 2.0 * x * x + 0.5;
+    let df = |x: f64| 3.0 * x * x - 4.0 * x;
+    let x = Tensor::new(vec![-1.0, 0.0, 0.5, 2.0, 3.0]);
+    let y = Tensor::new(x.iter().map(&f).collect());
+    let s = CubicSpline::new(&x, &y, SplineBoundary::Clamped(df(-1.0), df(3.0)));
+
+    let q: Tensor<f64> = Tensor::linspace(-1.0, 3.0, 17);
+    assert_close(&s.eval(&q), &Tensor::new(q.iter().map(&f).collect()), 1e-12);
+    assert_close(&s.derivative(&q, 1), &Tensor::new(q.iter().map(&df).collect()), 1e-12);
+    assert_close(&s.derivative(&q, 2), &Tensor::new(q.iter().map(|x| 6.0 * x - 4.0).collect()),
+                 1e-12);
+    assert_close(&s.derivative(&q, 3), &(&Tensor::ones(&[17]) * 6.0), 1e-10);
+}
+
+#[test]
+fn natural_spline() {
+    let x = Tensor::new(vec![0.0, 1.0, 2.0]);
+    let y = Tensor::new(vec![0.0, 1.0, 0.0]);
+    let s = CubicSpline::new(&x, &y, SplineBoundary::Natural);
+    // Second derivative vanishes at the ends; by symmetry, the slope is zero in the middle
+    let ends = Tensor::new(vec![0.0, 2.0]);
+    assert_close(&s.derivative(&ends, 2), &Tensor::zeros(&[2]), 1e-12);
+    assert!(s.derivative(&Tensor::new(vec![1.0]), 1)[(0,)].abs() < 1e-12);
+    // S(x) = 1.5x - 0.5x^3 on [0, 1]
+    assert!((s.eval(&Tensor::new(vec![0.5]))[(0,)] - 0.6875).abs() < 1e-12);
+
+    // With two points, a natural spline is a straight line
+    let s = CubicSpline::new(&Tensor::new(vec![0.0, 2.0]), &Tensor::new(vec![1.0, 5.0]),
+                             SplineBoundary::Natural);
+    assert_close(&s.eval(&Tensor::new(vec![0.5, 3.0])), &Tensor::new(vec![2.0, 7.0]), 1e-12);
+}
+
+#[test]
+fn regular_grid() {
+    let gx: Tensor<f64> = Tensor::linspace(0.0, 1.0, 3);
+    let gy: Tensor<f64> = Tensor::new(vec![0.0, 1.0, 3.0]);
+    // values = 2x + 3y + 1, which multilinear interpolation reproduces exactly
+    let mut v = Tensor::zeros(&[3, 3]);
+    for i in 0..3 {
+        for j in 0..3 {
+            v[(i, j)] = 2.0 * gx[(i,)] + 3.0 * gy[(j,)] + 1.0;
+        }
+    }
+    let interp = RegularGridInterpolator::new(&[gx, gy], &v);
+    let xi = Tensor::new(vec![0.25, 0.5,
+                              1.0, 3.0,
+                              0.7, 2.2,
+                              1.5, 0.0]).reshape(&[4, 2]);
+    let y = interp.eval(&xi);
+    assert_close(&y.index(&[::numeric::StridedSlice(None, Some(3), 1)]).canonize(),
+                 &Tensor::new(vec![3.0, 12.0, 9.0]), 1e-12);
+    assert!(y[(3,)].is_nan());
+    assert!(interp.fill_value(0.0).eval_point(&[1.5, 0.0]) == 0.0);
+
+    // Bilinear on a single cell, with a cross term
+    let v: Tensor<f64> = Tensor::new(vec![0.0, 0.0, 0.0, 1.0]).reshape(&[2, 2]);
+    let g = Tensor::new(vec![0.0, 1.0]);
+    let interp = RegularGridInterpolator::new(&[g.clone(), g], &v);
+    assert!((interp.eval_point(&[0.5, 0.5]) - 0.25).abs() < 1e-15);
+
+    // Three dimensions
+    let g: Tensor<f64> = Tensor::new(vec![0.0, 1.0]);
+    let v = Tensor::new(vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]).reshape(&[2, 2, 2]);
+    let interp = RegularGridInterpolator::new(&[g.clone(), g.clone(), g], &v);
+    assert!((interp.eval_point(&[0.5, 0.5, 0.5]) - 3.5).abs() < 1e-15);
+    assert!((interp.eval_point(&[1.0, 0.0, 0.25]) - 4.25).abs() < 1e-15);
+}
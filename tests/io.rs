@@ -1,4 +1,5 @@
-use numeric::{Tensor, io};
+use numeric::{Tensor, RandomState, io};
+use numeric::random::Philox4x64;
 use std::env;
 use std::fs;
 
@@ -42,3 +43,49 @@ fn hdf5() {
     }
 }
 
+#[test]
+fn hdf5_random_state() {
+    // Uses its own file, but see the note on thread-safety above
+    let mut path = env::temp_dir();
+    path.push("numeric_random_state.h5");
+    if path.exists() {
+        assert!(fs::remove_file(&path).is_ok());
+    }
+
+    {
+        // A checkpoint with a tensor and the random state that continues after it
+        let mut rs = RandomState::new(1234);
+        let t: Tensor<f64> = rs.uniform(0.0, 1.0, &[10]);
+
+        assert!(t.save_hdf5(&path).is_ok());
+        assert!(rs.save_hdf5(&path, "random_state").is_ok());
+        // Saving again overwrites the state
+        assert!(rs.save_hdf5(&path, "random_state").is_ok());
+        let next: Tensor<f64> = rs.uniform(0.0, 1.0, &[10]);
+
+        let t2 = io::load_hdf5_as_f64(&path, "/data").unwrap();
+        let mut rs2: RandomState = RandomState::load_hdf5(&path, "random_state").unwrap();
+        assert!(t == t2);
+        assert!(rs2.uniform(0.0, 1.0, &[10]) == next);
+        assert!(rs2.get_state() == rs.get_state());
+        assert!(fs::remove_file(&path).is_ok());
+    }
+
+    {
+        // A state of another size or type is not written over an existing dataset
+        let philox = RandomState::<Philox4x64>::from_seed(1);
+        let pcg = RandomState::new(2);
+        let t: Tensor<f64> = Tensor::range(10);
+
+        assert!(t.save_hdf5(&path).is_ok());
+        assert!(philox.save_hdf5(&path, "random_state").is_ok());
+        assert!(pcg.save_hdf5(&path, "random_state").is_err());
+        assert!(pcg.save_hdf5(&path, "data").is_err());
+
+        let t2 = io::load_hdf5_as_f64(&path, "/data").unwrap();
+        let rs2 = RandomState::<Philox4x64>::load_hdf5(&path, "random_state").unwrap();
+        assert!(t == t2);
+        assert!(rs2.get_state() == philox.get_state());
+        assert!(fs::remove_file(&path).is_ok());
+    }
+}
@@ -1,5 +1,8 @@
 #[macro_use]
 extern crate numeric;
+extern crate num;
+#[cfg(feature = "serde-serialize")]
+extern crate serde_json;
 
 mod creation;
 mod dot;
@@ -11,6 +14,18 @@ mod mul;
 mod div;
 
 mod linalg;
+mod sparse;
+mod complex;
+mod fft;
+mod signal;
+mod special;
+mod polynomial;
+mod interpolate;
+mod integrate;
+mod ode;
+mod optimize;
+mod random;
+mod qmc;
 
 mod indexing;
 mod summary;
@@ -2,8 +2,16 @@ macro_rules! add_tests {
     ($t:ty, $m:ident) => (
         mod $m {
             use numeric::{Tensor, AxisIndex};
+            use numeric::linalg::{LinalgError, QrMode, NormType};
             type T = Tensor<$t>;
 
+            fn assert_close(a: &T, b: &T) {
+                assert_eq!(a.shape(), b.shape());
+                for (v1, v2) in a.iter().zip(b.iter()) {
+                    assert!((v1 - v2).abs() < 1e-4, "{} != {}", v1, v2);
+                }
+            }
+
             #[test]
             fn svd_eye() {
                 let eye = T::eye(3);
@@ -13,6 +21,391 @@ macro_rules! add_tests {
                 assert!(s == T::ones(&[3]));
             }
 
+            #[test]
+            fn inv_eye() {
+                let eye = T::eye(3);
+                assert!(eye.inv().unwrap() == eye);
+            }
+
+            #[test]
+            fn inv_2x2() {
+                let a = T::new(vec![4.0, 7.0, 2.0, 6.0]).reshape(&[2, 2]);
+                let answer = T::new(vec![0.6, -0.7, -0.2, 0.4]).reshape(&[2, 2]);
+                assert_close(&a.inv().unwrap(), &answer);
+                assert_close(&a.dot(&a.inv().unwrap()), &T::eye(2));
+            }
+
+            #[test]
+            fn inv_singular() {
+                let a = T::new(vec![1.0, 2.0, 2.0, 4.0]).reshape(&[2, 2]);
+                assert!(a.inv().err() == Some(LinalgError::Singular));
+            }
+
+            #[test]
+            fn pinv_square() {
+                let a = T::new(vec![4.0, 7.0, 2.0, 6.0]).reshape(&[2, 2]);
+                assert_close(&a.pinv(1e-6), &a.inv().unwrap());
+            }
+
+            #[test]
+            fn det_2x2() {
+                let a = T::new(vec![1.0, 2.0, 3.0, 4.0]).reshape(&[2, 2]);
+                assert!((a.det() - -2.0).abs() < 1e-5);
+            }
+
+            #[test]
+            fn det_singular() {
+                let a = T::new(vec![1.0, 2.0, 2.0, 4.0]).reshape(&[2, 2]);
+                assert!(a.det() == 0.0);
+            }
+
+            #[test]
+            fn slogdet_3x3() {
+                let a = T::new(vec![0.0, 2.0, 0.0,
+                                    3.0, 0.0, 0.0,
+                                    0.0, 0.0, 4.0]).reshape(&[3, 3]);
+                let (sign, logdet) = a.slogdet();
+                assert!(sign == -1.0);
+                assert!((logdet - (24.0 as $t).ln()).abs() < 1e-5);
+            }
+
+            #[test]
+            fn lu_unpack() {
+                let a = T::new(vec![1.0, 2.0, 3.0,
+                                    4.0, 5.0, 6.0,
+                                    7.0, 8.0, 10.0]).reshape(&[3, 3]);
+                let (p, l, u) = a.lu().unpack();
+                assert_close(&p.dot(&l).dot(&u), &a);
+                assert!(l.index(&[AxisIndex::Index(0)]) == T::new(vec![1.0, 0.0, 0.0]));
+                assert!(u[(2, 0)] == 0.0);
+            }
+
+            #[test]
+            fn lu_solve() {
+                let a = T::new(vec![2.0, 1.0, 1.0, 3.0]).reshape(&[2, 2]);
+                let lu = a.lu();
+                let x = lu.solve(&T::new(vec![3.0, 4.0])).unwrap();
+                assert_close(&x, &T::new(vec![1.0, 1.0]));
+
+                let b = T::new(vec![3.0, 1.0, 4.0, 3.0]).reshape(&[2, 2]);
+                let x = lu.solve(&b).unwrap();
+                assert_close(&x, &T::new(vec![1.0, 0.0, 1.0, 1.0]).reshape(&[2, 2]));
+            }
+
+            #[test]
+            fn lu_singular() {
+                let a = T::new(vec![1.0, 2.0, 2.0, 4.0]).reshape(&[2, 2]);
+                assert!(a.lu().solve(&T::ones(&[2])).err() == Some(LinalgError::Singular));
+            }
+
+            #[test]
+            fn qr_reduced() {
+                let a = T::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).reshape(&[3, 2]);
+                let (q, r) = a.qr(QrMode::Reduced);
+                let q = q.unwrap();
+                assert!(q.shape() == &vec![3, 2]);
+                assert!(r.shape() == &vec![2, 2]);
+                assert!(r[(1, 0)] == 0.0);
+                assert_close(&q.dot(&r), &a);
+                assert_close(&q.transpose().dot(&q), &T::eye(2));
+            }
+
+            #[test]
+            fn qr_complete() {
+                let a = T::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).reshape(&[3, 2]);
+                let (q, r) = a.qr(QrMode::Complete);
+                let q = q.unwrap();
+                assert!(q.shape() == &vec![3, 3]);
+                assert!(r.shape() == &vec![3, 2]);
+                assert_close(&q.dot(&r), &a);
+                assert_close(&q.transpose().dot(&q), &T::eye(3));
+
+                let (q, r2) = a.qr(QrMode::R);
+                assert!(q.is_none());
+                assert_close(&r2, &r.index(&[AxisIndex::StridedSlice(None, Some(2), 1)]));
+            }
+
+            #[test]
+            fn cholesky() {
+                let a = T::new(vec![4.0, 2.0, 2.0, 3.0]).reshape(&[2, 2]);
+                let l = a.cholesky(true).unwrap();
+                assert_close(&l, &T::new(vec![2.0, 0.0, 1.0, (2.0 as $t).sqrt()]).reshape(&[2, 2]));
+                let u = a.cholesky(false).unwrap();
+                assert_close(&u, &l.transpose().canonize());
+                assert_close(&u.transpose().dot(&u), &a);
+            }
+
+            #[test]
+            fn cholesky_not_positive_definite() {
+                let a = T::new(vec![1.0, 2.0, 2.0, 1.0]).reshape(&[2, 2]);
+                assert!(a.cholesky(true).err() == Some(LinalgError::NotPositiveDefinite));
+            }
+
+            #[test]
+            fn eigh_2x2() {
+                let a = T::new(vec![2.0, 1.0, 1.0, 2.0]).reshape(&[2, 2]);
+                let (w, v) = a.eigh().unwrap();
+                assert_close(&w, &T::new(vec![1.0, 3.0]));
+                assert_close(&a.dot(&v), &(&v * &w.clone().reshape(&[1, 2])));
+                assert_close(&v.transpose().dot(&v), &T::eye(2));
+                assert_close(&a.eigvalsh().unwrap(), &w);
+            }
+
+            #[test]
+            fn eigh_subset() {
+                let a = T::new(vec![1.0, 0.0, 0.0,
+                                    0.0, 3.0, 0.0,
+                                    0.0, 0.0, 2.0]).reshape(&[3, 3]);
+                let (w, v) = a.eigh_subset(1, 3).unwrap();
+                assert_close(&w, &T::new(vec![2.0, 3.0]));
+                assert!(v.shape() == &vec![3, 2]);
+                assert!((v[(2, 0)].abs() - 1.0).abs() < 1e-5);
+                assert!((v[(1, 1)].abs() - 1.0).abs() < 1e-5);
+            }
+
+            #[test]
+            fn eig_rotation() {
+                let a = T::new(vec![0.0, -1.0, 1.0, 0.0]).reshape(&[2, 2]);
+                let w = a.eigvals().unwrap();
+                let (w2, v) = a.eig().unwrap();
+                assert!(v.shape() == &vec![2, 2]);
+                for (v1, v2) in w.iter().zip(w2.iter()) {
+                    assert!(v1.re.abs() < 1e-5 && (v1.im.abs() - 1.0).abs() < 1e-5);
+                    assert!((v1 - v2).norm() < 1e-5);
+                }
+                assert!((w[(0,)].im + w[(1,)].im).abs() < 1e-5);
+            }
+
+            #[test]
+            fn lstsq_line_fit() {
+                // Fit y = c0 + c1 * x to points that lie exactly on y = 1 + 2x
+                let a = T::new(vec![1.0, 0.0,
+                                    1.0, 1.0,
+                                    1.0, 2.0,
+                                    1.0, 3.0]).reshape(&[4, 2]);
+                let b = T::new(vec![1.0, 3.0, 5.0, 7.0]);
+                let res = a.lstsq(&b, -1.0).unwrap();
+                assert_close(&res.solution, &T::new(vec![1.0, 2.0]));
+                assert!(res.rank == 2);
+                assert!(res.residuals.shape() == &vec![1]);
+                assert!(res.residuals[(0,)].abs() < 1e-4);
+                assert!(res.singular_values.shape() == &vec![2]);
+            }
+
+            #[test]
+            fn lstsq_residuals() {
+                let a = T::new(vec![1.0, 1.0, 1.0]).reshape(&[3, 1]);
+                let b = T::new(vec![1.0, 2.0, 2.0, 4.0, 3.0, 6.0]).reshape(&[3, 2]);
+                let res = a.lstsq(&b, -1.0).unwrap();
+                assert_close(&res.solution, &T::new(vec![2.0, 4.0]).reshape(&[1, 2]));
+                assert_close(&res.residuals, &T::new(vec![2.0, 8.0]));
+            }
+
+            #[test]
+            fn matrix_rank() {
+                let a = T::new(vec![1.0, 2.0, 2.0, 4.0]).reshape(&[2, 2]);
+                assert!(a.matrix_rank(None) == 1);
+                assert!(T::eye(3).matrix_rank(None) == 3);
+                assert!(T::zeros(&[2, 3]).matrix_rank(None) == 0);
+            }
+
+            #[test]
+            fn norm_vector() {
+                let a = T::new(vec![3.0, -4.0]);
+                assert!((a.norm(NormType::P(2.0)) - 5.0).abs() < 1e-5);
+                assert!(a.norm(NormType::P(1.0)) == 7.0);
+                assert!(a.norm(NormType::Inf) == 4.0);
+                assert!(a.norm(NormType::NegInf) == 3.0);
+                assert!(a.norm(NormType::P(0.0)) == 2.0);
+                assert!((a.norm(NormType::P(3.0)) - (91.0 as $t).powf(1.0 / 3.0)).abs() < 1e-4);
+            }
+
+            #[test]
+            fn norm_matrix() {
+                let a = T::new(vec![1.0, -2.0, 3.0, 4.0]).reshape(&[2, 2]);
+                assert!((a.norm(NormType::Fro) - (30.0 as $t).sqrt()).abs() < 1e-5);
+                assert!(a.norm(NormType::Inf) == 7.0);
+                assert!(a.norm(NormType::NegInf) == 3.0);
+                assert!(a.norm(NormType::P(1.0)) == 6.0);
+                assert!(a.norm(NormType::P(-1.0)) == 4.0);
+
+                let d = T::new(vec![3.0, 0.0, 0.0, -2.0]).reshape(&[2, 2]);
+                assert!((d.norm(NormType::P(2.0)) - 3.0).abs() < 1e-5);
+                assert!((d.norm(NormType::P(-2.0)) - 2.0).abs() < 1e-5);
+                assert!((d.norm(NormType::Nuc) - 5.0).abs() < 1e-5);
+            }
+
+            #[test]
+            fn norm_axis() {
+                let a = T::new(vec![3.0, 0.0, 1.0, -4.0, 0.0, 0.0]).reshape(&[2, 3]);
+                assert_close(&a.norm_axis(NormType::P(2.0), 0), &T::new(vec![5.0, 0.0, 1.0]));
+                assert_close(&a.norm_axis(NormType::P(1.0), 1), &T::new(vec![4.0, 4.0]));
+            }
+
+            #[test]
+            fn cond() {
+                let a = T::new(vec![4.0, 0.0, 0.0, 0.5]).reshape(&[2, 2]);
+                assert!((a.cond(NormType::P(2.0)) - 8.0).abs() < 1e-4);
+                assert!((a.cond(NormType::P(1.0)) - 8.0).abs() < 1e-4);
+                assert!((a.cond(NormType::Fro) - 8.125).abs() < 1e-3);
+
+                let s = T::new(vec![1.0, 2.0, 2.0, 4.0]).reshape(&[2, 2]);
+                assert!(s.cond(NormType::P(1.0)).is_infinite());
+            }
+
+            #[test]
+            fn trace() {
+                let a = T::range(9).reshape(&[3, 3]);
+                assert!(a.trace(0) == 12.0);
+                assert!(a.trace(1) == 6.0);
+                assert!(a.trace(-2) == 6.0);
+                assert!(a.trace(3) == 0.0);
+            }
+
+            #[test]
+            fn solve_vector() {
+                let a = T::new(vec![2.0, 1.0, 1.0, 3.0]).reshape(&[2, 2]);
+                let x = a.solve(&T::new(vec![3.0, 5.0]));
+                assert_close(&x, &T::new(vec![0.8, 1.4]));
+            }
+
+            #[test]
+            fn solve_matrix() {
+                let a = T::new(vec![2.0, 1.0, 1.0, 3.0]).reshape(&[2, 2]);
+                let b = T::new(vec![3.0, 1.0, 4.0, 3.0]).reshape(&[2, 2]);
+                let x = a.solve(&b);
+                assert_close(&x, &T::new(vec![1.0, 0.0, 1.0, 1.0]).reshape(&[2, 2]));
+            }
+
+            #[test]
+            fn solve_batched() {
+                let a = T::new(vec![2.0, 0.0, 0.0, 4.0,
+                                    1.0, 1.0, 0.0, 1.0]).reshape(&[2, 2, 2]);
+                let b = T::new(vec![2.0, 4.0,
+                                    3.0, 1.0]).reshape(&[2, 2]);
+                let x = a.solve(&b);
+                assert_close(&x, &T::new(vec![1.0, 1.0, 2.0, 1.0]).reshape(&[2, 2]));
+
+                let b3 = T::new(vec![2.0, 4.0, 4.0, 8.0,
+                                     3.0, 6.0, 1.0, 2.0]).reshape(&[2, 2, 2]);
+                let x3 = a.solve(&b3);
+                assert_close(&x3, &T::new(vec![1.0, 2.0, 1.0, 2.0,
+                                               2.0, 4.0, 1.0, 2.0]).reshape(&[2, 2, 2]));
+
+                let empty = T::zeros(&[0, 2, 2]).solve(&T::zeros(&[0, 2]));
+                assert!(empty.shape() == &vec![0, 2]);
+            }
+
+            #[test]
+            fn solve_triangular() {
+                let l = T::new(vec![2.0, 0.0, 1.0, 1.0]).reshape(&[2, 2]);
+                let x = l.solve_triangular(&T::new(vec![2.0, 3.0]), true).unwrap();
+                assert_close(&x, &T::new(vec![1.0, 2.0]));
+
+                let u = l.transpose();
+                let x = u.solve_triangular(&T::new(vec![4.0, 2.0]), false).unwrap();
+                assert_close(&x, &T::new(vec![1.0, 2.0]));
+
+                let s = T::new(vec![0.0, 0.0, 1.0, 1.0]).reshape(&[2, 2]);
+                assert!(s.solve_triangular(&T::ones(&[2]), true).err() ==
+                        Some(LinalgError::Singular));
+            }
+
+            #[test]
+            fn cho_solve() {
+                let a = T::new(vec![4.0, 2.0, 2.0, 3.0]).reshape(&[2, 2]);
+                let b = T::new(vec![6.0, 5.0]);
+                for &lower in [true, false].iter() {
+                    let c = a.cholesky(lower).unwrap();
+                    assert_close(&c.cho_solve(&b, lower).unwrap(), &T::new(vec![1.0, 1.0]));
+                }
+            }
+
+            #[test]
+            fn solve_posdef() {
+                let a = T::new(vec![4.0, 2.0, 2.0, 3.0]).reshape(&[2, 2]);
+                let b = T::new(vec![6.0, 12.0, 5.0, 10.0]).reshape(&[2, 2]);
+                let x = a.solve_posdef(&b).unwrap();
+                assert_close(&x, &T::new(vec![1.0, 2.0, 1.0, 2.0]).reshape(&[2, 2]));
+
+                let n = T::new(vec![1.0, 2.0, 2.0, 1.0]).reshape(&[2, 2]);
+                assert!(n.solve_posdef(&T::ones(&[2])).err() ==
+                        Some(LinalgError::NotPositiveDefinite));
+            }
+
+            #[test]
+            fn solve_banded() {
+                // Tridiagonal matrix [[2, -1, 0], [-1, 2, -1], [0, -1, 2]]
+                let ab = T::new(vec![ 0.0, -1.0, -1.0,
+                                      2.0,  2.0,  2.0,
+                                     -1.0, -1.0,  0.0]).reshape(&[3, 3]);
+                let x = ab.solve_banded(1, 1, &T::new(vec![1.0, 0.0, 1.0])).unwrap();
+                assert_close(&x, &T::new(vec![1.0, 1.0, 1.0]));
+            }
+
+            #[test]
+            fn matrix_power() {
+                let a = T::new(vec![1.0, 1.0, 0.0, 1.0]).reshape(&[2, 2]);
+                assert_close(&a.matrix_power(0).unwrap(), &T::eye(2));
+                assert_close(&a.matrix_power(3).unwrap(),
+                             &T::new(vec![1.0, 3.0, 0.0, 1.0]).reshape(&[2, 2]));
+                assert_close(&a.matrix_power(-2).unwrap(),
+                             &T::new(vec![1.0, -2.0, 0.0, 1.0]).reshape(&[2, 2]));
+
+                let m = T::new(vec![-1.0, 0.0, 0.0, -1.0]).reshape(&[2, 2]);
+                assert_close(&m.matrix_power(isize::MIN).unwrap(), &T::eye(2));
+
+                let s = T::zeros(&[2, 2]);
+                assert!(s.matrix_power(-1).err() == Some(LinalgError::Singular));
+            }
+
+            #[test]
+            fn expm() {
+                assert_close(&T::zeros(&[2, 2]).expm(), &T::eye(2));
+
+                let d = T::new(vec![1.0, 0.0, 0.0, 2.0]).reshape(&[2, 2]);
+                let e = (1.0 as $t).exp();
+                assert_close(&d.expm(), &T::new(vec![e, 0.0, 0.0, e * e]).reshape(&[2, 2]));
+
+                // Rotation generators, small and large enough to require scaling
+                for &theta in [0.5, 10.0].iter() {
+                    let theta: $t = theta;
+                    let a = T::new(vec![0.0, -theta, theta, 0.0]).reshape(&[2, 2]);
+                    let (c, s) = (theta.cos(), theta.sin());
+                    assert_close(&a.expm(), &T::new(vec![c, -s, s, c]).reshape(&[2, 2]));
+                }
+            }
+
+            #[test]
+            fn sqrtm() {
+                let a = T::new(vec![4.0, 1.0, 1.0, 3.0]).reshape(&[2, 2]);
+                let r = a.sqrtm().unwrap();
+                assert_close(&r.dot(&r), &a);
+
+                // Complex eigenvalues
+                let b = T::new(vec![1.0, -2.0, 2.0, 1.0]).reshape(&[2, 2]);
+                let r = b.sqrtm().unwrap();
+                assert_close(&r.dot(&r), &b);
+            }
+
+            #[test]
+            fn logm() {
+                let a = T::new(vec![0.5, 0.2, 0.2, -0.3]).reshape(&[2, 2]);
+                assert_close(&a.expm().logm().unwrap(), &a);
+
+                let b = T::new(vec![0.0, -0.5, 0.5, 0.0]).reshape(&[2, 2]);
+                assert_close(&b.expm().logm().unwrap(), &b);
+
+                let s = T::new(vec![1.0, 2.0, 0.0, 0.0]).reshape(&[2, 2]);
+                assert!(s.logm().err() == Some(LinalgError::Singular));
+            }
+
+            #[test]
+            fn funm() {
+                let a = T::new(vec![1.0, -2.0, 2.0, 1.0]).reshape(&[2, 2]);
+                assert_close(&a.funm(|z| z.exp()).unwrap(), &a.expm());
+            }
+
             // TODO: Needs more tests
         }
     )
@@ -20,3 +413,142 @@ macro_rules! add_tests {
 
 add_tests!(f32, float32);
 add_tests!(f64, float64);
+
+#[test]
+fn trace_int() {
+    use numeric::Tensor;
+    let a: Tensor<i32> = Tensor::range(6).reshape(&[2, 3]);
+    assert!(a.trace(0) == 4);
+    assert!(a.trace(-1) == 3);
+}
+
+mod complex {
+    use numeric::Tensor;
+    use num::complex::Complex64;
+
+    #[test]
+    fn inv_det_2x2() {
+        let a = Tensor::new(vec![Complex64::new(0.0, 1.0), Complex64::new(2.0, 0.0),
+                                 Complex64::new(1.0, 0.0), Complex64::new(1.0, 0.0)])
+                    .reshape(&[2, 2]);
+        // det = i - 2
+        let d = a.det();
+        assert!((d - Complex64::new(-2.0, 1.0)).norm() < 1e-10);
+
+        let ainv = a.inv().unwrap();
+        let expected = [Complex64::new(1.0, 0.0), Complex64::new(-2.0, 0.0),
+                        Complex64::new(-1.0, 0.0), Complex64::new(0.0, 1.0)];
+        for (v, e) in ainv.iter().zip(expected.iter()) {
+            assert!((v - e / d).norm() < 1e-10);
+        }
+
+        let (sign, logdet) = a.slogdet();
+        assert!((sign * logdet.exp() - d).norm() < 1e-10);
+    }
+}
+
+mod iterative {
+    use numeric::Tensor;
+    use numeric::sparse::{CooMatrix, CsrMatrix};
+    use numeric::linalg::{LinalgError, Identity, Jacobi, Ilu0, cg, bicgstab, gmres, minres};
+
+    /// Tridiagonal matrix with `d` on the diagonal, `l` below and `u` above it.
+    fn tridiag(n: usize, l: f64, d: f64, u: f64) -> CsrMatrix<f64> {
+        let mut coo = CooMatrix::new(&[n, n]);
+        for i in 0..n {
+            coo.push(i, i, d + i as f64 * 0.1);
+            if i > 0 {
+                coo.push(i, i - 1, l);
+            }
+            if i + 1 < n {
+                coo.push(i, i + 1, u);
+            }
+        }
+        coo.to_csr()
+    }
+
+    fn check(a: &CsrMatrix<f64>, x: &Tensor<f64>, b: &Tensor<f64>) {
+        let r = b - &a.dot(x);
+        assert!(r.iter().all(|v| v.abs() < 1e-6));
+    }
+
+    #[test]
+    fn cg_poisson() {
+        let a = tridiag(30, -1.0, 2.0, -1.0);
+        let b = Tensor::ones(&[30]);
+        let res = cg(&a, &b, &Identity, 1e-10, 100);
+        assert!(res.converged);
+        assert_eq!(res.iterations, res.residuals.len());
+        check(&a, &res.solution, &b);
+
+        let pre = cg(&a, &b, &Ilu0::new(&a).unwrap(), 1e-10, 100);
+        assert!(pre.converged);
+        assert!(pre.iterations < res.iterations);
+        check(&a, &pre.solution, &b);
+
+        let few = cg(&a, &b, &Identity, 1e-10, 3);
+        assert!(!few.converged);
+        assert_eq!(few.iterations, 3);
+    }
+
+    #[test]
+    fn cg_dense_and_closure() {
+        let a: Tensor<f64> = Tensor::new(vec![4.0, 1.0, 1.0, 3.0]).reshape(&[2, 2]);
+        let b = Tensor::new(vec![1.0, 2.0]);
+        let expected = a.solve(&b);
+        let res = cg(&a, &b, &Jacobi::new(&a).unwrap(), 1e-12, 10);
+        assert!(res.solution.iter().zip(expected.iter()).all(|(x, y)| (x - y).abs() < 1e-8));
+
+        let op = |x: &Tensor<f64>| a.dot(x);
+        let res = cg(&op, &b, &Identity, 1e-12, 10);
+        assert!(res.solution.iter().zip(expected.iter()).all(|(x, y)| (x - y).abs() < 1e-8));
+    }
+
+    #[test]
+    fn nonsymmetric() {
+        let a = tridiag(30, -1.5, 3.0, -0.5);
+        let b = Tensor::ones(&[30]);
+        for res in vec![bicgstab(&a, &b, &Identity, 1e-10, 200),
+                        bicgstab(&a, &b, &Jacobi::from_csr(&a).unwrap(), 1e-10, 200),
+                        gmres(&a, &b, &Identity, 10, 1e-10, 200),
+                        gmres(&a, &b, &Ilu0::new(&a).unwrap(), 5, 1e-10, 200)] {
+            assert!(res.converged);
+            check(&a, &res.solution, &b);
+        }
+    }
+
+    #[test]
+    fn minres_indefinite() {
+        let mut a = tridiag(20, 1.0, 0.5, 1.0);
+        a = &a - &CsrMatrix::from_tensor(&(&Tensor::eye(20) * 1.3));
+        let b = Tensor::ones(&[20]);
+        let res = minres(&a, &b, &Identity, 1e-10, 200);
+        assert!(res.converged);
+        check(&a, &res.solution, &b);
+    }
+
+    #[test]
+    fn zero_rhs() {
+        let a = tridiag(5, -1.0, 2.0, -1.0);
+        let res = gmres(&a, &Tensor::zeros(&[5]), &Identity, 5, 1e-10, 10);
+        assert!(res.converged);
+        assert!(res.solution == Tensor::zeros(&[5]));
+    }
+
+    #[test]
+    fn gmres_singular() {
+        // A maps b to zero, so the Krylov space gives no reduction of the residual
+        let a = CsrMatrix::from_tensor(&Tensor::new(vec![0.0, 1.0, 0.0, 0.0]).reshape(&[2, 2]));
+        let res = gmres(&a, &Tensor::new(vec![1.0, 0.0]), &Identity, 2, 1e-10, 10);
+        assert!(!res.converged);
+        assert!(res.solution.iter().all(|x| x.is_finite()));
+        assert!(res.residuals.iter().all(|r| r.is_finite()));
+    }
+
+    #[test]
+    fn preconditioner_singular() {
+        let a = CsrMatrix::from_tensor(&Tensor::new(vec![0.0, 1.0, 1.0, 0.0]).reshape(&[2, 2]));
+        assert!(Jacobi::from_csr(&a).err() == Some(LinalgError::Singular));
+        assert!(Ilu0::new(&a).err() == Some(LinalgError::Singular));
+    }
+}
@@ -0,0 +1,146 @@
+use numeric::Tensor;
+use numeric::integrate::{solve_ivp, solve_ivp_options, OdeMethod, OdeOptions, OdeStatus, Event};
+
+fn last(y: &Tensor<f64>, j: usize) -> f64 {
+    y[(y.dim(0) - 1, j)]
+}
+
+#[test]
+fn ode_exponential_decay() {
+    let y0 = Tensor::new(vec![1.0, 2.0]);
+    for &method in [OdeMethod::Rk4(0.01), OdeMethod::Rk45, OdeMethod::Dop853,
+                    OdeMethod::Bdf].iter() {
+        let res = solve_ivp(|_t, y: &Tensor<f64>| -y, (0.0, 2.0), &y0, method);
+        assert!(res.status == OdeStatus::Finished);
+        assert!(res.y.shape() == &vec![res.t.size(), 2]);
+        assert!(res.t[(0,)] == 0.0 && res.t[(res.t.size() - 1,)] == 2.0);
+        let e = (-2.0f64).exp();
+        assert!((last(&res.y, 0) - e).abs() < 1e-3);
+        assert!((last(&res.y, 1) - 2.0 * e).abs() < 2e-3);
+    }
+}
+
+#[test]
+fn ode_rk4_fixed_step() {
+    // 0.3 does not divide the interval, so 4 steps of 0.25 are taken
+    let res = solve_ivp(|t, _y: &Tensor<f64>| Tensor::new(vec![t.cos()]), (0.0, 1.0),
+                        &Tensor::new(vec![0.0]), OdeMethod::Rk4(0.3));
+    assert!(res.t == Tensor::new(vec![0.0, 0.25, 0.5, 0.75, 1.0]));
+    assert!(res.nfev == 1 + 4 * 4);
+    assert!((last(&res.y, 0) - 1.0f64.sin()).abs() < 1e-5);
+}
+
+#[test]
+fn ode_harmonic_oscillator() {
+    let f = |_t: f64, y: &Tensor<f64>| Tensor::new(vec![y[(1,)], -y[(0,)]]);
+    let mut options = OdeOptions::default();
+    options.rtol = 1e-10;
+    options.atol = 1e-12;
+    let res = solve_ivp_options(&f, (0.0, 10.0), &Tensor::new(vec![1.0, 0.0]),
+                                OdeMethod::Dop853, options);
+    assert!((last(&res.y, 0) - 10.0f64.cos()).abs() < 1e-8);
+    assert!((last(&res.y, 1) + 10.0f64.sin()).abs() < 1e-8);
+
+    let mut options = OdeOptions::default();
+    options.rtol = 1e-8;
+    options.atol = 1e-10;
+    let res = solve_ivp_options(&f, (0.0, 10.0), &Tensor::new(vec![1.0, 0.0]),
+                                OdeMethod::Rk45, options);
+    assert!((last(&res.y, 0) - 10.0f64.cos()).abs() < 1e-6);
+}
+
+#[test]
+fn ode_stiff() {
+    // The solution quickly approaches cos(t) + sin(t) / 1000 (approximately)
+    let f = |t: f64, y: &Tensor<f64>| Tensor::new(vec![-1000.0 * (y[(0,)] - t.cos())]);
+    let mut options = OdeOptions::default();
+    options.rtol = 1e-6;
+    options.atol = 1e-9;
+    let res = solve_ivp_options(&f, (0.0, 10.0), &Tensor::new(vec![0.0]), OdeMethod::Bdf,
+                                options);
+    assert!(res.status == OdeStatus::Finished);
+    let exact = (1e6 * 10.0f64.cos() + 1000.0 * 10.0f64.sin()) / (1e6 + 1.0);
+    assert!((last(&res.y, 0) - exact).abs() < 1e-5);
+    assert!(res.njev >= 1 && res.nlu >= 1);
+    // An explicit method needs far more steps
+    let explicit = solve_ivp(&f, (0.0, 10.0), &Tensor::new(vec![0.0]), OdeMethod::Rk45);
+    assert!(explicit.t.size() > 5 * res.t.size());
+}
+
+#[test]
+fn ode_robertson() {
+    let f = |_t: f64, y: &Tensor<f64>| {
+        let (a, b, c) = (y[(0,)], y[(1,)], y[(2,)]);
+        Tensor::new(vec![-0.04 * a + 1e4 * b * c,
+                         0.04 * a - 1e4 * b * c - 3e7 * b * b,
+                         3e7 * b * b])
+    };
+    let mut options = OdeOptions::default();
+    options.rtol = 1e-6;
+    options.atol = 1e-10;
+    let res = solve_ivp_options(f, (0.0, 40.0), &Tensor::new(vec![1.0, 0.0, 0.0]),
+                                OdeMethod::Bdf, options);
+    assert!(res.status == OdeStatus::Finished);
+    // Reference values at t = 40
+    assert!((last(&res.y, 0) - 0.7158270687).abs() < 1e-5);
+    assert!((last(&res.y, 1) - 9.185534764e-6).abs() < 1e-9);
+    let total = last(&res.y, 0) + last(&res.y, 1) + last(&res.y, 2);
+    assert!((total - 1.0).abs() < 1e-8);
+}
+
+#[test]
+fn ode_t_eval_and_dense_output() {
+    let mut options = OdeOptions::default();
+    options.rtol = 1e-8;
+    options.atol = 1e-10;
+    options.t_eval = Some(vec![0.0, 0.5, 1.0, 1.5, 3.0]);
+    options.dense_output = true;
+    let res = solve_ivp_options(|_t, y: &Tensor<f64>| -y, (0.0, 2.0), &Tensor::new(vec![1.0]),
+                                OdeMethod::Rk45, options);
+    // 3.0 is outside of the interval
+    assert!(res.t == Tensor::new(vec![0.0, 0.5, 1.0, 1.5]));
+    assert!(res.y.shape() == &vec![4, 1]);
+    for i in 0..4 {
+        assert!((res.y[(i, 0)] - (-0.5 * i as f64).exp()).abs() < 1e-6);
+    }
+    let sol = res.sol.unwrap();
+    assert!((sol.eval(0.7)[(0,)] - (-0.7f64).exp()).abs() < 1e-6);
+    let ys = sol.eval_many(&Tensor::new(vec![0.1, 1.9]));
+    assert!(ys.shape() == &vec![2, 1]);
+    assert!((ys[(1, 0)] - (-1.9f64).exp()).abs() < 1e-6);
+}
+
+#[test]
+fn ode_events() {
+    // A ball falling from a height of 10, hitting the ground at sqrt(20 / 9.81)
+    let f = |_t: f64, y: &Tensor<f64>| Tensor::new(vec![y[(1,)], -9.81]);
+    let mut options = OdeOptions::default();
+    options.events = vec![Event::new(|_t, y: &Tensor<f64>| y[(0,)]).terminal(true),
+                          Event::new(|_t, y: &Tensor<f64>| y[(0,)] - 5.0).direction(1.0),
+                          Event::new(|_t, y: &Tensor<f64>| y[(0,)] - 5.0).direction(-1.0)];
+    let res = solve_ivp_options(f, (0.0, 10.0), &Tensor::new(vec![10.0, 0.0]), OdeMethod::Rk45,
+                                options);
+    assert!(res.status == OdeStatus::Terminated);
+    let hit = (20.0f64 / 9.81).sqrt();
+    assert!(res.t_events[0].len() == 1 && (res.t_events[0][0] - hit).abs() < 1e-8);
+    assert!(res.y_events[0][0][(0,)].abs() < 1e-8);
+    assert!((res.t[(res.t.size() - 1,)] - hit).abs() < 1e-8);
+    // Only the downward crossing of 5 is reported
+    assert!(res.t_events[1].is_empty());
+    assert!(res.t_events[2].len() == 1);
+    assert!((res.t_events[2][0] - (10.0f64 / 9.81).sqrt()).abs() < 1e-8);
+}
+
+#[test]
+fn ode_backwards() {
+    for &method in [OdeMethod::Rk4(0.01), OdeMethod::Rk45, OdeMethod::Dop853,
+                    OdeMethod::Bdf].iter() {
+        let mut options = OdeOptions::default();
+        options.rtol = 1e-6;
+        options.atol = 1e-9;
+        let res = solve_ivp_options(|_t, y: &Tensor<f64>| -y, (1.0, 0.0),
+                                    &Tensor::new(vec![1.0]), method, options);
+        assert!(res.t[(res.t.size() - 1,)] == 0.0);
+        assert!((last(&res.y, 0) - 1.0f64.exp()).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,153 @@
+use numeric::Tensor;
+use numeric::optimize::{brentq, brentq_tol, newton, minimize, minimize_options, MinimizeMethod,
+                        MinimizeOptions, least_squares, curve_fit};
+
+fn rosen(x: &Tensor<f64>) -> f64 {
+    (0..x.size() - 1).map(|i| {
+        let (a, b) = (x[(i,)], x[(i + 1,)]);
+        100.0 * (b - a * a) * (b - a * a) + (1.0 - a) * (1.0 - a)
+    }).sum()
+}
+
+fn rosen_der(x: &Tensor<f64>) -> Tensor<f64> {
+    let n = x.size();
+    let mut g = Tensor::zeros(&[n]);
+    for i in 0..n - 1 {
+        let (a, b) = (x[(i,)], x[(i + 1,)]);
+        g[(i,)] += -400.0 * a * (b - a * a) - 2.0 * (1.0 - a);
+        g[(i + 1,)] += 200.0 * (b - a * a);
+    }
+    g
+}
+
+#[test]
+fn brentq_roots() {
+    let r = brentq(|x: f64| x.cos() - x, 0.0, 1.0);
+    assert!(r.converged);
+    assert!((r.root - 0.7390851332151607).abs() < 1e-11);
+    let r = brentq(|x: f64| x * x * x - 2.0 * x - 5.0, 3.0, 2.0);
+    assert!((r.root - 2.0945514815423265).abs() < 1e-11);
+    // A root at an end point
+    let r = brentq(|x: f64| x - 1.0, 1.0, 3.0);
+    assert!(r.root == 1.0 && r.iterations == 0);
+    let r = brentq_tol(|x: f64| x.exp() - 2.0, 0.0, 1.0, 1e-4, 0.0, 100);
+    assert!((r.root - 2.0f64.ln()).abs() < 1e-4);
+    let r = brentq_tol(|x: f64| x.exp() - 2.0, 0.0, 1.0, 1e-15, 0.0, 2);
+    assert!(!r.converged && r.iterations == 2);
+}
+
+#[test]
+#[should_panic]
+fn brentq_no_sign_change() {
+    brentq(|x: f64| x * x + 1.0, -1.0, 1.0);
+}
+
+#[test]
+fn newton_roots() {
+    let r = newton(|x: f64| x * x - 2.0, |x: f64| 2.0 * x, 1.0);
+    assert!(r.converged && (r.root - 2.0f64.sqrt()).abs() < 1e-12);
+    assert!(r.function_calls == r.iterations);
+    // Zero derivative at the starting point
+    let r = newton(|x: f64| x * x - 2.0, |x: f64| 2.0 * x, 0.0);
+    assert!(!r.converged);
+}
+
+#[test]
+fn minimize_rosenbrock() {
+    let x0 = Tensor::new(vec![1.3, 0.7, 0.8, 1.9, 1.2]);
+    let ones: Tensor<f64> = Tensor::ones(&[5]);
+    for &method in [MinimizeMethod::Bfgs, MinimizeMethod::ProjectedLBfgs].iter() {
+        let res = minimize(rosen, &x0, method);
+        assert!(res.converged);
+        assert!(res.x.iter().zip(ones.iter()).all(|(a, b)| (a - b).abs() < 1e-4));
+        assert!(res.grad.iter().all(|g| g.abs() < 1e-2));
+        assert!(res.fun < 1e-8 && res.njev == 0);
+    }
+
+    let mut options = MinimizeOptions::default();
+    options.jac = Some(Box::new(rosen_der));
+    let res = minimize_options(rosen, &x0, MinimizeMethod::Bfgs, options);
+    assert!(res.converged && res.njev > 0);
+    assert!(res.x.iter().all(|a| (a - 1.0).abs() < 1e-4));
+    assert!(res.grad == rosen_der(&res.x));
+    // The gradient of the last iterate is reused rather than evaluated again
+    assert!(res.njev == res.nfev);
+
+    let mut options = MinimizeOptions::default();
+    options.xatol = 1e-8;
+    options.fatol = 1e-8;
+    options.maxiter = Some(5000);
+    options.jac = Some(Box::new(rosen_der));
+    let res = minimize_options(rosen, &Tensor::new(vec![-1.2, 1.0]), MinimizeMethod::NelderMead,
+                               options);
+    assert!(res.converged);
+    assert!(res.x.iter().all(|a| (a - 1.0).abs() < 1e-6));
+    assert!(res.grad == rosen_der(&res.x) && res.njev == 1);
+
+    let res = minimize(rosen, &Tensor::new(vec![-1.2, 1.0]), MinimizeMethod::NelderMead);
+    assert!(res.grad.size() == 0 && res.njev == 0);
+}
+
+#[test]
+fn minimize_bounds() {
+    // The unconstrained minimum (3, -1) is outside of the bounds
+    let f = |x: &Tensor<f64>| {
+        let (a, b) = (x[(0,)], x[(1,)]);
+        (a - 3.0) * (a - 3.0) + (b + 1.0) * (b + 1.0) + a * b
+    };
+    for &method in [MinimizeMethod::ProjectedLBfgs, MinimizeMethod::NelderMead].iter() {
+        let mut options = MinimizeOptions::default();
+        options.bounds = Some(vec![(0.0, 2.0), (0.0, ::std::f64::INFINITY)]);
+        options.xatol = 1e-10;
+        options.fatol = 1e-12;
+        let res = minimize_options(&f, &Tensor::new(vec![1.0, 1.0]), method, options);
+        assert!(res.converged);
+        // At a = 2, b = 0 the gradient (-2 + 0, 2 + 2) points out of the feasible region
+        assert!((res.x[(0,)] - 2.0).abs() < 1e-6 && res.x[(1,)].abs() < 1e-6);
+        assert!((res.fun - 2.0).abs() < 1e-6);
+    }
+}
+
+#[test]
+#[should_panic]
+fn minimize_bfgs_bounds() {
+    let mut options = MinimizeOptions::default();
+    options.bounds = Some(vec![(0.0, 1.0)]);
+    minimize_options(|x: &Tensor<f64>| x[(0,)], &Tensor::new(vec![0.5]), MinimizeMethod::Bfgs,
+                     options);
+}
+
+#[test]
+fn least_squares_rosenbrock() {
+    let f = |x: &Tensor<f64>| {
+        Tensor::new(vec![10.0 * (x[(1,)] - x[(0,)] * x[(0,)]), 1.0 - x[(0,)]])
+    };
+    let res = least_squares(f, &Tensor::new(vec![-1.2, 1.0]));
+    assert!(res.converged);
+    assert!((res.x[(0,)] - 1.0).abs() < 1e-7 && (res.x[(1,)] - 1.0).abs() < 1e-7);
+    assert!(res.fun < 1e-14);
+    assert!(res.grad.size() == 2);
+}
+
+#[test]
+fn curve_fit_exponential() {
+    // y = 2.5 exp(-1.3 x) + 0.5 with a deterministic perturbation
+    let x: Tensor<f64> = Tensor::linspace(0.0, 4.0, 50);
+    let noise: Vec<f64> = (0..50).map(|i| 0.01 * ((i * 7 % 11) as f64 - 5.0)).collect();
+    let y = Tensor::new(x.iter().zip(noise.iter())
+                         .map(|(xi, e)| 2.5 * (-1.3 * xi).exp() + 0.5 + e).collect());
+    let model = |x: &Tensor<f64>, p: &Tensor<f64>| {
+        Tensor::new(x.iter().map(|xi| p[(0,)] * (-p[(1,)] * xi).exp() + p[(2,)]).collect())
+    };
+    let (res, pcov) = curve_fit(model, &x, &y, &Tensor::new(vec![1.0, 1.0, 0.0]));
+    assert!(res.converged);
+    assert!((res.x[(0,)] - 2.5).abs() < 0.05);
+    assert!((res.x[(1,)] - 1.3).abs() < 0.05);
+    assert!((res.x[(2,)] - 0.5).abs() < 0.05);
+    assert!(pcov.shape() == &vec![3, 3]);
+    for i in 0..3 {
+        // Standard errors are small but positive
+        assert!(pcov[(i, i)] > 0.0 && pcov[(i, i)].sqrt() < 0.05);
+        assert!((pcov[(i, (i + 1) % 3)] - pcov[((i + 1) % 3, i)]).abs() < 1e-12);
+    }
+}
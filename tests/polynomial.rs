@@ -0,0 +1,134 @@
+use numeric::Tensor;
+use numeric::polynomial::{polyval, polyfit, roots, polyder, polyint, Polynomial,
+                          chebval, chebfit, legval, legfit};
+
+fn assert_close(a: &Tensor<f64>, b: &Tensor<f64>, tol: f64) {
+    assert!(a.shape() == b.shape());
+    assert!(a.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() < tol));
+}
+
+#[test]
+fn polyval_horner() {
+    // 2x^2 - 3x + 1
+    let p = Tensor::new(vec![2.0, -3.0, 1.0]);
+    let x = Tensor::new(vec![0.0, 1.0, 2.0, -1.0]);
+    assert!(polyval(&p, &x) == Tensor::new(vec![1.0, 0.0, 3.0, 6.0]));
+    let pi: Tensor<i32> = Tensor::new(vec![1, 0, 1]);
+    assert!(polyval(&pi, &Tensor::new(vec![3, 4]).reshape(&[1, 2])) ==
+            Tensor::new(vec![10, 17]).reshape(&[1, 2]));
+}
+
+#[test]
+fn polyfit_exact() {
+    let x: Tensor<f64> = Tensor::range(6);
+    let p = Tensor::new(vec![0.5, -1.0, 2.0, 3.0]);
+    let y = polyval(&p, &x);
+    assert_close(&polyfit(&x, &y, 3).unwrap(), &p, 1e-10);
+
+    // Straight line through noisy-free points with a higher degree fit
+    let y = Tensor::new(vec![1.0, 3.0, 5.0, 7.0, 9.0, 11.0]);
+    assert_close(&polyfit(&x, &y, 1).unwrap(), &Tensor::new(vec![2.0, 1.0]), 1e-12);
+}
+
+#[test]
+fn polyfit_least_squares() {
+    let x = Tensor::new(vec![0.0, 1.0, 2.0, 3.0]);
+    let y = Tensor::new(vec![0.0, 1.0, 1.0, 3.0]);
+    // Normal equations give slope 0.9 and intercept -0.1
+    assert_close(&polyfit(&x, &y, 1).unwrap(), &Tensor::new(vec![0.9, -0.1]), 1e-12);
+}
+
+#[test]
+fn roots_companion() {
+    // (x - 1)(x - 2)(x + 3) = x^3 - 7x + 6
+    let r = roots(&Tensor::new(vec![1.0, 0.0, -7.0, 6.0])).unwrap();
+    let mut re: Vec<f64> = r.iter().map(|z| z.re).collect();
+    re.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert!(r.iter().all(|z| z.im.abs() < 1e-12));
+    assert!((re[0] + 3.0).abs() < 1e-12 && (re[1] - 1.0).abs() < 1e-12 &&
+            (re[2] - 2.0).abs() < 1e-12);
+
+    // x^2 + 1, with a leading and a trailing zero: roots +-i and 0
+    let r = roots(&Tensor::new(vec![0.0, 1.0, 0.0, 1.0, 0.0])).unwrap();
+    assert!(r.size() == 3);
+    assert!(r.iter().filter(|z| (z.im.abs() - 1.0).abs() < 1e-12 && z.re.abs() < 1e-12)
+             .count() == 2);
+    assert!(r[(2,)].norm() == 0.0);
+    assert!(roots(&Tensor::new(vec![5.0])).unwrap().size() == 0);
+}
+
+#[test]
+fn derivative_and_integral() {
+    let p = Tensor::new(vec![3.0, 2.0, 1.0]);
+    assert!(polyder(&p, 1) == Tensor::new(vec![6.0, 2.0]));
+    assert!(polyder(&p, 2) == Tensor::new(vec![6.0]));
+    assert!(polyder(&p, 3) == Tensor::new(vec![0.0]));
+    assert!(polyint(&p, 1, 0.0) == Tensor::new(vec![1.0, 1.0, 1.0, 0.0]));
+    assert!(polyint(&Tensor::new(vec![2.0]), 2, 1.0) == Tensor::new(vec![1.0, 1.0, 1.0]));
+    assert!(polyder(&polyint(&p, 2, 4.0), 2) == p);
+}
+
+#[test]
+fn polynomial_arithmetic() {
+    let a = Polynomial::new(vec![1.0, 2.0]);        // 1 + 2x
+    let b = Polynomial::new(vec![-1.0, 0.0, 3.0]);  // -1 + 3x^2
+    assert!(&a + &b == Polynomial::new(vec![0.0, 2.0, 3.0]));
+    assert!(&a - &b == Polynomial::new(vec![2.0, 2.0, -3.0]));
+    assert!(&a * &b == Polynomial::new(vec![-1.0, -2.0, 3.0, 6.0]));
+    assert!(&a * 2.0 == Polynomial::new(vec![2.0, 4.0]));
+    assert!(-a.clone() == Polynomial::new(vec![-1.0, -2.0]));
+    assert!((a.clone() - a.clone()).degree() == 0);
+    assert!((a.clone() - a.clone()).coeffs().is_empty());
+
+    let c = &a * &b;
+    assert!(c.degree() == 3);
+    assert!(c.eval(2.0) == 5.0 * 11.0);
+    assert!(c.eval_tensor(&Tensor::new(vec![0.0, 2.0])) == Tensor::new(vec![-1.0, 55.0]));
+    let (q, r) = c.div_rem(&b);
+    assert!(q == a && r.coeffs().is_empty());
+    let (q, r) = (&c + &Polynomial::new(vec![1.0])).div_rem(&a);
+    assert!(q == b && r == Polynomial::new(vec![1.0]));
+
+    assert!(b.deriv(1) == Polynomial::new(vec![0.0, 6.0]));
+    assert!(b.integ(1, 2.0) == Polynomial::new(vec![2.0, -1.0, 0.0, 1.0]));
+    assert!(Polynomial::from_tensor(&b.to_tensor()) == b);
+}
+
+#[test]
+fn polynomial_fit_and_roots() {
+    let x: Tensor<f64> = Tensor::range(5);
+    let y = Tensor::new(vec![6.0, 2.0, 0.0, 0.0, 2.0]); // x^2 - 5x + 6
+    let p = Polynomial::fit(&x, &y, 2).unwrap();
+    assert!(p.coeffs().iter().zip([6.0, -5.0, 1.0].iter()).all(|(a, b)| (a - b).abs() < 1e-10));
+    let r = p.roots().unwrap();
+    let mut re: Vec<f64> = r.iter().map(|z| z.re).collect();
+    re.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert!((re[0] - 2.0).abs() < 1e-9 && (re[1] - 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn chebyshev() {
+    let x = Tensor::new(vec![-1.0, -0.5, 0.0, 0.3, 1.0]);
+    // T_3(x) = 4x^3 - 3x
+    let c = Tensor::new(vec![0.0, 0.0, 0.0, 1.0]);
+    let t3 = polyval(&Tensor::new(vec![4.0, 0.0, -3.0, 0.0]), &x);
+    assert_close(&chebval(&c, &x), &t3, 1e-14);
+
+    let c = Tensor::new(vec![1.0, -0.5, 0.25, 2.0]);
+    let xs = Tensor::linspace(-1.0, 1.0, 20);
+    let fit = chebfit(&xs, &chebval(&c, &xs), 3).unwrap();
+    assert_close(&fit, &c, 1e-12);
+}
+
+#[test]
+fn legendre() {
+    let x = Tensor::new(vec![-1.0, -0.5, 0.0, 0.3, 1.0]);
+    // P_2(x) = (3x^2 - 1) / 2
+    let c = Tensor::new(vec![0.0, 0.0, 1.0]);
+    assert_close(&legval(&c, &x), &polyval(&Tensor::new(vec![1.5, 0.0, -0.5]), &x), 1e-14);
+
+    let c = Tensor::new(vec![0.5, 1.0, -2.0, 0.0, 0.75]);
+    let xs = Tensor::linspace(-1.0, 1.0, 25);
+    let fit = legfit(&xs, &legval(&c, &xs), 4).unwrap();
+    assert_close(&fit, &c, 1e-12);
+}
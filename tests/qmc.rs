@@ -0,0 +1,106 @@
+use numeric::Tensor;
+use numeric::qmc::{sobol, Sobol, SOBOL_MAX_DIM, halton, scrambled_halton, latin_hypercube};
+use numeric::random::RandomState;
+
+/// Checks that the first `2^m` points have exactly one point in each box of volume `2^-m`
+/// with sides `2^-k` and `2^-(m - k)` in dimensions `i` and `j`, that is, that the projection
+/// is a (0, m, 2)-net in base 2.
+fn is_net(x: &Tensor<f64>, i: usize, j: usize, m: u32) -> bool {
+    let n = 1usize << m;
+    (0..m + 1).all(|k| {
+        let mut counts = vec![0; n];
+        for p in 0..n {
+            let a = (x[(p, i)] * (1u64 << k) as f64) as usize;
+            let b = (x[(p, j)] * (1u64 << (m - k)) as f64) as usize;
+            counts[(a << (m - k)) | b] += 1;
+        }
+        counts.iter().all(|&c| c == 1)
+    })
+}
+
+#[test]
+fn sobol_points() {
+    let x = sobol(8, 2);
+    let expected = Tensor::new(vec![0.0, 0.0, 0.5, 0.5, 0.75, 0.25, 0.25, 0.75,
+                                    0.375, 0.375, 0.875, 0.875, 0.625, 0.125, 0.125, 0.625])
+                       .reshape(&[8, 2]);
+    assert!(x == expected);
+
+    let x = sobol(1 << 10, SOBOL_MAX_DIM);
+    assert!(SOBOL_MAX_DIM == 21 && x.shape() == &vec![1024, SOBOL_MAX_DIM]);
+    assert!(x.iter().all(|v| v >= 0.0 && v < 1.0));
+    // Each coordinate is stratified, and the first two dimensions form a (0, m, 2)-net
+    for j in 0..SOBOL_MAX_DIM {
+        let mut strata: Vec<usize> = (0..1024).map(|i| (x[(i, j)] * 1024.0) as usize).collect();
+        strata.sort();
+        assert!(strata == (0..1024).collect::<Vec<_>>());
+    }
+    assert!(is_net(&x, 0, 1, 10));
+    // Reference values for the last two dimensions, from their Joe-Kuo direction numbers
+    let last: Vec<f64> = (1..9).map(|i| x[(i, SOBOL_MAX_DIM - 1)]).collect();
+    assert!(last == vec![0.5, 0.25, 0.75, 0.125, 0.625, 0.375, 0.875, 0.1875]);
+    assert!(x[(8, SOBOL_MAX_DIM - 2)] == 0.3125);
+    assert!(x[(100, SOBOL_MAX_DIM - 2)] == 0.3359375 && x[(100, SOBOL_MAX_DIM - 1)] == 0.7578125);
+
+    // Drawing in chunks continues the sequence
+    let mut s = Sobol::new(5);
+    let a = s.sample(100);
+    let b = s.sample(28);
+    let all = sobol(128, 5);
+    assert!((0..100).all(|i| (0..5).all(|j| a[(i, j)] == all[(i, j)])));
+    assert!((0..28).all(|i| (0..5).all(|j| b[(i, j)] == all[(100 + i, j)])));
+}
+
+#[test]
+#[should_panic]
+fn sobol_too_many_dimensions() {
+    sobol(4, SOBOL_MAX_DIM + 1);
+}
+
+#[test]
+fn sobol_integration() {
+    // The integral of prod_j 2 x_j over the unit cube is one
+    let d = 5;
+    let x = sobol(1 << 12, d);
+    let mean = (0..1 << 12).map(|i| (0..d).map(|j| 2.0 * x[(i, j)]).product::<f64>())
+                           .sum::<f64>() / (1 << 12) as f64;
+    // Much closer than the typical Monte Carlo error of about 0.025
+    assert!((mean - 1.0).abs() < 0.005);
+}
+
+#[test]
+fn halton_points() {
+    let x = halton(6, 2);
+    let expected = Tensor::new(vec![0.0, 0.0, 0.5, 1.0 / 3.0, 0.25, 2.0 / 3.0,
+                                    0.75, 1.0 / 9.0, 0.125, 4.0 / 9.0, 0.625, 7.0 / 9.0])
+                       .reshape(&[6, 2]);
+    assert!(x.iter().zip(expected.iter()).all(|(a, b)| (a - b).abs() < 1e-15));
+
+    let mut rs = RandomState::new(1);
+    let x = scrambled_halton(2 * 3 * 5 * 7, 4, &mut rs);
+    assert!(x.iter().all(|v| v >= 0.0 && v < 1.0));
+    // Scrambling keeps the stratification of each coordinate in its base
+    for (j, &b) in [2usize, 3, 5, 7].iter().enumerate() {
+        let mut counts = vec![0; b];
+        for i in 0..210 {
+            counts[(x[(i, j)] * b as f64) as usize] += 1;
+        }
+        assert!(counts.iter().all(|&c| c == 210 / b));
+    }
+    assert!(scrambled_halton(10, 4, &mut RandomState::new(2)) ==
+            scrambled_halton(10, 4, &mut RandomState::new(2)));
+    assert!(x != halton(210, 4));
+}
+
+#[test]
+fn latin_hypercube_strata() {
+    let mut rs = RandomState::new(3);
+    let n = 50;
+    let x = latin_hypercube(n, 4, &mut rs);
+    assert!(x.shape() == &vec![n, 4]);
+    for j in 0..4 {
+        let mut strata: Vec<usize> = (0..n).map(|i| (x[(i, j)] * n as f64) as usize).collect();
+        strata.sort();
+        assert!(strata == (0..n).collect::<Vec<_>>());
+    }
+}
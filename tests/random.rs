@@ -0,0 +1,520 @@
+use numeric::Tensor;
+use numeric::random::{RandomState, BitGenerator, Pcg64, Xoshiro256PlusPlus, Philox4x64, jackknife};
+
+const N: usize = 100000;
+
+fn mean_var(t: &Tensor<f64>) -> (f64, f64) {
+    let n = t.size() as f64;
+    let mean = t.iter().sum::<f64>() / n;
+    let var = t.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / n;
+    (mean, var)
+}
+
+fn assert_moments(t: &Tensor<f64>, mean: f64, var: f64, tol: f64) {
+    let (m, v) = mean_var(t);
+    assert!((m - mean).abs() < tol * var.sqrt().max(1.0), "mean {} != {}", m, mean);
+    assert!((v - var).abs() < 10.0 * tol * var.max(1.0), "variance {} != {}", v, var);
+}
+
+#[test]
+fn random_seeded() {
+    let a: Tensor<f64> = RandomState::new(1234).gamma(2.0, 1.0, &[10]);
+    let b: Tensor<f64> = RandomState::new(1234).gamma(2.0, 1.0, &[10]);
+    let c: Tensor<f64> = RandomState::new(4321).gamma(2.0, 1.0, &[10]);
+    assert!(a == b);
+    assert!(a != c);
+    assert!(a.shape() == &vec![10]);
+}
+
+#[test]
+fn random_normal() {
+    let mut rs = RandomState::new(1);
+    let t: Tensor<f64> = rs.normal(3.0, 2.0, &[N]);
+    assert_moments(&t, 3.0, 4.0, 0.02);
+    let t: Tensor<f64> = rs.standard_normal(&[200, 500]);
+    assert!(t.shape() == &vec![200, 500]);
+    assert_moments(&t, 0.0, 1.0, 0.02);
+    let t: Tensor<f64> = rs.lognormal(0.0, 0.5, &[N]);
+    assert_moments(&t, 0.125f64.exp(), (0.25f64.exp() - 1.0) * 0.25f64.exp(), 0.02);
+}
+
+#[test]
+fn random_continuous() {
+    let mut rs = RandomState::new(2);
+    assert_moments(&rs.standard_exponential(&[N]), 1.0, 1.0, 0.02);
+    assert_moments(&rs.gamma(3.0, 2.0, &[N]), 6.0, 12.0, 0.02);
+    assert_moments(&rs.gamma(0.5, 1.0, &[N]), 0.5, 0.5, 0.02);
+    assert_moments(&rs.beta(2.0, 3.0, &[N]), 0.4, 0.04, 0.02);
+    assert_moments(&rs.chi_square(4.0, &[N]), 4.0, 8.0, 0.02);
+    assert_moments(&rs.student_t(10.0, &[N]), 0.0, 1.25, 0.02);
+    assert_moments(&rs.laplace(1.0, 2.0, &[N]), 1.0, 8.0, 0.02);
+    assert_moments(&rs.weibull(2.0, &[N]), 0.886226925452758, 0.214601836602552, 0.02);
+
+    // The Cauchy distribution has no mean, but its median is the location
+    let mut c: Vec<f64> = rs.cauchy(5.0, 1.0, &[N]).iter().collect();
+    c.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert!((c[N / 2] - 5.0).abs() < 0.02);
+    assert!((c[3 * N / 4] - 6.0).abs() < 0.05);
+
+    let t: Tensor<f32> = rs.gamma(2.0, 1.0, &[3, 4]);
+    assert!(t.shape() == &vec![3, 4] && t.iter().all(|x| x > 0.0));
+}
+
+#[test]
+fn random_discrete() {
+    let mut rs = RandomState::new(3);
+    for &lam in [0.0, 3.5, 40.0, 1000.0].iter() {
+        let t: Tensor<f64> = rs.poisson(lam, &[N]);
+        assert!(t.iter().all(|x| x >= 0.0 && x == x.floor()));
+        assert_moments(&t, lam, lam, 0.02);
+    }
+    for &(n, p) in [(10, 0.3), (200, 0.5), (1000, 0.9), (5, 1.0)].iter() {
+        let t: Tensor<f64> = rs.binomial(n, p, &[N]);
+        assert!(t.iter().all(|x| x >= 0.0 && x <= n as f64 && x == x.floor()));
+        assert_moments(&t, n as f64 * p, n as f64 * p * (1.0 - p), 0.02);
+    }
+    let t: Tensor<f64> = rs.geometric(0.25, &[N]);
+    assert!(t.iter().all(|x| x >= 1.0));
+    assert_moments(&t, 4.0, 12.0, 0.02);
+    assert_moments(&rs.bernoulli(0.3, &[N]), 0.3, 0.21, 0.02);
+
+    let t: Tensor<i64> = rs.poisson(2.0, &[5]);
+    assert!(t.iter().all(|x| x >= 0));
+    let t: Tensor<i32> = rs.randint(-3, 4, &[N]);
+    assert!(t.iter().all(|x| x >= -3 && x < 4));
+    assert!((-3..4).all(|v| t.iter().any(|x| x == v)));
+    let t: Tensor<usize> = rs.binomial(20, 0.5, &[2, 2]);
+    assert!(t.shape() == &vec![2, 2]);
+}
+
+#[test]
+fn random_multivariate_normal() {
+    let mut rs = RandomState::new(4);
+    let mean = Tensor::new(vec![1.0, -2.0, 0.5]);
+    let cov = Tensor::new(vec![4.0, 1.2, -0.6,
+                               1.2, 1.0, 0.3,
+                               -0.6, 0.3, 2.0]).reshape(&[3, 3]);
+    let x = rs.multivariate_normal(&mean, &cov, N).unwrap();
+    assert!(x.shape() == &vec![N, 3]);
+    let m: Vec<f64> = (0..3).map(|j| (0..N).map(|i| x[(i, j)]).sum::<f64>() / N as f64).collect();
+    for j in 0..3 {
+        assert!((m[j] - mean[(j,)]).abs() < 0.03);
+        for k in 0..3 {
+            let c = (0..N).map(|i| (x[(i, j)] - m[j]) * (x[(i, k)] - m[k])).sum::<f64>() / N as f64;
+            assert!((c - cov[(j, k)]).abs() < 0.05);
+        }
+    }
+
+    // A singular covariance: the second component is twice the first
+    let cov = Tensor::new(vec![1.0, 2.0, 2.0, 4.0]).reshape(&[2, 2]);
+    let x = rs.multivariate_normal(&Tensor::zeros(&[2]), &cov, 100).unwrap();
+    assert!((0..100).all(|i| (x[(i, 1)] - 2.0 * x[(i, 0)]).abs() < 1e-6));
+
+    let indefinite = Tensor::new(vec![1.0, 2.0, 2.0, 1.0]).reshape(&[2, 2]);
+    assert!(rs.multivariate_normal(&Tensor::zeros(&[2]), &indefinite, 10).is_err());
+    let asymmetric = Tensor::new(vec![1.0, 0.5, 0.0, 1.0]).reshape(&[2, 2]);
+    assert!(rs.multivariate_normal(&Tensor::zeros(&[2]), &asymmetric, 10).is_err());
+}
+
+#[test]
+fn random_dirichlet_multinomial() {
+    let mut rs = RandomState::new(5);
+    let alpha = Tensor::new(vec![1.0, 2.0, 7.0]);
+    let x = rs.dirichlet(&alpha, N);
+    assert!(x.shape() == &vec![N, 3]);
+    assert!((0..N).all(|i| ((0..3).map(|j| x[(i, j)]).sum::<f64>() - 1.0).abs() < 1e-12));
+    for j in 0..3 {
+        let m = (0..N).map(|i| x[(i, j)]).sum::<f64>() / N as f64;
+        assert!((m - alpha[(j,)] / 10.0).abs() < 0.005);
+    }
+
+    let pvals = Tensor::new(vec![0.2, 0.0, 0.5, 0.3]);
+    let c: Tensor<f64> = rs.multinomial(50, &pvals, &[N / 10, 10]);
+    assert!(c.shape() == &vec![N / 10, 10, 4]);
+    let c = c.reshape(&[N as isize, 4]);
+    assert!((0..N).all(|i| (0..4).map(|j| c[(i, j)]).sum::<f64>() == 50.0));
+    for j in 0..4 {
+        let m = (0..N).map(|i| c[(i, j)]).sum::<f64>() / N as f64;
+        assert!((m - 50.0 * pvals[(j,)]).abs() < 0.05);
+    }
+
+    let probs = Tensor::new(vec![1.0, 0.0, 3.0]);
+    let k: Tensor<usize> = rs.categorical(&probs, &[N]);
+    assert!(k.iter().all(|v| v == 0 || v == 2));
+    let frac = k.iter().filter(|&v| v == 2).count() as f64 / N as f64;
+    assert!((frac - 0.75).abs() < 0.01);
+}
+
+#[test]
+fn random_permutation_shuffle() {
+    let mut rs = RandomState::new(6);
+    let mut p: Vec<usize> = rs.permutation(50).iter().collect();
+    assert!(p != (0..50).collect::<Vec<_>>());
+    p.sort();
+    assert!(p == (0..50).collect::<Vec<_>>());
+    assert!(rs.permutation(0).size() == 0);
+
+    // Each row and column keeps its contents under permutation along the other axis
+    let t: Tensor<f64> = Tensor::range(24).reshape(&[2, 3, 4]);
+    let u = rs.permutation_of(&t, 2);
+    assert!(u.shape() == t.shape());
+    for i in 0..2 {
+        for j in 0..3 {
+            let mut row: Vec<f64> = (0..4).map(|k| u[(i, j, k)]).collect();
+            row.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            assert!(row == (0..4).map(|k| t[(i, j, k)]).collect::<Vec<_>>());
+        }
+    }
+
+    let mut s = t.clone();
+    rs.shuffle_axis(&mut s, 1);
+    for i in 0..2 {
+        for j in 0..3 {
+            // The slice at (i, j) is some slice (i, j') of the original
+            let src = (s[(i, j, 0)] as usize % 12) / 4;
+            assert!((0..4).all(|k| s[(i, j, k)] == t[(i, src, k)]));
+        }
+    }
+
+    // Shuffling along the first axis matches the permutation with the same seed
+    let mut a: Tensor<f64> = Tensor::range(30).reshape(&[10, 3]);
+    RandomState::new(7).shuffle(&mut a);
+    let p = RandomState::new(7).permutation(10);
+    assert!((0..10).all(|i| (0..3).all(|j| a[(i, j)] == (3 * p[(i,)] + j) as f64)));
+}
+
+#[test]
+fn random_choice() {
+    let mut rs = RandomState::new(8);
+    let a: Tensor<f64> = Tensor::range(10);
+
+    let c = rs.choice(&a, &[4, 5], true, None);
+    assert!(c.shape() == &vec![4, 5]);
+    assert!(c.iter().all(|x| x >= 0.0 && x < 10.0));
+
+    let mut c: Vec<f64> = rs.choice(&a, &[10], false, None).iter().collect();
+    c.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert!(c == a.iter().collect::<Vec<_>>());
+
+    let w = Tensor::new(vec![1.0, 0.0, 2.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 7.0]);
+    let c = rs.choice(&a, &[N], true, Some(&w));
+    assert!(c.iter().all(|x| x == 0.0 || x == 2.0 || x == 9.0));
+    for &(v, p) in [(0.0, 0.1), (2.0, 0.2), (9.0, 0.7)].iter() {
+        let frac = c.iter().filter(|&x| x == v).count() as f64 / N as f64;
+        assert!((frac - p).abs() < 0.01);
+    }
+
+    // Without replacement, the first draw follows the weights
+    let mut first = 0;
+    for _ in 0..10000 {
+        let c = rs.choice(&a, &[3], false, Some(&w));
+        let mut v: Vec<f64> = c.iter().collect();
+        first += (v[0] == 9.0) as usize;
+        v.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert!(v == vec![0.0, 2.0, 9.0]);
+    }
+    assert!((first as f64 / 10000.0 - 0.7).abs() < 0.02);
+    assert!(rs.choice(&a, &[0], false, None).size() == 0);
+}
+
+#[test]
+#[should_panic]
+fn random_choice_too_many() {
+    let a: Tensor<f64> = Tensor::range(3);
+    RandomState::new(1).choice(&a, &[4], false, None);
+}
+
+#[test]
+fn random_reservoir_sample() {
+    let mut rs = RandomState::new(9);
+    assert!(rs.reservoir_sample(0..3, 5) == vec![0, 1, 2]);
+    let mut counts = vec![0; 20];
+    for _ in 0..10000 {
+        let s = rs.reservoir_sample((0..20).map(|i| i * 2), 5);
+        assert!(s.len() == 5);
+        for x in s {
+            counts[x / 2] += 1;
+        }
+    }
+    // Each item is kept with probability 1/4
+    assert!(counts.iter().all(|&c| (c as f64 / 10000.0 - 0.25).abs() < 0.03));
+}
+
+#[test]
+fn random_bit_generators() {
+    // Reference outputs of pcg64_srandom_r(42, 54), Philox4x64-10 and xoshiro256++
+    let mut g = Pcg64::new(42, 54);
+    let v: Vec<u64> = (0..6).map(|_| g.next_u64()).collect();
+    assert!(v == vec![0x86b1da1d72062b68, 0x1304aa46c9853d39, 0xa3670e9e0dd50358,
+                      0xf9090e529a7dae00, 0xc85b9fd837996f2c, 0x606121f8e3919196]);
+
+    let mut g = Philox4x64::new([0, 0], [0; 4]);
+    let v: Vec<u64> = (0..4).map(|_| g.next_u64()).collect();
+    assert!(v == vec![0x16554d9eca36314c, 0xdb20fe9d672d0fdc, 0xd7e772cee186176b,
+                      0x7e68b68aec7ba23b]);
+    let mut g = Philox4x64::new([0x452821e638d01377, 0xbe5466cf34e90c6c],
+                                [0x243f6a8885a308d3, 0x13198a2e03707344,
+                                 0xa4093822299f31d0, 0x082efa98ec4e6c89]);
+    let v: Vec<u64> = (0..4).map(|_| g.next_u64()).collect();
+    assert!(v == vec![0xa528f45403e61d95, 0x38c72dbd566e9788, 0xa5a1610e72fd18b5,
+                      0x57bd43b5e52b7fe6]);
+
+    let mut g = Xoshiro256PlusPlus::new([1, 2, 3, 4]);
+    let v: Vec<u64> = (0..4).map(|_| g.next_u64()).collect();
+    assert!(v == vec![41943041, 58720359, 3588806011781223, 3591011842654386]);
+}
+
+#[test]
+fn random_reproducible() {
+    // These values must not change between versions
+    let t: Tensor<f64> = RandomState::new(1234).uniform(0.0, 1.0, &[3]);
+    assert!(t == Tensor::new(vec![0.7812905187199857, 0.1809218601375071, 0.7805691944270181]));
+    let t: Tensor<f64> = RandomState::<Xoshiro256PlusPlus>::from_seed(1234).uniform(0.0, 1.0, &[3]);
+    assert!(t == Tensor::new(vec![0.7570482775828282, 0.4243049356232066, 0.9538300022966579]));
+    let t: Tensor<f64> = RandomState::<Philox4x64>::from_seed(1234).uniform(0.0, 1.0, &[3]);
+    assert!(t == Tensor::new(vec![0.3965462722256661, 0.24041120211834144, 0.23883499037321365]));
+    let t: Tensor<i32> = RandomState::new(1234).uniform(-5, 100, &[6]);
+    assert!(t == Tensor::new(vec![77, 13, 76, 10, 97, 35]));
+}
+
+#[test]
+fn random_jump_spawn() {
+    let mut a = Pcg64::from_seed(5);
+    let mut b = a.clone();
+    a.advance(1000);
+    for _ in 0..1000 {
+        b.next_u64();
+    }
+    assert!(a == b);
+
+    // Jumping Philox moves to the counter 2^128 blocks ahead
+    let mut a = Philox4x64::new([7, 8], [0; 4]);
+    a.next_u64();
+    a.jump();
+    let mut b = Philox4x64::new([7, 8], [0, 0, 1, 0]);
+    b.next_u64();
+    assert!(a.next_u64() == b.next_u64());
+
+    let mut a = Xoshiro256PlusPlus::from_seed(5);
+    let b = a.clone();
+    a.jump();
+    assert!(a != b);
+
+    let mut rs = RandomState::new(11);
+    let mut first = rs.clone();
+    let mut second = rs.clone();
+    second.jump();
+    let mut children = rs.spawn(3);
+    assert!(children.len() == 3);
+    // The first child continues the parent's stream, the others start at successive jumps
+    let x: Tensor<f64> = first.uniform(0.0, 1.0, &[10]);
+    assert!(children[0].uniform(0.0, 1.0, &[10]) == x);
+    assert!(children[1].uniform(0.0, 1.0, &[10]) == second.uniform(0.0, 1.0, &[10]));
+    let y: Tensor<f64> = children[2].uniform(0.0, 1.0, &[10]);
+    let z: Tensor<f64> = rs.uniform(0.0, 1.0, &[10]);
+    assert!(x != y && y != z && x != z);
+}
+
+#[test]
+fn random_get_set_state() {
+    let mut rs = RandomState::new(12);
+    rs.standard_exponential::<f64>(&[7]);
+    let state = rs.get_state();
+    let a: Tensor<f64> = rs.gamma(2.0, 1.0, &[20]);
+    let mut rs2 = RandomState::new(0);
+    rs2.set_state(&state);
+    assert!(rs2.gamma(2.0, 1.0, &[20]) == a);
+
+    // Philox restores its position within a block of outputs
+    let mut rs = RandomState::<Philox4x64>::from_seed(3);
+    rs.uniform(0.0, 1.0, &[3]);
+    let mut rs2 = RandomState::<Philox4x64>::from_seed(0);
+    rs2.set_state(&rs.get_state());
+    assert!(rs.uniform(0.0, 1.0, &[10]) == rs2.uniform(0.0, 1.0, &[10]));
+
+    let mut rs = RandomState::<Xoshiro256PlusPlus>::from_seed(3);
+    let mut rs2 = RandomState::<Xoshiro256PlusPlus>::from_seed(4);
+    rs2.set_state(&rs.get_state());
+    assert!(rs.uniform(0, 1000, &[10]) == rs2.uniform(0, 1000, &[10]));
+}
+
+#[test]
+#[should_panic]
+fn random_set_state_mismatch() {
+    let state = RandomState::<Xoshiro256PlusPlus>::from_seed(3).get_state();
+    RandomState::new(1).set_state(&state);
+}
+
+#[cfg(feature = "serde-serialize")]
+#[test]
+fn random_serde() {
+    use serde_json;
+
+    let mut rs = RandomState::new(21);
+    rs.uniform(0.0, 1.0, &[5]);
+    let json = serde_json::to_string(&rs).unwrap();
+    let mut rs2: RandomState = serde_json::from_str(&json).unwrap();
+    assert!(rs.uniform(0.0, 1.0, &[10]) == rs2.uniform(0.0, 1.0, &[10]));
+
+    let mut rs = RandomState::<Philox4x64>::from_seed(21);
+    rs.uniform(0.0, 1.0, &[5]);
+    let json = serde_json::to_string(&rs).unwrap();
+    let mut rs2: RandomState<Philox4x64> = serde_json::from_str(&json).unwrap();
+    assert!(rs.uniform(0.0, 1.0, &[10]) == rs2.uniform(0.0, 1.0, &[10]));
+    assert!(serde_json::from_str::<RandomState<Xoshiro256PlusPlus>>(&json).is_err());
+
+    // The serialized form is the state vector, and malformed states are rejected
+    let state: Vec<u64> = rs.get_state().iter().collect();
+    assert!(serde_json::to_string(&rs).unwrap() == serde_json::to_string(&state).unwrap());
+    assert!(serde_json::from_str::<RandomState<Philox4x64>>("[3, 1, 2, 0, 0, 0, 0, 5]").is_err());
+    assert!(serde_json::from_str::<RandomState<Xoshiro256PlusPlus>>("[2, 0, 0, 0, 0]").is_err());
+    assert!(serde_json::from_str::<RandomState>("[1, 0, 7, 0, 4]").is_err());
+    assert!(serde_json::from_str::<RandomState>("[1, 0, 7, 0, 5]").is_ok());
+}
+
+#[test]
+fn random_ziggurat() {
+    let mut rs = RandomState::new(13);
+    let n = 10 * N;
+    let z: Tensor<f64> = rs.standard_normal(&[n]);
+    assert!(z.iter().all(|x| x.is_finite()));
+    assert_moments(&z, 0.0, 1.0, 0.01);
+    // Probability mass in the Ziggurat tail (beyond R = 3.654) and below one
+    let tail = z.iter().filter(|x| x.abs() > 3.6541528853610088).count() as f64 / n as f64;
+    assert!((tail - 2.58e-4).abs() < 5e-5);
+    let below = z.iter().filter(|&x| x < 1.0).count() as f64 / n as f64;
+    assert!((below - 0.8413447460685429).abs() < 2e-3);
+
+    let e: Tensor<f64> = rs.standard_exponential(&[n]);
+    assert!(e.iter().all(|x| x >= 0.0 && x.is_finite()));
+    assert_moments(&e, 1.0, 1.0, 0.01);
+    let tail = e.iter().filter(|&x| x > 7.69711747013104972).count() as f64 / n as f64;
+    assert!((tail - 4.54e-4).abs() < 7e-5);
+
+    // The tables are constants, so the samples are the same on every platform
+    let mut rs = RandomState::new(42);
+    let z: Tensor<f64> = rs.standard_normal(&[4]);
+    let e: Tensor<f64> = rs.standard_exponential(&[4]);
+    assert!(z == Tensor::new(vec![1.3848236998371548, 1.8167675876033496, -2.5432215263603917,
+                                  0.5475557948646401]));
+    assert!(e == Tensor::new(vec![0.06929641352084046, 1.7291427794170993, 2.9769602636167583,
+                                  0.4431487211352976]));
+}
+
+#[test]
+fn random_fill() {
+    let mut rs = RandomState::new(14);
+    let mut t: Tensor<f64> = Tensor::zeros(&[N]);
+    rs.fill_normal(&mut t, 3.0, 2.0);
+    assert_moments(&t, 3.0, 4.0, 0.02);
+    rs.fill_gamma(&mut t, 3.0, 2.0);
+    assert_moments(&t, 6.0, 12.0, 0.02);
+    rs.fill_poisson(&mut t, 3.5);
+    assert_moments(&t, 3.5, 3.5, 0.02);
+
+    // Filling gives the same samples as generating a new tensor
+    let mut a: Tensor<f32> = Tensor::zeros(&[4, 5]);
+    RandomState::new(15).fill_beta(&mut a, 2.0, 3.0);
+    assert!(a == RandomState::new(15).beta(2.0, 3.0, &[4, 5]));
+    let mut a: Tensor<i32> = Tensor::zeros(&[4, 5]);
+    RandomState::new(15).fill_uniform(&mut a, -10, 10);
+    assert!(a == RandomState::new(15).uniform(-10, 10, &[4, 5]));
+    let mut a: Tensor<usize> = Tensor::zeros(&[6]);
+    RandomState::new(15).fill_categorical(&mut a, &Tensor::new(vec![1.0, 2.0]));
+    assert!(a == RandomState::new(15).categorical(&Tensor::new(vec![1.0, 2.0]), &[6]));
+
+    let mean = Tensor::new(vec![1.0, -1.0]);
+    let cov = Tensor::new(vec![2.0, 0.5, 0.5, 1.0]).reshape(&[2, 2]);
+    let mut x = Tensor::zeros(&[10, 2]);
+    RandomState::new(16).fill_multivariate_normal(&mut x, &mean, &cov).unwrap();
+    assert!(x == RandomState::new(16).multivariate_normal(&mean, &cov, 10).unwrap());
+    let mut x = Tensor::zeros(&[10, 2]);
+    RandomState::new(16).fill_dirichlet(&mut x, &Tensor::new(vec![1.0, 1.0]));
+    assert!(x == RandomState::new(16).dirichlet(&Tensor::new(vec![1.0, 1.0]), 10));
+    let mut x: Tensor<f64> = Tensor::zeros(&[3, 2]);
+    rs.fill_multinomial(&mut x, 10, &Tensor::new(vec![0.5, 0.5]));
+    assert!((0..3).all(|i| x[(i, 0)] + x[(i, 1)] == 10.0));
+}
+
+fn mean(t: &Tensor<f64>) -> f64 {
+    t.iter().sum::<f64>() / t.size() as f64
+}
+
+#[test]
+fn random_bootstrap() {
+    let mut rs = RandomState::new(17);
+    let t: Tensor<f64> = rs.normal(5.0, 2.0, &[400]);
+    let res = rs.bootstrap(&t, 2000, 0, mean);
+    assert!(res.distribution.shape() == &vec![2000]);
+    assert!(res.estimate == mean(&t));
+    // The standard error of the mean is sigma / sqrt(n) = 0.1
+    assert!((res.standard_error - 0.1).abs() < 0.015);
+    let (low, high) = res.confidence_interval(0.95);
+    assert!(low < res.estimate && res.estimate < high);
+    assert!((high - low - 2.0 * 1.96 * 0.1).abs() < 0.06);
+    let (low90, high90) = res.confidence_interval(0.9);
+    assert!(low < low90 && high90 < high);
+
+    // Reproducible, and resampling rows of a matrix keeps them intact
+    let x: Tensor<f64> = Tensor::range(20).reshape(&[10, 2]);
+    let stat = |m: &Tensor<f64>| {
+        assert!(m.shape() == &vec![10, 2]);
+        assert!((0..10).all(|i| m[(i, 1)] == m[(i, 0)] + 1.0));
+        m[(0, 0)]
+    };
+    let a = RandomState::new(18).bootstrap(&x, 50, 0, &stat);
+    let b = RandomState::new(18).bootstrap(&x, 50, 0, &stat);
+    assert!(a.distribution == b.distribution);
+}
+
+#[test]
+fn random_jackknife() {
+    let t = Tensor::new(vec![2.0, 4.0, 4.0, 5.0, 7.0, 9.0]);
+    let res = jackknife(&t, 0, mean);
+    assert!(res.values.size() == 6);
+    // The mean is unbiased, and its jackknife standard error is the usual s / sqrt(n)
+    assert!(res.bias.abs() < 1e-12);
+    let m = mean(&t);
+    let s2 = t.iter().map(|x| (x - m) * (x - m)).sum::<f64>() / 5.0;
+    assert!((res.standard_error - (s2 / 6.0).sqrt()).abs() < 1e-12);
+
+    // The plug-in variance has bias -sigma^2 / n, which the jackknife estimates exactly
+    let var = |x: &Tensor<f64>| {
+        let m = mean(x);
+        x.iter().map(|v| (v - m) * (v - m)).sum::<f64>() / x.size() as f64
+    };
+    let res = jackknife(&t, 0, var);
+    assert!((res.estimate - res.bias - s2).abs() < 1e-12);
+}
+
+#[test]
+fn random_kfold() {
+    let mut rs = RandomState::new(19);
+    let folds = rs.kfold(23, 5);
+    assert!(folds.len() == 5);
+    let mut seen = vec![0; 23];
+    for &(ref train, ref test) in folds.iter() {
+        assert!(test.size() == 4 || test.size() == 5);
+        assert!(train.size() + test.size() == 23);
+        assert!(test.iter().all(|i| train.iter().all(|j| i != j)));
+        for i in test.iter() {
+            seen[i] += 1;
+        }
+    }
+    assert!(seen.iter().all(|&c| c == 1));
+
+    let labels = Tensor::new(vec![0, 0, 0, 0, 0, 0, 1, 1, 1, 2, 2, 2, 0, 0, 0, 1, 1, 1]);
+    let folds = rs.stratified_kfold(&labels, 3);
+    let mut seen = vec![0; 18];
+    for &(_, ref test) in folds.iter() {
+        assert!(test.size() == 6);
+        // Each fold has 3 zeros, 2 ones and 1 two
+        for &(label, count) in [(0, 3), (1, 2), (2, 1)].iter() {
+            assert!(test.iter().filter(|&i| labels[(i,)] == label).count() == count);
+        }
+        for i in test.iter() {
+            seen[i] += 1;
+        }
+    }
+    assert!(seen.iter().all(|&c| c == 1));
+}
@@ -0,0 +1,89 @@
+use numeric::Tensor;
+use numeric::signal::{convolve, correlate, fftconvolve, convolve_nd, ConvMode, Boundary};
+
+fn assert_close(a: &Tensor<f64>, b: &Tensor<f64>) {
+    assert!(a.shape() == b.shape());
+    assert!(a.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() < 1e-9));
+}
+
+#[test]
+fn convolve_modes() {
+    let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0]);
+    let v = Tensor::new(vec![1.0, -1.0, 2.0]);
+    assert!(convolve(&a, &v, ConvMode::Full) ==
+            Tensor::new(vec![1.0, 1.0, 3.0, 5.0, 2.0, 8.0]));
+    assert!(convolve(&a, &v, ConvMode::Same) == Tensor::new(vec![1.0, 3.0, 5.0, 2.0]));
+    assert!(convolve(&a, &v, ConvMode::Valid) == Tensor::new(vec![3.0, 5.0]));
+    // Commutative, also when the second input is longer
+    for &mode in [ConvMode::Full, ConvMode::Same, ConvMode::Valid].iter() {
+        assert!(convolve(&a, &v, mode) == convolve(&v, &a, mode));
+    }
+
+    let i: Tensor<i32> = Tensor::new(vec![1, 2, 3]);
+    assert!(convolve(&i, &Tensor::new(vec![1, 1]), ConvMode::Full) ==
+            Tensor::new(vec![1, 3, 5, 3]));
+}
+
+#[test]
+fn correlate_modes() {
+    let a = Tensor::new(vec![1.0, 2.0, 3.0]);
+    let v = Tensor::new(vec![0.0, 1.0, 0.5]);
+    assert!(correlate(&a, &v, ConvMode::Full) == Tensor::new(vec![0.5, 2.0, 3.5, 3.0, 0.0]));
+    assert!(correlate(&a, &v, ConvMode::Same) == Tensor::new(vec![2.0, 3.5, 3.0]));
+    assert!(correlate(&a, &v, ConvMode::Valid) == Tensor::new(vec![3.5]));
+}
+
+#[test]
+fn fftconvolve_1d() {
+    let a: Tensor<f64> = Tensor::new((0..50).map(|k| (k as f64 * 0.3).sin()).collect());
+    let v: Tensor<f64> = Tensor::new((0..13).map(|k| 1.0 / (1.0 + k as f64)).collect());
+    for &mode in [ConvMode::Full, ConvMode::Same, ConvMode::Valid].iter() {
+        assert_close(&fftconvolve(&a, &v, mode), &convolve(&a, &v, mode));
+    }
+}
+
+#[test]
+fn fftconvolve_2d() {
+    let a: Tensor<f64> = Tensor::range(12).reshape(&[3, 4]);
+    let v = Tensor::new(vec![1.0, 2.0, 0.0, -1.0]).reshape(&[2, 2]);
+    let full = fftconvolve(&a, &v, ConvMode::Full);
+    assert!(full.shape() == &vec![4, 5]);
+    // Check a few entries: y[i, j] = sum a[i - p, j - q] v[p, q]
+    assert!((full[(0, 0)] - 0.0).abs() < 1e-9);
+    assert!((full[(1, 1)] - (5.0 + 2.0 * 4.0 - 0.0)).abs() < 1e-9);
+    assert!((full[(3, 4)] - -11.0).abs() < 1e-9);
+
+    let valid = fftconvolve(&a, &v, ConvMode::Valid);
+    assert!(valid.shape() == &vec![2, 3]);
+    assert_close(&valid, &full.index(&[::numeric::StridedSlice(Some(1), Some(3), 1),
+                                       ::numeric::StridedSlice(Some(1), Some(4), 1)])
+                               .canonize());
+}
+
+#[test]
+fn convolve_nd_1d() {
+    let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0]);
+    let k = Tensor::new(vec![1.0, -1.0, 2.0]);
+    assert!(convolve_nd(&a, &k, Boundary::Zero) == convolve(&a, &k, ConvMode::Same));
+    // Extended inputs: [2 1 | 1 2 3 4 | 4 3], [4 | ... | 1], [1 | ... | 4]
+    assert!(convolve_nd(&a, &k, Boundary::Reflect) == Tensor::new(vec![3.0, 3.0, 5.0, 6.0]));
+    assert!(convolve_nd(&a, &k, Boundary::Wrap) == Tensor::new(vec![9.0, 3.0, 5.0, 3.0]));
+    assert!(convolve_nd(&a, &k, Boundary::Nearest) == Tensor::new(vec![3.0, 3.0, 5.0, 6.0]));
+}
+
+#[test]
+fn convolve_nd_2d() {
+    let a: Tensor<f64> = Tensor::range(9).reshape(&[3, 3]);
+    let k = Tensor::new(vec![0.0, 1.0, 0.0,
+                             1.0, 1.0, 1.0,
+                             0.0, 1.0, 0.0]).reshape(&[3, 3]);
+    let zero = convolve_nd(&a, &k, Boundary::Zero);
+    assert!(zero == Tensor::new(vec![4.0, 7.0, 8.0,
+                                     13.0, 20.0, 19.0,
+                                     16.0, 25.0, 20.0]).reshape(&[3, 3]));
+    let wrap = convolve_nd(&a, &k, Boundary::Wrap);
+    // Each element contributes to itself and four periodic neighbours
+    assert!((wrap.sum() - 5.0 * a.sum()).abs() < 1e-9);
+    let nearest = convolve_nd(&a, &k, Boundary::Nearest);
+    assert!(nearest[(0, 0)] == 0.0 + 0.0 + 0.0 + 1.0 + 3.0);
+}
@@ -0,0 +1,135 @@
+use numeric::Tensor;
+use numeric::sparse::{CooMatrix, CsrMatrix, CscMatrix};
+
+fn dense() -> Tensor<f64> {
+    Tensor::new(vec![1.0, 0.0, 2.0, 0.0,
+                     0.0, 0.0, 3.0, 0.0,
+                     4.0, 5.0, 0.0, 6.0]).reshape(&[3, 4])
+}
+
+#[test]
+fn coo_duplicates() {
+    let mut coo = CooMatrix::new(&[2, 2]);
+    coo.push(1, 0, 1.0);
+    coo.push(0, 1, 2.0);
+    coo.push(1, 0, 3.0);
+    assert_eq!(coo.nnz(), 3);
+
+    let csr = coo.to_csr();
+    assert_eq!(csr.nnz(), 2);
+    assert_eq!(csr.indptr(), &[0, 1, 2]);
+    assert!(csr.to_tensor() == Tensor::new(vec![0.0, 2.0, 4.0, 0.0]).reshape(&[2, 2]));
+    assert!(coo.to_csc().to_tensor() == csr.to_tensor());
+    assert!(coo.to_tensor() == csr.to_tensor());
+}
+
+#[test]
+fn conversions() {
+    let a = dense();
+    let csr = CsrMatrix::from_tensor(&a);
+    let csc = CscMatrix::from_tensor(&a);
+    assert_eq!(csr.nnz(), 6);
+    assert_eq!(csr.shape(), vec![3, 4]);
+    assert_eq!(csc.shape(), vec![3, 4]);
+    assert_eq!(csr.indices(), &[0, 2, 2, 0, 1, 3]);
+    assert_eq!(csc.indptr(), &[0, 2, 3, 5, 6]);
+
+    assert!(csr.to_tensor() == a);
+    assert!(csc.to_tensor() == a);
+    assert!(csr.to_csc() == csc);
+    assert!(csc.to_csr() == csr);
+    assert!(csr.to_coo().to_tensor() == a);
+    assert!(csc.to_coo().to_csr() == csr);
+    assert!(CooMatrix::from_tensor(&a).to_tensor() == a);
+
+    assert_eq!(csr.get(2, 1), 5.0);
+    assert_eq!(csr.get(1, 1), 0.0);
+    assert_eq!(csc.get(2, 3), 6.0);
+}
+
+#[test]
+fn new_unsorted() {
+    let csr = CsrMatrix::new(&[2, 3], vec![0, 3, 4], vec![2, 0, 2, 1], vec![1, 2, 3, 4]);
+    assert_eq!(csr.indices(), &[0, 2, 1]);
+    assert_eq!(csr.data(), &[2, 4, 4]);
+}
+
+#[test]
+fn transpose() {
+    let a = dense();
+    let at = a.transpose().canonize();
+    assert!(CsrMatrix::from_tensor(&a).transpose().to_tensor() == at);
+    assert!(CscMatrix::from_tensor(&a).transpose().to_tensor() == at);
+    assert!(CooMatrix::from_tensor(&a).transpose().to_tensor() == at);
+}
+
+#[test]
+fn add_sub_scale() {
+    let a = dense();
+    let b = Tensor::new(vec![0.0, 1.0, -2.0, 0.0,
+                             0.0, 0.0, 0.0, 0.0,
+                             1.0, 0.0, 0.0, 0.0]).reshape(&[3, 4]);
+    let (sa, sb) = (CsrMatrix::from_tensor(&a), CsrMatrix::from_tensor(&b));
+    assert!((&sa + &sb).to_tensor() == &a + &b);
+    assert!((&sa - &sb).to_tensor() == &a - &b);
+    assert!(sa.scale(2.0).to_tensor() == &a * 2.0);
+
+    let (ca, cb) = (CscMatrix::from_tensor(&a), CscMatrix::from_tensor(&b));
+    assert!((&ca + &cb).to_tensor() == &a + &b);
+    assert!((&ca - &cb).to_tensor() == &a - &b);
+    assert!(ca.scale(2.0).to_tensor() == &a * 2.0);
+}
+
+#[test]
+fn dot_dense() {
+    let a = dense();
+    let x = Tensor::new(vec![1.0, 2.0, 3.0, 4.0]);
+    let y = Tensor::new(vec![7.0, 9.0, 38.0]);
+    assert!(CsrMatrix::from_tensor(&a).dot(&x) == y);
+    assert!(CscMatrix::from_tensor(&a).dot(&x) == y);
+
+    let m: Tensor<f64> = Tensor::range(8).reshape(&[4, 2]);
+    assert!(CsrMatrix::from_tensor(&a).dot(&m) == a.dot(&m));
+    assert!(CscMatrix::from_tensor(&a).dot(&m) == a.dot(&m));
+}
+
+#[test]
+fn dot_sparse() {
+    let a = dense();
+    let b: Tensor<f64> = Tensor::new(vec![1.0, 0.0, 0.0,
+                                          0.0, 2.0, 0.0,
+                                          0.0, 0.0, 0.0,
+                                          3.0, 0.0, 1.0]).reshape(&[4, 3]);
+    let ab = a.dot(&b);
+    let c = CsrMatrix::from_tensor(&a).dot_sparse(&CsrMatrix::from_tensor(&b));
+    assert!(c.to_tensor() == ab);
+    let c = CscMatrix::from_tensor(&a).dot_sparse(&CscMatrix::from_tensor(&b));
+    assert!(c.to_tensor() == ab);
+}
+
+#[test]
+fn slicing() {
+    let a = dense();
+    for s in [CsrMatrix::from_tensor(&a).to_coo(), CscMatrix::from_tensor(&a).to_coo()].iter() {
+        let csr = s.to_csr();
+        let csc = s.to_csc();
+        let rows = Tensor::new(vec![0.0, 0.0, 3.0, 0.0,
+                                    4.0, 5.0, 0.0, 6.0]).reshape(&[2, 4]);
+        let cols = Tensor::new(vec![0.0, 2.0,
+                                    0.0, 3.0,
+                                    5.0, 0.0]).reshape(&[3, 2]);
+        assert!(csr.slice_rows(1, 3).to_tensor() == rows);
+        assert!(csc.slice_rows(1, 3).to_tensor() == rows);
+        assert!(csr.slice_cols(1, 3).to_tensor() == cols);
+        assert!(csc.slice_cols(1, 3).to_tensor() == cols);
+    }
+}
+
+#[test]
+fn sum_axis() {
+    let a = dense();
+    for &axis in [0, 1].iter() {
+        assert!(CsrMatrix::from_tensor(&a).sum_axis(axis) == a.sum_axis(axis));
+        assert!(CscMatrix::from_tensor(&a).sum_axis(axis) == a.sum_axis(axis));
+    }
+}
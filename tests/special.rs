@@ -0,0 +1,159 @@
+use numeric::Tensor;
+use numeric::special;
+use numeric::special::scalar;
+
+/// Checks `f` against a table of reference values (computed with mpmath) to a relative tolerance.
+fn check<F: Fn(f64) -> f64>(f: F, table: &[(f64, f64)], tol: f64) {
+    for &(x, expected) in table {
+        let y = f(x);
+        assert!((y - expected).abs() <= tol * expected.abs(),
+                "f({}) = {}, expected {}", x, y, expected);
+    }
+}
+
+#[test]
+fn gamma() {
+    check(scalar::gamma, &[(0.1, 9.51350769866873), (0.5, 1.772453850905516), (1.0, 1.0),
+                           (2.5, 1.329340388179137), (5.0, 24.0), (10.3, 716430.6890623764),
+                           (30.0, 8.841761993739702e+30), (-0.5, -3.544907701811032),
+                           (-2.7, -0.931082784838964)], 1e-13);
+    check(scalar::gamma, &[(170.5, 5.56209241456e+305)], 1e-12);
+    assert!(scalar::gamma(0.0).is_nan());
+    assert!(scalar::gamma(-3.0).is_nan());
+    assert!(scalar::gamma(200.0) == ::std::f64::INFINITY);
+}
+
+#[test]
+fn lgamma() {
+    check(scalar::lgamma, &[(0.1, 2.252712651734206), (0.5, 0.5723649429247001),
+                            (3.0, 0.6931471805599453), (12.5, 18.734347511936445),
+                            (100.0, 359.1342053695754), (1000.0, 5905.220423209181),
+                            (100000.0, 1051287.7089736569), (-0.5, 1.2655121234846454),
+                            (-2.7, -0.07140708531564569)], 1e-13);
+    assert!(scalar::lgamma(1.0) == 0.0 && scalar::lgamma(2.0) == 0.0);
+}
+
+#[test]
+fn digamma() {
+    check(scalar::digamma, &[(0.1, -10.423754940411076), (0.5, -1.9635100260214235),
+                             (1.0, -0.5772156649015329), (2.0, 0.42278433509846713),
+                             (7.3, 1.9178203356379862), (30.0, 3.384438132685525),
+                             (1000.0, 6.907255195648812), (-0.5, 0.03648997397857652),
+                             (-2.7, -1.1153471291406896)], 1e-13);
+}
+
+#[test]
+fn erf() {
+    check(scalar::erf, &[(0.01, 0.011283415555849618), (0.3, 0.3286267594591274),
+                         (0.5, 0.5204998778130465), (1.0, 0.8427007929497149),
+                         (1.5, 0.9661051464753108), (2.0, 0.9953222650189527),
+                         (3.0, 0.9999779095030014), (-1.2, -0.9103139782296353)], 1e-14);
+    check(scalar::erfc, &[(0.1, 0.887537083981715), (0.5, 0.4795001221869535),
+                          (1.0, 0.15729920705028513), (2.0, 0.004677734981047266),
+                          (3.0, 2.209049699858544e-05), (5.0, 1.537459794428035e-12),
+                          (10.0, 2.088487583762545e-45), (20.0, 5.395865611607901e-176),
+                          (-1.0, 1.8427007929497148)], 1e-13);
+}
+
+#[test]
+fn erfinv() {
+    check(scalar::erfinv, &[(0.1, 0.08885599049425769), (0.5, 0.4769362762044699),
+                            (0.9, 1.1630871536766743), (0.999, 2.3267537655135246),
+                            (-0.3, -0.2724627147267543)], 1e-14);
+    check(scalar::erfinv, &[(0.999999, 3.458910737275499)], 1e-10);
+    assert!(scalar::erfinv(1.0) == ::std::f64::INFINITY);
+    assert!(scalar::erfinv(1.5).is_nan());
+}
+
+#[test]
+fn beta() {
+    check(|b| scalar::beta(2.0, b), &[(3.0, 0.08333333333333333)], 1e-14);
+    check(|b| scalar::beta(0.5, b), &[(0.5, 3.141592653589793)], 1e-14);
+    check(|b| scalar::beta(60.0, b), &[(70.0, 4.770685538608857e-40)], 1e-11);
+}
+
+#[test]
+fn incomplete() {
+    let p = [(0.5, 0.2, 0.4729107431344619), (1.0, 1.0, 0.6321205588285577),
+             (3.0, 2.0, 0.32332358381693654), (3.0, 10.0, 0.9972306042844884),
+             (50.0, 45.0, 0.24680203440017026), (0.1, 5.0, 0.9998560610341533)];
+    for &(a, x, expected) in p.iter() {
+        assert!((scalar::gammainc(a, x) - expected).abs() < 1e-13 * expected);
+    }
+    let q = [(0.5, 0.2, 0.5270892568655381), (3.0, 10.0, 0.002769395715511576),
+             (2.0, 30.0, 2.900863120340454e-12)];
+    for &(a, x, expected) in q.iter() {
+        assert!((scalar::gammaincc(a, x) - expected).abs() < 1e-12 * expected);
+    }
+    let i = [(1.0, 1.0, 0.3, 0.3), (2.0, 3.0, 0.4, 0.5248), (0.5, 0.5, 0.1, 0.20483276469913345),
+             (10.0, 20.0, 0.5, 0.9692858271300793), (5.0, 2.0, 0.9, 0.885735)];
+    for &(a, b, x, expected) in i.iter() {
+        assert!((scalar::betainc(a, b, x) - expected).abs() < 1e-13 * expected);
+    }
+}
+
+#[test]
+fn bessel() {
+    check(scalar::j0, &[(0.1, 0.99750156206604), (1.0, 0.7651976865579666),
+                        (5.0, -0.1775967713143383), (10.0, -0.24593576445134835),
+                        (24.0, -0.056230274166859266), (30.0, -0.08636798358104021),
+                        (100.0, 0.019985850304223122)], 1e-12);
+    assert!(scalar::j0(2.404825557695773).abs() < 1e-15);
+    check(scalar::j1, &[(0.1, 0.049937526036242), (1.0, 0.4400505857449335),
+                        (3.0, 0.3390589585259365), (8.0, 0.23463634685391463),
+                        (20.0, 0.06683312417585005), (26.0, 0.015045730586915811),
+                        (50.0, -0.09751182812517514), (-2.0, -0.5767248077568734)], 1e-12);
+    check(scalar::y0, &[(0.1, -1.5342386513503667), (1.0, 0.08825696421567696),
+                        (3.0, 0.3768500100127904), (8.0, 0.22352148938756622),
+                        (20.0, 0.06264059680938383), (26.0, 0.012044625860755602),
+                        (50.0, -0.09806499547007708)], 1e-12);
+    check(scalar::y1, &[(0.1, -6.4589510947020266), (1.0, -0.7812128213002887),
+                        (3.0, 0.3246744247918), (8.0, -0.1580604617312475),
+                        (20.0, -0.1655116143625213), (26.0, -0.15579655322960265),
+                        (50.0, -0.05679566856201477)], 1e-12);
+    check(scalar::i0, &[(0.1, 1.0025015629340956), (1.0, 1.2660658777520084),
+                        (5.0, 27.239871823604446), (20.0, 43558282.559553534),
+                        (100.0, 1.0737517071310738e+42)], 1e-13);
+    check(scalar::k0, &[(0.01, 4.721244730161095), (0.5, 0.9244190712276659),
+                        (1.0, 0.42102443824070834), (2.0, 0.11389387274953344),
+                        (10.0, 1.778006231616765e-05), (50.0, 3.4101677497894956e-23)], 1e-13);
+
+    let jn = [(2, 1.0, 0.11490348493190047), (5, 3.0, 0.043028434877047585),
+              (10, 1.0, 2.6306151236874534e-10), (3, 30.0, 0.129211228759725),
+              (20, 10.0, 1.1513369247813398e-05), (-3, 2.0, -0.12894324947440206)];
+    for &(n, x, expected) in jn.iter() {
+        assert!((scalar::jn(n, x) - expected).abs() < 1e-12 * expected.abs());
+    }
+}
+
+#[test]
+fn elementary() {
+    assert!(scalar::expit(0.0) == 0.5);
+    assert!((scalar::expit(-800.0)).abs() < 1e-300 && scalar::expit(800.0) == 1.0);
+    assert!((scalar::logit(scalar::expit(2.5)) - 2.5).abs() < 1e-14);
+    assert!(scalar::xlogy(0.0, 0.0) == 0.0);
+    assert!((scalar::xlogy(2.0, 3.0) - 2.0 * 3.0_f64.ln()).abs() < 1e-15);
+    assert!(scalar::sinc(0.0) == 1.0);
+    assert!(scalar::sinc(1.0).abs() < 1e-15);
+    assert!((scalar::sinc(0.5) - 2.0 / ::std::f64::consts::PI).abs() < 1e-15);
+}
+
+#[test]
+fn tensors() {
+    let x: Tensor<f64> = Tensor::new(vec![0.5, 1.0, 5.0]).reshape(&[3, 1]);
+    let y = special::gamma(x);
+    assert!(y.shape() == &vec![3, 1]);
+    assert!((y[(2, 0)] - 24.0).abs() < 1e-12);
+
+    let x32: Tensor<f32> = Tensor::new(vec![0.0, 1.0]);
+    let y32 = special::erf(x32);
+    assert!((y32[(1,)] - 0.84270079).abs() < 1e-7);
+
+    let a: Tensor<f64> = Tensor::new(vec![1.0, 2.0]);
+    let b: Tensor<f64> = Tensor::new(vec![1.0, 3.0]);
+    let x: Tensor<f64> = Tensor::new(vec![0.3, 0.4]);
+    let z = special::betainc(&a, &b, &x);
+    assert!((z[(0,)] - 0.3).abs() < 1e-14 && (z[(1,)] - 0.5248).abs() < 1e-14);
+    assert!((special::beta(&a, &b)[(1,)] - 1.0 / 12.0).abs() < 1e-15);
+    assert!((special::jn(2, Tensor::new(vec![1.0_f64]))[(0,)] - 0.11490348493190047).abs() < 1e-15);
+}